@@ -0,0 +1,137 @@
+use crate::{job_name, load_input, Job, DAYS};
+use common::bench::{Stats, Timings};
+use common::{input_name, Params};
+use std::fs::write;
+use std::path::Path;
+use std::time::Duration;
+
+// day, input file and the measured timings
+type BenchResult = (usize, String, Timings);
+
+/// Benchmarks all jobs, prints the results and writes them to a report file. The report is
+/// tab-separated with one line per day, input and phase, so reports of different commits can
+/// be compared with diff.
+pub fn bench_jobs(
+    jobs: &[Job],
+    params: &Params,
+    iterations: usize,
+    report_path: &Path,
+) -> Result<(), String> {
+    let mut results: Vec<BenchResult> = Vec::with_capacity(jobs.len());
+    let mut failed_jobs: Vec<String> = Vec::with_capacity(jobs.len());
+    for (day, input_path) in jobs {
+        let name = job_name(*day, input_path.as_deref());
+        println!("{}, {} iterations", name, iterations);
+        match bench_day(*day, input_path.as_deref(), params, iterations) {
+            Ok(timings) => {
+                print_timings(&timings);
+                let input = match input_path {
+                    Some(path) => input_name(path),
+                    None => "-".to_owned(),
+                };
+                results.push((*day, input, timings));
+            }
+            Err(e) => {
+                println!("{} failed:\n{}", name, e);
+                failed_jobs.push(name);
+            }
+        }
+    }
+
+    write(report_path, render_report(&results)).map_err(|e| {
+        format!(
+            "Unable to write report to '{}': {}",
+            report_path.display(),
+            e
+        )
+    })?;
+    println!("Report written to {}", report_path.display());
+
+    if failed_jobs.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed: {}", failed_jobs.join(", ")))
+    }
+}
+
+fn bench_day(
+    day: usize,
+    input_path: Option<&Path>,
+    params: &Params,
+    iterations: usize,
+) -> Result<Timings, String> {
+    let (content, filename) = load_input(input_path, params)?;
+    (DAYS[day - 1].bench)(&content, params, iterations)
+        .map_err(|e| e.in_file(&filename).to_string())
+}
+
+fn phases(timings: &Timings) -> Vec<(&'static str, &[Duration])> {
+    let mut phases: Vec<(&str, &[Duration])> =
+        vec![("parse", &timings.parse), ("part1", &timings.part1)];
+    if let Some(part2) = &timings.part2 {
+        phases.push(("part2", part2));
+    }
+    phases
+}
+
+fn print_timings(timings: &Timings) {
+    for (phase, measurements) in phases(timings) {
+        if let Some(stats) = Stats::of(measurements) {
+            println!(
+                "    {:<6} min {:>12?}  median {:>12?}  max {:>12?}",
+                phase, stats.min, stats.median, stats.max
+            );
+        }
+    }
+}
+
+fn render_report(results: &[BenchResult]) -> String {
+    let mut report = String::from("# day\tinput\tphase\titerations\tmin_ns\tmedian_ns\tmax_ns\n");
+    for (day, input, timings) in results {
+        for (phase, measurements) in phases(timings) {
+            if let Some(stats) = Stats::of(measurements) {
+                report.push_str(&format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                    day,
+                    input,
+                    phase,
+                    measurements.len(),
+                    stats.min.as_nanos(),
+                    stats.median.as_nanos(),
+                    stats.max.as_nanos()
+                ));
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_report_writes_one_line_per_day_input_and_phase() {
+        // given
+        let results = vec![(
+            25,
+            "day-25.txt".to_owned(),
+            Timings {
+                parse: vec![Duration::from_nanos(30), Duration::from_nanos(10)],
+                part1: vec![Duration::from_micros(2), Duration::from_micros(1)],
+                part2: None,
+            },
+        )];
+
+        // when
+        let report = render_report(&results);
+
+        // then
+        assert_eq!(
+            report,
+            "# day\tinput\tphase\titerations\tmin_ns\tmedian_ns\tmax_ns\n\
+             25\tday-25.txt\tparse\t2\t10\t30\t30\n\
+             25\tday-25.txt\tpart1\t2\t1000\t2000\t2000\n"
+        );
+    }
+}
@@ -0,0 +1,566 @@
+use common::bench::{bench, Timings};
+use common::fuzz::{fuzz_solve, Failure};
+use common::generate::{check_planted, Generated, Generator, Rng};
+use common::parallel;
+use common::trace::{record, Frame, Trace};
+use common::{
+    input_name, print_answers, read_input, run_mode, solve, Answers, Mode, Params, ParseError,
+    Solution,
+};
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+
+mod benchmark;
+mod records;
+mod serve;
+mod trace;
+mod verify;
+
+const USAGE: &str = r"Usage:
+    aoc run <day> <input file>... [--<parameter> <value>...] [--format text|json]
+            [--bench [--iterations <n>] [--report <file>]]
+            [--trace <directory> [--trace-format ascii|svg]] [--jobs <n>]
+    aoc run all --inputs <directory> [--format text|json] [--bench ...] [--jobs <n>]
+    aoc verify [--answers <answers file>]
+    aoc gen <day> [--seed <n>] [--size <n>] [--check]
+    aoc fuzz <day>|all [--seed <n>] [--iterations <n>]
+    aoc serve [--address <host:port>] [--time-limit <ms>] [--memory-limit <MB>]
+            [--input-limit <kB>]
+
+An input file '-' reads the input from stdin. Each input file is solved on its own.
+When running all days, the input for day n is expected at <directory>/day-nn.txt
+Other options are parameters for the day, e.g. --start 0,3,6 --turns1 10 for day 15. If there
+are parameters, the input file can be left out. Some days have other modes than solving the
+puzzle, selected by a flag: --stream for day 1, --audit for day 2, --search, --render and
+--route for day 3, --diagnose for day 4. Days 1, 10, 13, 14 and 18 fail on overflow unless run with --arithmetic big. --policy <file> checks the passwords of day 2 against the rules in the
+file. --slope and --slopes set the slopes of day 3, e.g. --slopes 1/1,3/2. --schema <file> validates
+the passports of day 4 against another schema.
+--format json prints one JSON record per line for each part of each day
+--bench times parsing and both parts over n iterations (default: 10) instead of printing the
+answers, and writes a report to the given file (default: bench-report.tsv)
+--trace records each step of days 11, 12, 17, 22 and 24 and writes the frames to the directory,
+as one text file per part or as one SVG image per frame
+--jobs runs on up to n threads (default: 1): the days of 'run all', the repairs of day 8, the
+messages of day 19 and the cells of days 11, 17 and 24. The answers do not change.
+verify checks the answers for the inputs listed in the answers file (default: answers.toml)
+gen prints a random input for the day (default: seed 0, size 100) and the answers planted in it
+to stderr, --check solves the input and compares the answers
+fuzz solves mutated generated inputs (default: seed 0, 1000 iterations) and reports the inputs
+that make the day panic
+serve answers POST /day/<n> requests with the input as body on the address (default:
+127.0.0.1:8020) with the JSON records of --format json. Parameters for the day go into the query,
+e.g. /day/15?turns2=1000. Each request may take 10000 ms, use 1024 MB and send 1024 kB by default.";
+
+struct Day {
+    /// the parameters of the day, see `Solution::PARAMS`
+    params: &'static [&'static str],
+    /// the parameters that name a file, see `Solution::FILE_PARAMS`
+    file_params: &'static [&'static str],
+    /// see `Solution::MODES`
+    modes: &'static [Mode],
+    solve: fn(&str, &Params) -> Result<Answers, ParseError>,
+    bench: fn(&str, &Params, usize) -> Result<Timings, ParseError>,
+    generate: fn(&mut Rng, usize) -> Generated,
+    check: fn(&Generated) -> Result<Vec<String>, String>,
+    fuzz: fn(u64, usize) -> Vec<Failure>,
+    /// `None` for days that have no steps worth recording
+    trace: Option<TraceFn>,
+}
+
+type TraceFn = fn(&str, &Params) -> Result<Vec<Result<Vec<Frame>, String>>, ParseError>;
+
+const fn day<S: Solution + Generator>() -> Day {
+    Day {
+        params: S::PARAMS,
+        file_params: S::FILE_PARAMS,
+        modes: S::MODES,
+        solve: solve::<S>,
+        bench: bench::<S>,
+        generate: S::generate,
+        check: check_planted::<S>,
+        fuzz: fuzz_solve::<S>,
+        trace: None,
+    }
+}
+
+const fn traced_day<S: Solution + Generator + Trace>() -> Day {
+    Day {
+        trace: Some(record::<S>),
+        ..day::<S>()
+    }
+}
+
+const DAYS: [Day; 25] = [
+    day::<day_01::Day01>(),
+    day::<day_02::Day02>(),
+    day::<day_03::Day03>(),
+    day::<day_04::Day04>(),
+    day::<day_05::Day05>(),
+    day::<day_06::Day06>(),
+    day::<day_07::Day07>(),
+    day::<day_08::Day08>(),
+    day::<day_09::Day09>(),
+    day::<day_10::Day10>(),
+    traced_day::<day_11::Day11>(),
+    traced_day::<day_12::Day12>(),
+    day::<day_13::Day13>(),
+    day::<day_14::Day14>(),
+    day::<day_15::Day15>(),
+    day::<day_16::Day16>(),
+    traced_day::<day_17::Day17>(),
+    day::<day_18::Day18>(),
+    day::<day_19::Day19>(),
+    day::<day_20::Day20>(),
+    day::<day_21::Day21>(),
+    traced_day::<day_22::Day22>(),
+    day::<day_23::Day23>(),
+    traced_day::<day_24::Day24>(),
+    day::<day_25::Day25>(),
+];
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(|s| s.as_str()) {
+        Some("run") => run_command(&args[1..]),
+        Some("verify") => verify_command(&args[1..]),
+        Some("gen") => gen_command(&args[1..]),
+        Some("fuzz") => fuzz_command(&args[1..]),
+        Some("serve") => serve_command(&args[1..]),
+        Some(command) => Err(format!("Unknown command '{}'\n{}", command, USAGE)),
+        None => Err(USAGE.to_owned()),
+    };
+    // print errors with Display, parse errors contain multi-line snippets of the input
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+/// The options of `aoc run`, all other options are parameters for the day
+const RUN_OPTIONS: &[&str] = &[
+    "--inputs",
+    "--format",
+    "--bench",
+    "--iterations",
+    "--report",
+    "--trace",
+    "--trace-format",
+    "--jobs",
+];
+
+// the day and the input file to run it on
+type Job = (usize, Option<PathBuf>);
+
+fn run_command(args: &[String]) -> Result<(), String> {
+    // a mode of the day, like `aoc run 2 input.txt --audit`, replaces solving the puzzle
+    if let Some(day) = args.first().and_then(|day| parse_day(day).ok()) {
+        if let Some(result) = run_mode(DAYS[day - 1].modes, &args[1..]) {
+            return result;
+        }
+    }
+    let (positional, mut options) = parse_options(args, &["--bench"])?;
+    let params = take_params(&mut options, RUN_OPTIONS);
+    parallel::set_jobs(parse_jobs(&options)?);
+    let json = match options.get("--format") {
+        None | Some(&"text") => false,
+        Some(&"json") => true,
+        Some(format) => return Err(format!("Unknown format '{}'\n{}", format, USAGE)),
+    };
+
+    let jobs: Vec<Job> = match positional.as_slice() {
+        ["all"] => {
+            let input_dir = options.get("--inputs").ok_or_else(|| {
+                format!("Running all days requires an input directory.\n{}", USAGE)
+            })?;
+            (1..=DAYS.len())
+                .map(|day| {
+                    let input_path = Path::new(input_dir).join(format!("day-{:02}.txt", day));
+                    (day, Some(input_path))
+                })
+                .collect()
+        }
+        [day] => vec![(parse_day(day)?, None)],
+        [day, files @ ..] => {
+            let day = parse_day(day)?;
+            files
+                .iter()
+                .map(|file| (day, Some(PathBuf::from(file))))
+                .collect()
+        }
+        [] => return Err(format!("No day given.\n{}", USAGE)),
+    };
+
+    if let Some(directory) = options.get("--trace") {
+        let format = match options.get("--trace-format") {
+            None | Some(&"ascii") => trace::TraceFormat::Ascii,
+            Some(&"svg") => trace::TraceFormat::Svg,
+            Some(format) => return Err(format!("Unknown trace format '{}'\n{}", format, USAGE)),
+        };
+        if json || options.contains_key("--bench") {
+            return Err("--trace can not be combined with --bench or --format json".to_owned());
+        }
+        return match jobs.as_slice() {
+            [job] => trace::trace_job(job, &params, Path::new(directory), format),
+            _ => Err("--trace needs a single day and input file".to_owned()),
+        };
+    }
+
+    if options.contains_key("--bench") {
+        if json {
+            return Err("--bench has no JSON output, see --report instead".to_owned());
+        }
+        let iterations = match options.get("--iterations") {
+            Some(n) => n
+                .parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| format!("Invalid number of iterations '{}'", n))?,
+            None => 10,
+        };
+        let report = Path::new(options.get("--report").unwrap_or(&"bench-report.tsv"));
+        benchmark::bench_jobs(&jobs, &params, iterations, report)
+    } else if json {
+        records::print_records(&jobs, &params)
+    } else {
+        run_jobs(&jobs, &params)
+    }
+}
+
+fn verify_command(args: &[String]) -> Result<(), String> {
+    let (positional, options) = parse_options(args, &[])?;
+    check_options(&options, &["--answers"])?;
+    if !positional.is_empty() {
+        return Err(format!("Too many arguments.\n{}", USAGE));
+    }
+    verify::verify(Path::new(
+        options.get("--answers").unwrap_or(&"answers.toml"),
+    ))
+}
+
+fn gen_command(args: &[String]) -> Result<(), String> {
+    let (positional, options) = parse_options(args, &["--check"])?;
+    check_options(&options, &["--seed", "--size", "--check"])?;
+    let day = match positional.as_slice() {
+        [day] => parse_day(day)?,
+        [] => return Err(format!("No day given.\n{}", USAGE)),
+        _ => return Err(format!("Too many arguments.\n{}", USAGE)),
+    };
+    let seed = parse_option(&options, "--seed", 0)?;
+    let size = parse_option(&options, "--size", 100)?;
+
+    let generated = (DAYS[day - 1].generate)(&mut Rng::new(seed), size);
+    print!("{}", generated.input);
+    for (part, planted) in [(1, &generated.part1), (2, &generated.part2)] {
+        eprintln!(
+            "Part {}: {}",
+            part,
+            planted.as_deref().unwrap_or("(not planted)")
+        );
+    }
+
+    if options.contains_key("--check") {
+        let mismatches = (DAYS[day - 1].check)(&generated)?;
+        if !mismatches.is_empty() {
+            return Err(format!("Check failed:\n{}", mismatches.join("\n")));
+        }
+        eprintln!("Check passed.");
+    }
+    Ok(())
+}
+
+fn fuzz_command(args: &[String]) -> Result<(), String> {
+    let (positional, options) = parse_options(args, &[])?;
+    check_options(&options, &["--seed", "--iterations"])?;
+    let days: Vec<usize> = match positional.as_slice() {
+        ["all"] => (1..=DAYS.len()).collect(),
+        [day] => vec![parse_day(day)?],
+        [] => return Err(format!("No day given.\n{}", USAGE)),
+        _ => return Err(format!("Too many arguments.\n{}", USAGE)),
+    };
+    let seed = parse_option(&options, "--seed", 0)?;
+    let iterations = parse_option(&options, "--iterations", 1000)?;
+
+    // the panics are expected and reported below, don't print each of them
+    std::panic::set_hook(Box::new(|_| ()));
+    let mut failed_days: Vec<String> = Vec::new();
+    for day in days {
+        let failures = (DAYS[day - 1].fuzz)(seed, iterations);
+        if failures.is_empty() {
+            println!("Day {}: ok", day);
+            continue;
+        }
+        println!("Day {}: {} different panics", day, failures.len());
+        for failure in failures {
+            println!("  input {:?} panics: {}", failure.input, failure.message);
+        }
+        failed_days.push(day.to_string());
+    }
+    let _ = std::panic::take_hook();
+
+    if failed_days.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Parsers panic for days {}", failed_days.join(", ")))
+    }
+}
+
+fn serve_command(args: &[String]) -> Result<(), String> {
+    let (positional, options) = parse_options(args, &[])?;
+    check_options(
+        &options,
+        &[
+            "--address",
+            "--time-limit",
+            "--memory-limit",
+            "--input-limit",
+        ],
+    )?;
+    if !positional.is_empty() {
+        return Err(format!("Too many arguments.\n{}", USAGE));
+    }
+    let limits = serve::Limits {
+        time: Duration::from_millis(parse_option(&options, "--time-limit", 10_000)?),
+        memory: parse_option::<u64>(&options, "--memory-limit", 1024)? * 1024 * 1024,
+        input: parse_option::<usize>(&options, "--input-limit", 1024)? * 1024,
+    };
+    serve::serve(
+        options.get("--address").unwrap_or(&"127.0.0.1:8020"),
+        limits,
+    )
+}
+
+fn parse_option<T: std::str::FromStr>(
+    options: &HashMap<&str, &str>,
+    name: &str,
+    default: T,
+) -> Result<T, String> {
+    match options.get(name) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("Invalid value '{}' for {}", value, name)),
+        None => Ok(default),
+    }
+}
+
+/// The number of threads given with `--jobs`, 1 if there is none
+fn parse_jobs(options: &HashMap<&str, &str>) -> Result<usize, String> {
+    match options.get("--jobs") {
+        Some(n) => n
+            .parse::<usize>()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| format!("Invalid number of jobs '{}'", n)),
+        None => Ok(1),
+    }
+}
+
+/// Splits the arguments into positional arguments and `--option value` pairs. `flags` are
+/// options without a value, they are mapped to an empty string.
+fn parse_options<'a>(
+    args: &'a [String],
+    flags: &[&str],
+) -> Result<(Vec<&'a str>, HashMap<&'a str, &'a str>), String> {
+    let mut positional = Vec::with_capacity(args.len());
+    let mut options = HashMap::with_capacity(args.len());
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if !arg.starts_with("--") {
+            positional.push(arg.as_str());
+            continue;
+        }
+        let value = if flags.contains(&arg.as_str()) {
+            ""
+        } else {
+            iter.next()
+                .ok_or_else(|| format!("Option '{}' requires a value", arg))?
+        };
+        if options.insert(arg.as_str(), value).is_some() {
+            return Err(format!("Option '{}' is given more than once", arg));
+        }
+    }
+    Ok((positional, options))
+}
+
+fn check_options(options: &HashMap<&str, &str>, known: &[&str]) -> Result<(), String> {
+    match options.keys().find(|option| !known.contains(option)) {
+        Some(option) => Err(format!("Unknown option '{}'\n{}", option, USAGE)),
+        None => Ok(()),
+    }
+}
+
+/// Removes all options that are not in `known` and turns them into parameters for the day.
+fn take_params(options: &mut HashMap<&str, &str>, known: &[&str]) -> Params {
+    let mut params = Params::new();
+    options.retain(|option, value| {
+        if known.contains(option) {
+            return true;
+        }
+        params.insert(option.trim_start_matches("--"), value);
+        false
+    });
+    params
+}
+
+fn parse_day(arg: &str) -> Result<usize, String> {
+    arg.parse::<usize>()
+        .ok()
+        .filter(|day| *day >= 1 && *day <= DAYS.len())
+        .ok_or_else(|| format!("Invalid day '{}', expected a number from 1 to 25", arg))
+}
+
+fn run_jobs(jobs: &[Job], params: &Params) -> Result<(), String> {
+    if let [(day, input_path)] = jobs {
+        return run_day(*day, input_path.as_deref(), params);
+    }
+
+    // the jobs may be solved in parallel, but their answers are printed in order
+    let results = parallel::par_map(jobs, |(day, input_path)| {
+        solve_day(*day, input_path.as_deref(), params)
+    });
+    let mut failed_jobs: Vec<String> = Vec::with_capacity(jobs.len());
+    for ((day, input_path), result) in jobs.iter().zip(results) {
+        let name = job_name(*day, input_path.as_deref());
+        println!("{}", name);
+        match result {
+            Ok(answers) => print_answers(&answers),
+            Err(e) => {
+                println!("{} failed:\n{}", name, e);
+                failed_jobs.push(name);
+            }
+        }
+    }
+
+    if failed_jobs.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed: {}", failed_jobs.join(", ")))
+    }
+}
+
+fn run_day(day: usize, input_path: Option<&Path>, params: &Params) -> Result<(), String> {
+    let answers = solve_day(day, input_path, params).map_err(|e| e.to_string())?;
+    print_answers(&answers);
+    Ok(())
+}
+
+/// Why a day could not be solved at all
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum DayError {
+    Input(String),
+    Parse(ParseError),
+}
+
+impl fmt::Display for DayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DayError::Input(e) => write!(f, "{}", e),
+            DayError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+fn solve_day(day: usize, input_path: Option<&Path>, params: &Params) -> Result<Answers, DayError> {
+    let (content, filename) = load_input(input_path, params).map_err(DayError::Input)?;
+    (DAYS[day - 1].solve)(&content, params).map_err(|e| DayError::Parse(e.in_file(&filename)))
+}
+
+/// Reads the input file. Returns the input and the file name for error messages. Without an input
+/// file, the input is empty if there are parameters that can take its place.
+fn load_input(input_path: Option<&Path>, params: &Params) -> Result<(String, String), String> {
+    match input_path {
+        Some(input_path) => Ok((read_input(input_path)?, input_name(input_path))),
+        None if !params.is_empty() => Ok((String::new(), "<none>".to_owned())),
+        None => Err("No file name given.".to_owned()),
+    }
+}
+
+/// A header for the output of a job, with the input file if there is one
+fn job_name(day: usize, input_path: Option<&Path>) -> String {
+    match input_path {
+        Some(path) => format!("Day {} ({})", day, input_name(path)),
+        None => format!("Day {}", day),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_day_accepts_valid_days() {
+        assert_eq!(parse_day("1"), Ok(1));
+        assert_eq!(parse_day("25"), Ok(25));
+    }
+
+    #[test]
+    fn parse_day_rejects_invalid_days() {
+        assert!(parse_day("0").is_err());
+        assert!(parse_day("26").is_err());
+        assert!(parse_day("seven").is_err());
+    }
+
+    #[test]
+    fn parse_options_separates_options_from_positional_arguments() {
+        // given
+        let args: Vec<String> = ["7", "--bench", "input.txt", "--iterations", "3"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        // when
+        let result = parse_options(&args, &["--bench"]);
+
+        // then
+        let (positional, options) = result.expect("Expected valid options");
+        assert_eq!(positional, vec!["7", "input.txt"]);
+        assert_eq!(options.len(), 2);
+        assert_eq!(options.get("--bench"), Some(&""));
+        assert_eq!(options.get("--iterations"), Some(&"3"));
+    }
+
+    #[test]
+    fn take_params_keeps_known_options() {
+        // given
+        let args: Vec<String> = ["15", "--start", "0,3,6", "--format", "json"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let (_, mut options) = parse_options(&args, &[]).expect("Expected valid options");
+
+        // when
+        let params = take_params(&mut options, &["--format"]);
+
+        // then
+        assert_eq!(options.len(), 1);
+        assert_eq!(options.get("--format"), Some(&"json"));
+        assert_eq!(params.get_str("start"), Some("0,3,6"));
+    }
+
+    #[test]
+    fn parse_jobs_rejects_zero_jobs() {
+        // given
+        let options: HashMap<&str, &str> = [("--jobs", "0")].iter().copied().collect();
+
+        // when
+        let result = parse_jobs(&options);
+
+        // then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_options_fails_for_missing_value() {
+        // given
+        let args = vec!["all".to_owned(), "--inputs".to_owned()];
+
+        // when
+        let result = parse_options(&args, &[]);
+
+        // then
+        assert!(result.is_err());
+    }
+}
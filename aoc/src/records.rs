@@ -0,0 +1,180 @@
+use crate::{job_name, solve_day, DayError, Job};
+use common::json::Json;
+use common::parallel;
+use common::{input_name, Answers, Params, Part};
+use std::path::Path;
+
+/// Solves all jobs and prints one JSON record per line for each part:
+///
+/// ```json
+/// {"day":1,"input":"input.txt","part":1,"answer":"514579","elapsed_ms":0.01,"warnings":[],"error":null}
+/// ```
+///
+/// If a part fails, `answer` is null and `error` describes the failure. If the input can not be
+/// read or parsed, there is a single record for the day with `part` set to null.
+pub fn print_records(jobs: &[Job], params: &Params) -> Result<(), String> {
+    let results = parallel::par_map(jobs, |(day, input_path)| {
+        solve_day(*day, input_path.as_deref(), params)
+    });
+    let mut failed_jobs: Vec<String> = Vec::with_capacity(jobs.len());
+    for ((day, input_path), result) in jobs.iter().zip(results) {
+        if result.is_err() {
+            failed_jobs.push(job_name(*day, input_path.as_deref()));
+        }
+        for record in day_records(*day, input_path.as_deref(), &result) {
+            println!("{}", record);
+        }
+    }
+
+    if failed_jobs.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed: {}", failed_jobs.join(", ")))
+    }
+}
+
+fn day_records(
+    day: usize,
+    input_path: Option<&Path>,
+    result: &Result<Answers, DayError>,
+) -> Vec<Json> {
+    let input = Json::from(input_path.map(input_name));
+    match result {
+        Ok(answers) => {
+            let mut records = vec![part_record(day, &input, 1, &answers.part1)];
+            if let Some(part2) = &answers.part2 {
+                records.push(part_record(day, &input, 2, part2));
+            }
+            records
+        }
+        Err(e) => vec![Json::object(vec![
+            ("day", Json::from(day)),
+            ("input", input),
+            ("part", Json::Null),
+            ("answer", Json::Null),
+            ("elapsed_ms", Json::Null),
+            ("warnings", Json::Array(vec![])),
+            ("error", error_record(e)),
+        ])],
+    }
+}
+
+fn part_record(day: usize, input: &Json, part_number: usize, part: &Part) -> Json {
+    let (answer, error) = match &part.answer {
+        Ok(answer) => (Json::from(answer.as_str()), Json::Null),
+        Err(e) => (
+            Json::Null,
+            Json::object(vec![
+                ("kind", Json::from("part_failed")),
+                ("message", Json::from(e.as_str())),
+            ]),
+        ),
+    };
+    Json::object(vec![
+        ("day", Json::from(day)),
+        ("input", input.clone()),
+        ("part", Json::from(part_number)),
+        ("answer", answer),
+        (
+            "elapsed_ms",
+            // rounded to microseconds, so the records carry no float noise
+            Json::from((part.elapsed.as_nanos() as f64 / 1000.0).round() / 1000.0),
+        ),
+        ("warnings", Json::from(part.warnings.clone())),
+        ("error", error),
+    ])
+}
+
+fn error_record(error: &DayError) -> Json {
+    match error {
+        DayError::Input(e) => Json::object(vec![
+            ("kind", Json::from("input")),
+            ("message", Json::from(e.as_str())),
+        ]),
+        DayError::Parse(e) => {
+            let location = e.location();
+            Json::object(vec![
+                ("kind", Json::from(e.kind().name())),
+                ("message", Json::from(e.kind().to_string())),
+                ("file", Json::from(location.and_then(|l| l.file.clone()))),
+                ("line", Json::from(location.map(|l| l.line))),
+                ("column", Json::from(location.map(|l| l.column))),
+            ])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::ParseError;
+    use std::time::Duration;
+
+    #[test]
+    fn day_records_writes_one_record_per_part() {
+        // given
+        let result = Ok(Answers {
+            part1: Part {
+                answer: Ok("295".to_owned()),
+                elapsed: Duration::from_micros(1500),
+                warnings: vec![],
+            },
+            part2: Some(Part {
+                answer: Err("Unable to find departure sequence".to_owned()),
+                elapsed: Duration::from_millis(2),
+                warnings: vec!["no buses".to_owned()],
+            }),
+        });
+
+        // when
+        let records = day_records(13, Some(Path::new("day-13.txt")), &result);
+
+        // then
+        let rendered: Vec<String> = records.iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                r#"{"day":13,"input":"day-13.txt","part":1,"answer":"295","elapsed_ms":1.5,"warnings":[],"error":null}"#,
+                r#"{"day":13,"input":"day-13.txt","part":2,"answer":null,"elapsed_ms":2,"warnings":["no buses"],"error":{"kind":"part_failed","message":"Unable to find departure sequence"}}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn part_record_rounds_elapsed_time_to_microseconds() {
+        // given
+        let part = Part {
+            answer: Ok("1".to_owned()),
+            elapsed: Duration::from_nanos(10_499),
+            warnings: vec![],
+        };
+
+        // when
+        let record = part_record(1, &Json::Null, 1, &part).to_string();
+
+        // then
+        assert!(record.contains(r#""elapsed_ms":0.01,"#), "{}", record);
+    }
+
+    #[test]
+    fn day_records_reports_location_of_parse_errors() {
+        // given
+        let input = "nop +0\njmp +banana\n";
+        let error = ParseError::unexpected_token(&input[11..18], "a number")
+            .locate(input)
+            .in_file("day-08.txt");
+        let result = Err(DayError::Parse(error));
+
+        // when
+        let records = day_records(8, Some(Path::new("day-08.txt")), &result);
+
+        // then
+        let rendered: Vec<String> = records.iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                r#"{"day":8,"input":"day-08.txt","part":null,"answer":null,"elapsed_ms":null,"warnings":[],"error":{"kind":"unexpected_token","message":"unexpected '+banana', expected a number","file":"day-08.txt","line":2,"column":5}}"#
+            ]
+        );
+    }
+}
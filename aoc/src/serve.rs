@@ -0,0 +1,525 @@
+use crate::DAYS;
+use common::json::Json;
+use std::fs::read_to_string;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a running solver is checked against the limits
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+/// Requests with longer headers are rejected
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+/// How long a client may take to send more of its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What a single request may use
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Limits {
+    pub time: Duration,
+    /// the largest resident memory of the solver in bytes
+    pub memory: u64,
+    /// the largest accepted puzzle input in bytes
+    pub input: usize,
+}
+
+/// Answers `POST /day/{n}` requests with the puzzle input as body. Each request is solved by a
+/// separate `aoc run <n> - --format json` process, which is killed if it exceeds the limits. The
+/// response is a JSON array with the records of `--format json`.
+pub fn serve(address: &str, limits: Limits) -> Result<(), String> {
+    let listener = TcpListener::bind(address)
+        .map_err(|e| format!("Unable to listen on {}: {}", address, e))?;
+    let exe =
+        std::env::current_exe().map_err(|e| format!("Unable to find the aoc executable: {}", e))?;
+    println!("Listening on http://{}", address);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let exe = exe.clone();
+                thread::spawn(move || {
+                    if let Err(e) = handle_connection(stream, &exe, limits) {
+                        eprintln!("Connection failed: {}", e);
+                    }
+                });
+            }
+            Err(e) => eprintln!("Unable to accept connection: {}", e),
+        }
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, exe: &Path, limits: Limits) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let response = match read_request(&mut reader, limits.input) {
+        Ok(request) => respond(&request, exe, limits),
+        Err(response) => response,
+    };
+    write_response(stream, &response)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, kind: &str, message: impl Into<String>) -> Response {
+        let record = Json::object(vec![(
+            "error",
+            Json::object(vec![
+                ("kind", Json::from(kind)),
+                ("message", Json::from(message.into())),
+            ]),
+        )]);
+        Response {
+            status,
+            body: record.to_string(),
+        }
+    }
+}
+
+/// Reads the request line, the headers and a body of `Content-Length` bytes. Fails with the
+/// response to send if the request is malformed or too large.
+fn read_request(reader: &mut impl BufRead, max_body: usize) -> Result<Request, Response> {
+    let bad_request = |message: &str| Response::error(400, "bad_request", message);
+    let mut header_bytes = 0;
+    let mut read_line = |reader: &mut dyn BufRead| -> Result<String, Response> {
+        let mut line = String::new();
+        reader
+            .take((MAX_HEADER_BYTES - header_bytes) as u64)
+            .read_line(&mut line)
+            .map_err(|_| bad_request("Unable to read request"))?;
+        header_bytes += line.len();
+        if !line.ends_with('\n') {
+            return Err(bad_request("Request header is incomplete or too long"));
+        }
+        Ok(line.trim_end().to_owned())
+    };
+
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split(' ');
+    let (method, path) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(path), Some(version)) if version.starts_with("HTTP/1.") => {
+            (method.to_owned(), path.to_owned())
+        }
+        _ => return Err(bad_request("Invalid request line")),
+    };
+
+    let mut content_length = 0;
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| bad_request("Invalid header"))?;
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .trim()
+                .parse()
+                .map_err(|_| bad_request("Invalid Content-Length"))?;
+        }
+    }
+    if content_length > max_body {
+        return Err(Response::error(
+            413,
+            "input_too_large",
+            format!(
+                "The input has {} bytes, at most {} are allowed",
+                content_length, max_body
+            ),
+        ));
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad_request("The body is shorter than its Content-Length"))?;
+    let body = String::from_utf8(body).map_err(|_| bad_request("The input is not UTF-8"))?;
+    Ok(Request { method, path, body })
+}
+
+fn respond(request: &Request, exe: &Path, limits: Limits) -> Response {
+    let (day, params) = match parse_path(&request.path) {
+        Ok(route) => route,
+        Err(response) => return response,
+    };
+    if request.method != "POST" {
+        return Response::error(405, "method_not_allowed", "Send the puzzle input with POST");
+    }
+
+    let mut command = Command::new(exe);
+    command.args(["run", &day.to_string(), "-", "--format", "json"]);
+    for (name, value) in &params {
+        command.arg(format!("--{}", name)).arg(value);
+    }
+    match run_limited(command, &request.body, limits) {
+        Ok((status, output, errors)) => {
+            let records: Vec<&str> = output.lines().filter(|l| !l.is_empty()).collect();
+            if records.is_empty() {
+                // without records, the solver did not get as far as the input, e.g. because of
+                // invalid parameters
+                return match errors.trim() {
+                    _ if status.success() => {
+                        Response::error(500, "internal", "The solver printed no answers")
+                    }
+                    "" => {
+                        Response::error(400, "bad_request", "The solver failed without a message")
+                    }
+                    errors => Response::error(400, "bad_request", errors),
+                };
+            }
+            Response {
+                // a failed run is a day whose input could not be read or parsed
+                status: if status.success() { 200 } else { 422 },
+                body: format!("[{}]", records.join(",")),
+            }
+        }
+        Err(Exceeded::Time) => Response::error(
+            503,
+            "time_limit",
+            format!(
+                "Day {} took longer than {} ms",
+                day,
+                limits.time.as_millis()
+            ),
+        ),
+        Err(Exceeded::Memory) => Response::error(
+            503,
+            "memory_limit",
+            format!(
+                "Day {} used more than {} MB",
+                day,
+                limits.memory / (1024 * 1024)
+            ),
+        ),
+        Err(Exceeded::Failed(e)) => Response::error(500, "internal", e),
+    }
+}
+
+/// Splits `/day/{n}?name=value&…` into the day and its parameters
+fn parse_path(path: &str) -> Result<(usize, Vec<(String, String)>), Response> {
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let day = path
+        .strip_prefix("/day/")
+        .and_then(|day| day.parse::<usize>().ok())
+        .filter(|day| *day >= 1 && *day <= DAYS.len())
+        .ok_or_else(|| {
+            Response::error(
+                404,
+                "not_found",
+                format!("Unknown path '{}', expected /day/1 to /day/25", path),
+            )
+        })?;
+    // only the parameters of the day are passed on, except those that would read a file
+    let (params, file_params) = (DAYS[day - 1].params, DAYS[day - 1].file_params);
+    let params = query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            let (name, value) = param
+                .split_once('=')
+                .filter(|(name, _)| !name.is_empty())
+                .ok_or_else(|| format!("Invalid parameter '{}', expected name=value", param))?;
+            let (name, value) = (percent_decode(name)?, percent_decode(value)?);
+            if file_params.contains(&name.as_str()) {
+                Err(format!(
+                    "'{}' names a file, which is not supported when serving",
+                    name
+                ))
+            } else if params.contains(&name.as_str()) {
+                Ok((name, value))
+            } else {
+                Err(format!("'{}' is not a parameter of day {}", name, day))
+            }
+        })
+        .collect::<Result<_, _>>()
+        .map_err(|message| Response::error(400, "bad_request", message))?;
+    Ok((day, params))
+}
+
+// Decodes the `%XX` escapes of a query, the result has to be UTF-8
+fn percent_decode(text: &str) -> Result<String, String> {
+    let invalid = || format!("Invalid percent encoding in '{}'", text);
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2).ok_or_else(invalid)?;
+            let hex = std::str::from_utf8(hex).map_err(|_| invalid())?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// Why a solver was stopped
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Exceeded {
+    Time,
+    Memory,
+    /// the solver could not be started or did not finish normally
+    Failed(String),
+}
+
+/// Runs the command with the input on stdin and returns its exit status, stdout and stderr. Kills
+/// it when it runs longer or uses more memory than allowed.
+fn run_limited(
+    mut command: Command,
+    input: &str,
+    limits: Limits,
+) -> Result<(ExitStatus, String, String), Exceeded> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Exceeded::Failed(format!("Unable to start the solver: {}", e)))?;
+
+    // feed and drain the pipes on their own threads, so a full pipe can not block the solver
+    let mut stdin = child.stdin.take();
+    let input = input.to_owned();
+    thread::spawn(move || {
+        if let Some(stdin) = stdin.as_mut() {
+            // the solver may exit without reading everything, that is its business
+            let _ = stdin.write_all(input.as_bytes());
+        }
+    });
+    let output = drain(child.stdout.take());
+    let errors = drain(child.stderr.take());
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => {}
+            Err(e) => return Err(kill(child, Exceeded::Failed(e.to_string()))),
+        }
+        if start.elapsed() > limits.time {
+            return Err(kill(child, Exceeded::Time));
+        }
+        if resident_memory(child.id()).is_some_and(|memory| memory > limits.memory) {
+            return Err(kill(child, Exceeded::Memory));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    let output = output
+        .join()
+        .map_err(|_| Exceeded::Failed("Unable to read the answers".to_owned()))?;
+    let errors = errors
+        .join()
+        .map_err(|_| Exceeded::Failed("Unable to read the errors".to_owned()))?;
+    Ok((status, output, errors))
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut text);
+        }
+        text
+    })
+}
+
+fn kill(mut child: Child, reason: Exceeded) -> Exceeded {
+    let _ = child.kill();
+    let _ = child.wait();
+    reason
+}
+
+// the resident memory of a process in bytes. Only known on Linux, elsewhere there is no memory
+// limit.
+fn resident_memory(pid: u32) -> Option<u64> {
+    let status = read_to_string(PathBuf::from(format!("/proc/{}/status", pid))).ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes: u64 = line
+        .trim_start_matches("VmRSS:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    const LIMITS: Limits = Limits {
+        time: Duration::from_secs(5),
+        memory: 1 << 30,
+        input: 100,
+    };
+
+    #[test]
+    fn read_request_reads_body_of_content_length() {
+        // given
+        let mut raw = Cursor::new(
+            "POST /day/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 9\r\n\r\n1721\n979\nignored",
+        );
+
+        // when
+        let request = read_request(&mut raw, LIMITS.input);
+
+        // then
+        assert_eq!(
+            request,
+            Ok(Request {
+                method: "POST".to_owned(),
+                path: "/day/1".to_owned(),
+                body: "1721\n979\n".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn read_request_rejects_large_input() {
+        // given
+        let mut raw = Cursor::new("POST /day/1 HTTP/1.1\r\nContent-Length: 101\r\n\r\n");
+
+        // when
+        let request = read_request(&mut raw, LIMITS.input);
+
+        // then
+        let response = request.expect_err("Expected the input to be rejected");
+        assert_eq!(response.status, 413);
+        assert!(response.body.contains(r#""kind":"input_too_large""#));
+    }
+
+    #[test]
+    fn read_request_rejects_endless_header() {
+        // given
+        let mut raw = Cursor::new(format!("POST /day/1 HTTP/1.1\r\nX: {}", "x".repeat(20_000)));
+
+        // when
+        let request = read_request(&mut raw, LIMITS.input);
+
+        // then
+        assert_eq!(request.map_err(|response| response.status), Err(400));
+    }
+
+    #[test]
+    fn parse_path_reads_day_and_parameters() {
+        assert_eq!(
+            parse_path("/day/15?turns2=100&start=0,3,6"),
+            Ok((
+                15,
+                vec![
+                    ("turns2".to_owned(), "100".to_owned()),
+                    ("start".to_owned(), "0,3,6".to_owned()),
+                ]
+            ))
+        );
+        assert_eq!(parse_path("/day/7"), Ok((7, vec![])));
+        let status = |path| parse_path(path).map_err(|response| response.status);
+        assert_eq!(status("/day/26"), Err(404));
+        assert_eq!(status("/days/7"), Err(404));
+        assert_eq!(status("/day/7?turns"), Err(400));
+        assert_eq!(status("/day/7?trace=/tmp"), Err(400));
+        assert_eq!(status("/day/2?policy=/etc/passwd"), Err(400));
+        assert_eq!(status("/day/4?schema=/etc/passwd"), Err(400));
+        assert_eq!(status("/day/7?start=1"), Err(400));
+    }
+
+    #[test]
+    fn parse_path_decodes_parameters() {
+        assert_eq!(
+            parse_path("/day/15?start=1%2C2%2c3"),
+            Ok((15, vec![("start".to_owned(), "1,2,3".to_owned())]))
+        );
+        let status = |path| parse_path(path).map_err(|response| response.status);
+        assert_eq!(status("/day/15?start=1%2"), Err(400));
+        assert_eq!(status("/day/15?start=%zz"), Err(400));
+        assert_eq!(status("/day/15?start=%FF"), Err(400));
+    }
+
+    #[test]
+    fn run_limited_kills_slow_command() {
+        // given
+        let mut command = Command::new("sleep");
+        command.arg("10");
+        let limits = Limits {
+            time: Duration::from_millis(50),
+            ..LIMITS
+        };
+
+        // when
+        let start = Instant::now();
+        let result = run_limited(command, "", limits);
+
+        // then
+        assert_eq!(result, Err(Exceeded::Time));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn run_limited_returns_output() {
+        // given
+        let command = Command::new("cat");
+
+        // when
+        let result = run_limited(command, "{\"day\":1}\n", LIMITS);
+
+        // then
+        let (status, output, _) = result.expect("Expected cat to succeed");
+        assert!(status.success());
+        assert_eq!(output, "{\"day\":1}\n");
+    }
+
+    #[test]
+    fn run_limited_returns_errors() {
+        // given
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo 'invalid parameter' >&2; exit 1"]);
+
+        // when
+        let result = run_limited(command, "", LIMITS);
+
+        // then
+        let (status, output, errors) = result.expect("Expected sh to run");
+        assert!(!status.success());
+        assert_eq!(output, "");
+        assert_eq!(errors, "invalid parameter\n");
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{job_name, load_input, Job, DAYS};
+use common::trace::{to_ascii, to_svg, Frame};
+use common::Params;
+use std::fs::{create_dir_all, write};
+use std::path::Path;
+
+/// How the recorded frames are written
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TraceFormat {
+    /// all frames of a part in a single text file
+    Ascii,
+    /// one SVG image per frame
+    Svg,
+}
+
+/// Records the steps of both parts of the job and writes them to files in `directory`, named
+/// after the day and part (e.g. `day-11-part-1.txt` or `day-11-part-1-0042.svg`).
+pub fn trace_job(
+    (day, input_path): &Job,
+    params: &Params,
+    directory: &Path,
+    format: TraceFormat,
+) -> Result<(), String> {
+    let record = DAYS[day - 1]
+        .trace
+        .ok_or_else(|| format!("Day {} has nothing to trace", day))?;
+    let (content, filename) = load_input(input_path.as_deref(), params)?;
+    let parts = record(&content, params).map_err(|e| e.in_file(&filename).to_string())?;
+    create_dir_all(directory)
+        .map_err(|e| format!("Unable to create '{}': {}", directory.display(), e))?;
+
+    println!("{}", job_name(*day, input_path.as_deref()));
+    let mut failed_parts: Vec<String> = Vec::with_capacity(2);
+    for (part, frames) in (1..).zip(parts) {
+        match frames {
+            Ok(frames) => {
+                let written = write_frames(&frames, directory, *day, part, format)?;
+                println!(
+                    "Part {}: {} frames written to {}",
+                    part,
+                    frames.len(),
+                    written
+                );
+            }
+            Err(e) => {
+                println!("Part {} failed: {}", part, e);
+                failed_parts.push(part.to_string());
+            }
+        }
+    }
+
+    if failed_parts.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed to trace part {}", failed_parts.join(", ")))
+    }
+}
+
+// returns where the frames were written, for the user
+fn write_frames(
+    frames: &[Frame],
+    directory: &Path,
+    day: usize,
+    part: usize,
+    format: TraceFormat,
+) -> Result<String, String> {
+    let name = format!("day-{:02}-part-{}", day, part);
+    let write_file = |file_name: String, content: String| {
+        let path = directory.join(file_name);
+        write(&path, content).map_err(|e| format!("Unable to write '{}': {}", path.display(), e))
+    };
+    match format {
+        TraceFormat::Ascii => {
+            write_file(format!("{}.txt", name), to_ascii(frames))?;
+            Ok(directory
+                .join(format!("{}.txt", name))
+                .display()
+                .to_string())
+        }
+        TraceFormat::Svg => {
+            for (i, frame) in frames.iter().enumerate() {
+                write_file(format!("{}-{:04}.svg", name, i), to_svg(frame))?;
+            }
+            Ok(directory
+                .join(format!("{}-*.svg", name))
+                .display()
+                .to_string())
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{solve_day, DAYS};
+use common::toml::{parse_toml, Table, Value};
+use common::{Answers, Params, ParseError};
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+/// Known-good answers for one input file, as listed in the answers file:
+///
+/// ```toml
+/// [[answer]]
+/// day = 1
+/// input = "example-inputs/day-01.txt"
+/// part1 = 514579
+/// part2 = 241861950
+/// ```
+///
+/// The input path is relative to the answers file. Parts without an answer are not checked. Other
+/// keys are parameters for the day, like `preamble = 5` for `--preamble 5`; parameters that name a
+/// file are relative to the answers file as well.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Expected {
+    day: usize,
+    input: Option<PathBuf>,
+    params: Params,
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+const KEYS: &[&str] = &["day", "input", "part1", "part2"];
+
+pub fn verify(answers_path: &Path) -> Result<(), String> {
+    let content = read_to_string(answers_path)
+        .map_err(|e| format!("Unable to read '{}': {}", answers_path.display(), e))?;
+    let base_dir = answers_path.parent().unwrap_or_else(|| Path::new(""));
+    let expected = parse_answers(&content, base_dir).map_err(|e| {
+        e.locate(&content)
+            .in_file(&answers_path.display().to_string())
+            .to_string()
+    })?;
+
+    let mut failed = 0;
+    for exp in &expected {
+        let input_name = exp
+            .input
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "<none>".to_owned());
+        let mismatches = match solve_day(exp.day, exp.input.as_deref(), &exp.params) {
+            Ok(answers) => check_answers(exp, &answers),
+            Err(e) => vec![e.to_string()],
+        };
+        if mismatches.is_empty() {
+            println!("Day {} ({}): ok", exp.day, input_name);
+        } else {
+            failed += 1;
+            println!("Day {} ({}): FAILED", exp.day, input_name);
+            for mismatch in mismatches {
+                println!("    {}", mismatch);
+            }
+        }
+    }
+
+    if failed == 0 {
+        println!("All {} inputs produce the expected answers", expected.len());
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} inputs did not produce the expected answers",
+            failed,
+            expected.len()
+        ))
+    }
+}
+
+fn parse_answers(content: &str, base_dir: &Path) -> Result<Vec<Expected>, ParseError> {
+    let tables = parse_toml(content)?;
+    let mut expected = Vec::with_capacity(tables.len());
+    for table in tables {
+        if table.name.is_empty() && table.entries.is_empty() {
+            continue;
+        }
+        if table.name != "answer" || !table.is_array {
+            return Err(ParseError::bad_layout(
+                table.header,
+                "expected only [[answer]] tables",
+            ));
+        }
+        expected.push(parse_expected(&table, base_dir)?);
+    }
+    Ok(expected)
+}
+
+fn parse_expected(table: &Table, base_dir: &Path) -> Result<Expected, ParseError> {
+    let day = table.require_int("day")?;
+    if day < 1 || day > DAYS.len() as i64 {
+        // require_int already made sure that the entry exists
+        let raw = table.get("day").map(|entry| entry.raw).unwrap_or_default();
+        return Err(ParseError::unexpected_token(raw, "a day from 1 to 25"));
+    }
+    let day = day as usize;
+    let mut params = Params::new();
+    for entry in table.entries.iter().filter(|e| !KEYS.contains(&e.key)) {
+        let value = match &entry.value {
+            Value::String(s) if DAYS[day - 1].file_params.contains(&entry.key) => {
+                base_dir.join(s).display().to_string()
+            }
+            Value::String(s) => s.clone(),
+            Value::Integer(i) => i.to_string(),
+            _ => {
+                return Err(ParseError::unexpected_token(
+                    entry.raw,
+                    "a string or an integer",
+                ))
+            }
+        };
+        params.insert(entry.key, &value);
+    }
+    Ok(Expected {
+        day,
+        input: table.get_str("input")?.map(|input| base_dir.join(input)),
+        params,
+        part1: expected_answer(table, "part1")?,
+        part2: expected_answer(table, "part2")?,
+    })
+}
+
+// answers may be given as strings or as numbers
+fn expected_answer(table: &Table, key: &str) -> Result<Option<String>, ParseError> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(entry) => match &entry.value {
+            Value::String(s) => Ok(Some(s.clone())),
+            Value::Integer(i) => Ok(Some(i.to_string())),
+            _ => Err(ParseError::unexpected_token(
+                entry.raw,
+                "a string or an integer",
+            )),
+        },
+    }
+}
+
+fn check_answers(expected: &Expected, answers: &Answers) -> Vec<String> {
+    let mut mismatches = Vec::with_capacity(2);
+    if let Some(exp) = &expected.part1 {
+        check_part(1, exp, Some(&answers.part1.answer), &mut mismatches);
+    }
+    if let Some(exp) = &expected.part2 {
+        let actual = answers.part2.as_ref().map(|part| &part.answer);
+        check_part(2, exp, actual, &mut mismatches);
+    }
+    mismatches
+}
+
+fn check_part(
+    part: u8,
+    expected: &str,
+    actual: Option<&Result<String, String>>,
+    mismatches: &mut Vec<String>,
+) {
+    match actual {
+        Some(Ok(answer)) if answer == expected => (),
+        Some(Ok(answer)) => mismatches.push(format!(
+            "part {}: expected {}, got {}",
+            part, expected, answer
+        )),
+        Some(Err(e)) => mismatches.push(format!(
+            "part {}: expected {}, but it failed: {}",
+            part, expected, e
+        )),
+        None => mismatches.push(format!(
+            "part {}: expected {}, but there is no part {}",
+            part, expected, part
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::{ErrorKind, Part};
+    use std::time::Duration;
+
+    fn part(answer: Result<String, String>) -> Part {
+        Part {
+            answer,
+            elapsed: Duration::from_millis(1),
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn parse_answers_parses_valid_answers() {
+        // given
+        let content = r#"
+[[answer]]
+day = 1
+input = "day-01.txt"
+part1 = 514579
+
+[[answer]]
+day = 21
+input = "day-21.txt"
+part2 = "mxmxvkd,sqjhc,fvjkl"
+"#;
+
+        // when
+        let result = parse_answers(content, Path::new("examples"));
+
+        // then
+        assert_eq!(
+            result,
+            Ok(vec![
+                Expected {
+                    day: 1,
+                    input: Some(PathBuf::from("examples/day-01.txt")),
+                    params: Params::new(),
+                    part1: Some("514579".to_owned()),
+                    part2: None,
+                },
+                Expected {
+                    day: 21,
+                    input: Some(PathBuf::from("examples/day-21.txt")),
+                    params: Params::new(),
+                    part1: None,
+                    part2: Some("mxmxvkd,sqjhc,fvjkl".to_owned()),
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_answers_takes_other_keys_as_params() {
+        // given
+        let content =
+            "[[answer]]\nday = 9\npreamble = 5\n\n[[answer]]\nday = 2\npolicy = \"policy.txt\"\n";
+
+        // when
+        let result = parse_answers(content, Path::new("examples"));
+
+        // then
+        let params: Vec<Params> = result
+            .expect("Expected valid answers")
+            .into_iter()
+            .map(|expected| expected.params)
+            .collect();
+        let mut preamble = Params::new();
+        preamble.insert("preamble", "5");
+        let mut policy = Params::new();
+        let policy_path = Path::new("examples").join("policy.txt");
+        policy.insert("policy", &policy_path.display().to_string());
+        assert_eq!(params, vec![preamble, policy]);
+    }
+
+    #[test]
+    fn parse_answers_fails_for_invalid_day() {
+        // given
+        let content = "[[answer]]\nday = 26\n";
+
+        // when
+        let result = parse_answers(content, Path::new(""));
+
+        // then
+        assert!(matches!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::UnexpectedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn check_answers_reports_mismatches_and_failures() {
+        // given
+        let expected = Expected {
+            day: 8,
+            input: None,
+            params: Params::new(),
+            part1: Some("5".to_owned()),
+            part2: Some("8".to_owned()),
+        };
+        let answers = Answers {
+            part1: part(Ok("6".to_owned())),
+            part2: Some(part(Err("No loop detected".to_owned()))),
+        };
+
+        // when
+        let mismatches = check_answers(&expected, &answers);
+
+        // then
+        assert_eq!(
+            mismatches,
+            vec![
+                "part 1: expected 5, got 6".to_owned(),
+                "part 2: expected 8, but it failed: No loop detected".to_owned()
+            ]
+        );
+    }
+
+    #[test]
+    fn check_answers_ignores_parts_without_expected_answer() {
+        // given
+        let expected = Expected {
+            day: 5,
+            input: None,
+            params: Params::new(),
+            part1: Some("820".to_owned()),
+            part2: None,
+        };
+        let answers = Answers {
+            part1: part(Ok("820".to_owned())),
+            part2: Some(part(Err("There is no free seat!".to_owned()))),
+        };
+
+        // when
+        let mismatches = check_answers(&expected, &answers);
+
+        // then
+        assert!(mismatches.is_empty());
+    }
+}
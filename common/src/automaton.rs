@@ -0,0 +1,545 @@
+use crate::grid::{Grid, NEIGHBOURS8};
+use crate::parallel;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+
+// the default hasher is resistant against collision attacks, which costs a lot of time when
+// looking up millions of cells. Nobody attacks a cellular automaton, so a simple multiplicative
+// hash (like the one used in rustc) will do.
+#[derive(Clone, Copy, Default, Debug)]
+struct CellHasher(u64);
+
+impl Hasher for CellHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+
+    fn write_i64(&mut self, n: i64) {
+        self.write_u64(n as u64);
+    }
+}
+
+type Cells<P, S> = HashMap<P, S, BuildHasherDefault<CellHasher>>;
+
+/// Where the cells of an automaton are and which cells are neighbours
+pub trait Topology: Sync {
+    type Pos: Copy + Eq + Hash + Send + Sync;
+
+    fn neighbours(&self, pos: Self::Pos) -> impl Iterator<Item = Self::Pos> + '_;
+}
+
+/// How a cell changes from one generation to the next
+pub trait Rule<S>: Sync {
+    /// The next state of a cell, given its state and the states of its neighbours in no particular
+    /// order
+    fn next(&self, cell: &S, neighbours: &[S]) -> S;
+
+    /// Whether a cell in the background state can change at all. If not, only the cells that are
+    /// not in the background state are updated, which is a lot faster.
+    fn changes_background(&self) -> bool {
+        true
+    }
+}
+
+/// A rule like the one of Conway's game of life: a dead cell is born with one of the `birth`
+/// numbers of live neighbours and a live cell survives with one of the `survival` numbers.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LifeLike {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl LifeLike {
+    /// Conway's game of life is `LifeLike::new(&[3], &[2, 3])`. Cells can not be born without
+    /// live neighbours, there would be infinitely many of them.
+    pub fn new(birth: &[usize], survival: &[usize]) -> LifeLike {
+        assert!(
+            !birth.contains(&0),
+            "cells can not be born without neighbours"
+        );
+        LifeLike {
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+        }
+    }
+}
+
+impl Rule<bool> for LifeLike {
+    fn next(&self, alive: &bool, neighbours: &[bool]) -> bool {
+        let alive_neighbours = neighbours.iter().filter(|n| **n).count();
+        if *alive {
+            self.survival.contains(&alive_neighbours)
+        } else {
+            self.birth.contains(&alive_neighbours)
+        }
+    }
+}
+
+/// A finite square grid. Each cell has up to eight neighbours, including the diagonal ones.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Square {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Topology for Square {
+    type Pos = (usize, usize);
+
+    fn neighbours(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS8.iter().filter_map(move |(dx, dy)| {
+            let x = x.checked_add_signed(*dx).filter(|x| *x < self.width)?;
+            let y = y.checked_add_signed(*dy).filter(|y| *y < self.height)?;
+            Some((x, y))
+        })
+    }
+}
+
+/// A finite square grid where the neighbours of a cell are the first cells in each of the eight
+/// directions that can not be seen through. The cells that can be seen through must never change.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct LineOfSight {
+    neighbours: Grid<Vec<(usize, usize)>>,
+}
+
+impl LineOfSight {
+    pub fn new<S>(grid: &Grid<S>, transparent: impl Fn(&S) -> bool) -> LineOfSight {
+        let neighbours = Grid::from_fn(grid.width(), grid.height(), |x, y| {
+            NEIGHBOURS8
+                .iter()
+                .filter_map(|(dx, dy)| {
+                    let mut pos = (x, y);
+                    loop {
+                        pos = grid.offset(pos.0, pos.1, *dx, *dy)?;
+                        if !transparent(&grid[pos]) {
+                            return Some(pos);
+                        }
+                    }
+                })
+                .collect()
+        });
+        LineOfSight { neighbours }
+    }
+}
+
+impl Topology for LineOfSight {
+    type Pos = (usize, usize);
+
+    fn neighbours(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours
+            .get(pos.0, pos.1)
+            .into_iter()
+            .flat_map(|neighbours| neighbours.iter().copied())
+    }
+}
+
+/// An infinite grid of hexagons in axial coordinates
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Hex;
+
+impl Hex {
+    /// The offsets of the six neighbours of a hexagon: east, west, north east, north west, south
+    /// east and south west
+    pub const NEIGHBOURS: [(i64, i64); 6] = [(1, 0), (-1, 0), (1, -1), (0, -1), (0, 1), (-1, 1)];
+}
+
+impl Topology for Hex {
+    type Pos = (i64, i64);
+
+    fn neighbours(&self, (x, y): (i64, i64)) -> impl Iterator<Item = (i64, i64)> + '_ {
+        Hex::NEIGHBOURS.iter().map(move |(dx, dy)| (x + dx, y + dy))
+    }
+}
+
+/// An infinite grid in `N` dimensions. Each cell has 3^N - 1 neighbours, all cells that differ
+/// by at most one in each coordinate.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Moore<const N: usize> {
+    offsets: Vec<[i64; N]>,
+}
+
+impl<const N: usize> Moore<N> {
+    pub fn new() -> Moore<N> {
+        let mut offsets: Vec<[i64; N]> = vec![[0; N]];
+        for dimension in 0..N {
+            offsets = offsets
+                .into_iter()
+                .flat_map(|offset| {
+                    (-1..=1).map(move |d| {
+                        let mut offset = offset;
+                        offset[dimension] = d;
+                        offset
+                    })
+                })
+                .collect();
+        }
+        offsets.retain(|offset| offset.iter().any(|d| *d != 0));
+        Moore { offsets }
+    }
+}
+
+impl<const N: usize> Default for Moore<N> {
+    fn default() -> Moore<N> {
+        Moore::new()
+    }
+}
+
+impl<const N: usize> Topology for Moore<N> {
+    type Pos = [i64; N];
+
+    fn neighbours(&self, pos: [i64; N]) -> impl Iterator<Item = [i64; N]> + '_ {
+        self.offsets.iter().map(move |offset| {
+            let mut neighbour = pos;
+            for (coordinate, d) in neighbour.iter_mut().zip(offset) {
+                *coordinate += d;
+            }
+            neighbour
+        })
+    }
+}
+
+/// A repeating sequence of generations: generation `start + length` equals generation `start`.
+/// A fixed point is a cycle of length 1.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// A cellular automaton. Only the cells that are not in the background state are stored, so the
+/// topology may be infinite. A cell in the background state must not change as long as all its
+/// neighbours are in the background state.
+#[derive(Clone, Debug)]
+pub struct Automaton<T: Topology, S, R> {
+    topology: T,
+    rule: R,
+    background: S,
+    cells: Cells<T::Pos, S>,
+    generation: usize,
+}
+
+impl<T: Topology, S: Clone + PartialEq + Send + Sync, R: Rule<S>> Automaton<T, S, R> {
+    /// Creates the automaton at generation 0. Cells that are not given are in the background
+    /// state.
+    pub fn new(
+        topology: T,
+        rule: R,
+        background: S,
+        cells: impl IntoIterator<Item = (T::Pos, S)>,
+    ) -> Automaton<T, S, R> {
+        let cells = cells
+            .into_iter()
+            .filter(|(_, state)| *state != background)
+            .collect();
+        Automaton {
+            topology,
+            rule,
+            background,
+            cells,
+            generation: 0,
+        }
+    }
+
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    /// The number of steps made so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn get(&self, pos: T::Pos) -> &S {
+        self.cells.get(&pos).unwrap_or(&self.background)
+    }
+
+    /// All cells that are not in the background state, in no particular order
+    pub fn cells(&self) -> impl Iterator<Item = (T::Pos, &S)> {
+        self.cells.iter().map(|(pos, state)| (*pos, state))
+    }
+
+    /// The number of cells that are not in the background state, e.g. the live cells
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cells in the given state, which must not be the background state
+    pub fn count(&self, state: &S) -> usize {
+        self.cells.values().filter(|s| *s == state).count()
+    }
+
+    /// Advances by one generation. Returns whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        let next = self.next_cells(&self.cells);
+        let changed = next != self.cells;
+        self.cells = next;
+        self.generation += 1;
+        changed
+    }
+
+    /// Advances by the given number of generations.
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Advances until the next generation would be the same as the current one and returns the
+    /// current generation. Returns `None` (and stops) if that does not happen within
+    /// `max_generations` steps.
+    pub fn run_to_fixed_point(&mut self, max_generations: usize) -> Option<usize> {
+        for _ in 0..=max_generations {
+            let next = self.next_cells(&self.cells);
+            if next == self.cells {
+                return Some(self.generation);
+            }
+            self.cells = next;
+            self.generation += 1;
+        }
+        None
+    }
+
+    /// Finds the first generation from the current one on that repeats, without changing the
+    /// automaton. Returns `None` if no generation repeats within `max_generations` steps.
+    pub fn find_cycle(&self, max_generations: usize) -> Option<Cycle> {
+        // Brent's algorithm, which only keeps two generations around at a time
+        let mut power = 1;
+        let mut length = 1;
+        let mut tortoise = self.cells.clone();
+        let mut hare = self.next_cells(&tortoise);
+        let mut steps = 1;
+        while tortoise != hare {
+            if steps >= max_generations {
+                return None;
+            }
+            if power == length {
+                tortoise = hare.clone();
+                power *= 2;
+                length = 0;
+            }
+            hare = self.next_cells(&hare);
+            length += 1;
+            steps += 1;
+        }
+
+        let mut tortoise = self.cells.clone();
+        let mut hare = self.cells.clone();
+        for _ in 0..length {
+            hare = self.next_cells(&hare);
+        }
+        let mut start = self.generation;
+        while tortoise != hare {
+            tortoise = self.next_cells(&tortoise);
+            hare = self.next_cells(&hare);
+            start += 1;
+        }
+        Some(Cycle { start, length })
+    }
+
+    fn next_cells(&self, cells: &Cells<T::Pos, S>) -> Cells<T::Pos, S> {
+        if !self.rule.changes_background() {
+            let stored: Vec<(&T::Pos, &S)> = cells.iter().collect();
+            return self.update(&stored, |(pos, cell), neighbours| {
+                neighbours.extend(
+                    self.topology
+                        .neighbours(**pos)
+                        .map(|n| cells.get(&n).unwrap_or(&self.background).clone()),
+                );
+                self.next_cell(**pos, cell, neighbours)
+            });
+        }
+
+        // with cells that can be born, most cells to update are in the background state. Going
+        // through the stored cells once and telling their neighbours about them is a lot cheaper
+        // than looking up all neighbours of each of those.
+        let mut neighbours: Cells<T::Pos, Vec<S>> = Cells::default();
+        for (pos, state) in cells {
+            for neighbour in self.topology.neighbours(*pos) {
+                neighbours.entry(neighbour).or_default().push(state.clone());
+            }
+        }
+        for pos in cells.keys() {
+            neighbours.entry(*pos).or_default();
+        }
+        let neighbours: Vec<(T::Pos, Vec<S>)> = neighbours.into_iter().collect();
+        self.update(&neighbours, |(pos, stored_neighbours), all_neighbours| {
+            let background_neighbours =
+                self.topology.neighbours(*pos).count() - stored_neighbours.len();
+            all_neighbours.extend_from_slice(stored_neighbours);
+            all_neighbours.extend((0..background_neighbours).map(|_| self.background.clone()));
+            let cell = cells.get(pos).unwrap_or(&self.background);
+            self.next_cell(*pos, cell, all_neighbours)
+        })
+    }
+
+    // calls `next` for all cells to update, on up to `parallel::jobs()` threads. `next` gets an empty
+    // buffer for the neighbours.
+    fn update<C: Sync>(
+        &self,
+        to_update: &[C],
+        next: impl Fn(&C, &mut Vec<S>) -> Option<(T::Pos, S)> + Sync,
+    ) -> Cells<T::Pos, S> {
+        let chunk_size = to_update.len() / (parallel::jobs() * 4) + 1;
+        let chunks: Vec<&[C]> = to_update.chunks(chunk_size).collect();
+        parallel::par_map(&chunks, |chunk| {
+            let mut neighbours: Vec<S> = Vec::new();
+            chunk
+                .iter()
+                .filter_map(|cell| {
+                    neighbours.clear();
+                    next(cell, &mut neighbours)
+                })
+                .collect::<Vec<(T::Pos, S)>>()
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    // the next state of the cell, `None` if it is the background state
+    fn next_cell(&self, pos: T::Pos, cell: &S, neighbours: &[S]) -> Option<(T::Pos, S)> {
+        let next = self.rule.next(cell, neighbours);
+        if next == self.background {
+            None
+        } else {
+            Some((pos, next))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn game_of_life(cells: &[(i64, i64)]) -> Automaton<Moore<2>, bool, LifeLike> {
+        Automaton::new(
+            Moore::new(),
+            LifeLike::new(&[3], &[2, 3]),
+            false,
+            cells.iter().map(|(x, y)| ([*x, *y], true)),
+        )
+    }
+
+    #[test]
+    fn moore_has_all_neighbours() {
+        // given
+        let topology = Moore::<3>::new();
+
+        // when
+        let neighbours: HashSet<[i64; 3]> = topology.neighbours([5, 5, 5]).collect();
+
+        // then
+        assert_eq!(neighbours.len(), 26);
+        assert!(neighbours.contains(&[4, 6, 5]));
+        assert!(!neighbours.contains(&[5, 5, 5]));
+    }
+
+    #[test]
+    fn find_cycle_finds_blinker() {
+        // given
+        let blinker = game_of_life(&[(0, 0), (1, 0), (2, 0)]);
+
+        // when
+        let cycle = blinker.find_cycle(100);
+
+        // then
+        assert_eq!(
+            cycle,
+            Some(Cycle {
+                start: 0,
+                length: 2
+            })
+        );
+    }
+
+    #[test]
+    fn find_cycle_finds_start_of_cycle() {
+        // given
+        // an L of three cells turns into a block after one generation
+        let automaton = game_of_life(&[(0, 0), (1, 0), (0, 1)]);
+
+        // when
+        let cycle = automaton.find_cycle(100);
+
+        // then
+        assert_eq!(
+            cycle,
+            Some(Cycle {
+                start: 1,
+                length: 1
+            })
+        );
+        assert_eq!(automaton.generation(), 0);
+    }
+
+    #[test]
+    fn find_cycle_gives_up_for_glider() {
+        // given
+        let glider = game_of_life(&[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+
+        // when
+        let cycle = glider.find_cycle(50);
+
+        // then
+        assert_eq!(cycle, None);
+    }
+
+    #[test]
+    fn run_to_fixed_point_stops_at_block() {
+        // given
+        let mut automaton = game_of_life(&[(0, 0), (1, 0), (0, 1)]);
+
+        // when
+        let generation = automaton.run_to_fixed_point(100);
+
+        // then
+        assert_eq!(generation, Some(1));
+        assert_eq!(automaton.population(), 4);
+        assert!(*automaton.get([1, 1]));
+    }
+
+    #[test]
+    fn run_gives_same_cells_in_parallel() {
+        // given
+        // an r-pentomino keeps growing for more than a thousand generations
+        let mut sequential = game_of_life(&[(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)]);
+        let mut parallel = sequential.clone();
+        sequential.run(100);
+
+        // when
+        parallel::with_jobs(4, || parallel.run(100));
+
+        // then
+        assert!(sequential.population() > 100);
+        assert_eq!(parallel.cells, sequential.cells);
+    }
+
+    #[test]
+    fn line_of_sight_skips_transparent_cells() {
+        // given
+        let grid = Grid::<bool>::parse("#..#\n....\n#.#.\n").expect("Expected valid grid");
+
+        // when
+        let topology = LineOfSight::new(&grid, |cell| !*cell);
+        let neighbours: Vec<(usize, usize)> = topology.neighbours((0, 0)).collect();
+
+        // then
+        assert_eq!(neighbours, vec![(3, 0), (0, 2), (2, 2)]);
+    }
+}
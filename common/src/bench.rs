@@ -0,0 +1,99 @@
+use crate::{take_warnings, Params, ParseError, Solution};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Measured durations of the phases of a day, one measurement per iteration.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Timings {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    /// `None` if the day has no second part
+    pub part2: Option<Vec<Duration>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// `None` if there are no measurements. For an even number of measurements, the median is
+    /// the upper one of the two middle values.
+    pub fn of(measurements: &[Duration]) -> Option<Stats> {
+        let mut sorted = measurements.to_vec();
+        sorted.sort_unstable();
+        Some(Stats {
+            min: *sorted.first()?,
+            median: sorted[sorted.len() / 2],
+            max: *sorted.last()?,
+        })
+    }
+}
+
+/// Parses the input and solves both parts `iterations` times, timing each phase separately.
+/// Only a parse failure stops the benchmark, a part that fails is timed like one that succeeds.
+pub fn bench<S: Solution>(
+    input: &str,
+    params: &Params,
+    iterations: usize,
+) -> Result<Timings, ParseError> {
+    params.check_known(S::PARAMS)?;
+    let mut parse = Vec::with_capacity(iterations);
+    let mut part1 = Vec::with_capacity(iterations);
+    let mut part2 = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = S::parse_with_params(black_box(input), params).map_err(|e| e.locate(input))?;
+        parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&parsed).is_ok());
+        part1.push(start.elapsed());
+
+        if S::HAS_PART2 {
+            let start = Instant::now();
+            black_box(S::part2(&parsed).is_ok());
+            part2.push(start.elapsed());
+        }
+        // warnings are not reported when benchmarking
+        take_warnings();
+    }
+
+    Ok(Timings {
+        parse,
+        part1,
+        part2: if S::HAS_PART2 { Some(part2) } else { None },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats_of_finds_min_median_and_max() {
+        // given
+        let measurements = [5, 1, 4, 2, 3].map(Duration::from_millis);
+
+        // when
+        let stats = Stats::of(&measurements);
+
+        // then
+        assert_eq!(
+            stats,
+            Some(Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            })
+        );
+    }
+
+    #[test]
+    fn stats_of_is_none_without_measurements() {
+        assert_eq!(Stats::of(&[]), None);
+    }
+}
@@ -0,0 +1,299 @@
+use std::fmt;
+use std::num::{IntErrorKind, ParseIntError};
+use std::str::FromStr;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ErrorKind {
+    /// Something was found that does not belong at this position
+    UnexpectedToken { found: String, expected: String },
+    /// A required part of the input is missing
+    MissingField { field: String },
+    /// A number does not fit into the type it is parsed into
+    IntegerOverflow { found: String, target: &'static str },
+    /// The overall structure of the input is invalid, e.g. rows of different length
+    BadLayout { problem: String },
+}
+
+impl ErrorKind {
+    /// A short, stable name of the kind, for machine-readable output
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorKind::UnexpectedToken { .. } => "unexpected_token",
+            ErrorKind::MissingField { .. } => "missing_field",
+            ErrorKind::IntegerOverflow { .. } => "integer_overflow",
+            ErrorKind::BadLayout { .. } => "bad_layout",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedToken { found, expected } => {
+                write!(f, "unexpected '{}', expected {}", found, expected)
+            }
+            ErrorKind::MissingField { field } => write!(f, "missing {}", field),
+            ErrorKind::IntegerOverflow { found, target } => {
+                write!(f, "'{}' does not fit into {}", found, target)
+            }
+            ErrorKind::BadLayout { problem } => write!(f, "bad layout: {}", problem),
+        }
+    }
+}
+
+/// The position of an error in the input, resolved by [`ParseError::locate`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Location {
+    pub file: Option<String>,
+    /// 1-based
+    pub line: usize,
+    /// 1-based, counted in chars
+    pub column: usize,
+    /// length of the offending part in chars, at least 1
+    pub length: usize,
+    pub source_line: String,
+}
+
+/// An error that occurred while parsing puzzle input.
+///
+/// Parsers create the error with the slice of the input that caused it. The slice is remembered
+/// by its address only, so the error does not borrow the input. Once the error reaches code that
+/// knows the complete input, [`ParseError::locate`] turns the address into line and column.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    kind: ErrorKind,
+    span_start: usize,
+    span_len: usize,
+    // boxed to keep the error small, most errors are passed around unlocated
+    location: Option<Box<Location>>,
+}
+
+impl ParseError {
+    pub fn new(kind: ErrorKind, at: &str) -> ParseError {
+        ParseError {
+            kind,
+            span_start: at.as_ptr() as usize,
+            span_len: at.len(),
+            location: None,
+        }
+    }
+
+    pub fn unexpected_token(found: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(
+            ErrorKind::UnexpectedToken {
+                found: found.to_owned(),
+                expected: expected.into(),
+            },
+            found,
+        )
+    }
+
+    /// `at` should point to where the missing field was expected, e.g. the (empty) end of a line
+    pub fn missing_field(at: &str, field: impl Into<String>) -> ParseError {
+        ParseError::new(
+            ErrorKind::MissingField {
+                field: field.into(),
+            },
+            at,
+        )
+    }
+
+    pub fn bad_layout(at: &str, problem: impl Into<String>) -> ParseError {
+        ParseError::new(
+            ErrorKind::BadLayout {
+                problem: problem.into(),
+            },
+            at,
+        )
+    }
+
+    pub fn invalid_integer<T>(found: &str, error: &ParseIntError) -> ParseError {
+        let kind = match error.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ErrorKind::IntegerOverflow {
+                found: found.to_owned(),
+                target: std::any::type_name::<T>(),
+            },
+            IntErrorKind::Empty => ErrorKind::MissingField {
+                field: "a number".to_owned(),
+            },
+            _ => ErrorKind::UnexpectedToken {
+                found: found.to_owned(),
+                expected: "a number".to_owned(),
+            },
+        };
+        ParseError::new(kind, found)
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_deref()
+    }
+
+    /// Resolves line and column of the error. `input` must be the complete input the failed
+    /// parser worked on. If the error does not point into `input`, it stays unlocated.
+    pub fn locate(mut self, input: &str) -> ParseError {
+        let base = input.as_ptr() as usize;
+        if self.location.is_some() || self.span_start < base || self.span_start > base + input.len()
+        {
+            return self;
+        }
+        let offset = self.span_start - base;
+        let line_start = input[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[offset..]
+            .find('\n')
+            .map(|i| i + offset)
+            .unwrap_or_else(|| input.len());
+        let span_end = (offset + self.span_len).min(line_end);
+
+        self.location = Some(Box::new(Location {
+            file: None,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            length: input[offset..span_end].chars().count().max(1),
+            source_line: input[line_start..line_end].to_owned(),
+        }));
+        self
+    }
+
+    /// Sets the name of the file the input was read from, for error messages. Errors already
+    /// located in another file, e.g. a file named by a parameter, keep their file.
+    pub fn in_file(mut self, file: &str) -> ParseError {
+        if let Some(location) = &mut self.location {
+            location.file.get_or_insert_with(|| file.to_owned());
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error: {}", self.kind)?;
+        if let Some(location) = &self.location {
+            let line_number = location.line.to_string();
+            let gutter = " ".repeat(line_number.len());
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{}--> {}:{}:{}",
+                gutter,
+                location.file.as_deref().unwrap_or("<input>"),
+                location.line,
+                location.column
+            )?;
+            writeln!(f, "{} |", gutter)?;
+            writeln!(f, "{} | {}", line_number, location.source_line)?;
+            write!(
+                f,
+                "{} | {}{}",
+                gutter,
+                " ".repeat(location.column - 1),
+                "^".repeat(location.length)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The empty slice at the end of `s`, to point at something missing after `s`.
+pub fn end_of(s: &str) -> &str {
+    &s[s.len()..]
+}
+
+/// Parses an integer, the error points to the offending string.
+pub fn parse_int<T>(s: &str) -> Result<T, ParseError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    s.parse::<T>()
+        .map_err(|e| ParseError::invalid_integer::<T>(s, &e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locate_finds_line_and_column() {
+        // given
+        let input = "acc +1\njmp +banana\nnop +0\n";
+        let error = ParseError::unexpected_token(&input[12..18], "a number");
+
+        // when
+        let located = error.locate(input);
+
+        // then
+        let location = located.location().expect("Expected error to be located");
+        assert_eq!(location.line, 2);
+        assert_eq!(location.column, 6);
+        assert_eq!(location.length, 6);
+        assert_eq!(location.source_line, "jmp +banana");
+    }
+
+    #[test]
+    fn locate_ignores_errors_outside_of_the_input() {
+        // given
+        let input = "acc +1\n";
+        let other = "jmp +banana";
+        let error = ParseError::unexpected_token(other, "a number");
+
+        // when
+        let located = error.locate(input);
+
+        // then
+        assert_eq!(located.location(), None);
+    }
+
+    #[test]
+    fn display_renders_caret_annotated_snippet() {
+        // given
+        let input = "acc +1\njmp +banana\n";
+        let error = ParseError::unexpected_token(&input[11..18], "a number")
+            .locate(input)
+            .in_file("input.txt");
+
+        // when
+        let rendered = error.to_string();
+
+        // then
+        assert_eq!(
+            rendered,
+            r"error: unexpected '+banana', expected a number
+ --> input.txt:2:5
+  |
+2 | jmp +banana
+  |     ^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn missing_field_at_end_of_line_points_behind_the_line() {
+        // given
+        let input = "acc\n";
+        let error = ParseError::missing_field(&input[3..3], "argument").locate(input);
+
+        // when
+        let location = error.location().expect("Expected error to be located");
+
+        // then
+        assert_eq!(location.column, 4);
+        assert_eq!(location.length, 1);
+    }
+
+    #[test]
+    fn parse_int_distinguishes_overflow_and_invalid_digits() {
+        assert!(matches!(
+            parse_int::<u8>("300").map_err(|e| e.kind().clone()),
+            Err(ErrorKind::IntegerOverflow { .. })
+        ));
+        assert!(matches!(
+            parse_int::<u8>("3x").map_err(|e| e.kind().clone()),
+            Err(ErrorKind::UnexpectedToken { .. })
+        ));
+        assert_eq!(parse_int::<u8>("42"), Ok(42));
+    }
+}
@@ -0,0 +1,180 @@
+use crate::generate::{Generator, Rng};
+use crate::{solve, Params, Solution};
+use std::panic::{self, AssertUnwindSafe};
+
+// tokens that tend to break parsers: separators, signs, non-ascii text and numbers that overflow
+const TOKENS: [&str; 16] = [
+    "\n",
+    "\n\n",
+    " ",
+    ",",
+    ":",
+    "-",
+    "+",
+    "x",
+    "#",
+    "(",
+    ")",
+    "\"",
+    "é",
+    "0",
+    "99999999999999999999",
+    "-9223372036854775809",
+];
+
+/// An input that made the day panic, with the panic message
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Failure {
+    pub input: String,
+    pub message: String,
+}
+
+/// Solves the input and formats the error, if any. Returns the panic message if that panics.
+pub fn solve_panics<S: Solution>(input: &str, params: &Params) -> Option<String> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        // locating and printing the error looks at the input again, so that must not panic either
+        if let Err(e) = solve::<S>(input, params) {
+            let _ = e.to_string();
+        }
+    }))
+    .err()
+    .map(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "(no message)".to_owned())
+    })
+}
+
+/// Changes the input a little: deletes, inserts, duplicates or swaps characters or lines.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    let mut lines: Vec<&str> = input.split_inclusive('\n').collect();
+    let at = |rng: &mut Rng, len: usize| rng.index(len + 1);
+    match rng.below(7) {
+        0 => {
+            let start = at(rng, chars.len());
+            let end = (start + rng.index(8) + 1).min(chars.len());
+            chars.drain(start..end);
+            chars.into_iter().collect()
+        }
+        1 => {
+            let position = at(rng, chars.len());
+            chars.splice(position..position, rng.choose(&TOKENS).chars());
+            chars.into_iter().collect()
+        }
+        2 if !chars.is_empty() => {
+            let position = rng.index(chars.len());
+            chars[position] = *rng.choose(&chars);
+            chars.into_iter().collect()
+        }
+        3 => {
+            chars.truncate(at(rng, chars.len()));
+            chars.into_iter().collect()
+        }
+        4 if !lines.is_empty() => {
+            lines.remove(rng.index(lines.len()));
+            lines.concat()
+        }
+        5 if !lines.is_empty() => {
+            let line = *rng.choose(&lines);
+            lines.insert(at(rng, lines.len()), line);
+            lines.concat()
+        }
+        6 if !lines.is_empty() => {
+            let (a, b) = (rng.index(lines.len()), rng.index(lines.len()));
+            lines.swap(a, b);
+            lines.concat()
+        }
+        _ => input.to_owned(),
+    }
+}
+
+/// Throws `iterations` mutated generated inputs at the day, parsing and solving both parts. Returns
+/// the inputs that made it panic, shrunk to a (locally) minimal input that still panics.
+pub fn fuzz_solve<S: Solution + Generator>(seed: u64, iterations: usize) -> Vec<Failure> {
+    let mut rng = Rng::new(seed);
+    let params = S::fuzz_params();
+    let mut failures: Vec<Failure> = Vec::new();
+    for _ in 0..iterations {
+        let size = rng.index(10) + 1;
+        let mut input = S::generate(&mut rng, size).input;
+        for _ in 0..rng.between(1, 4) {
+            input = mutate(&mut rng, &input);
+        }
+        if solve_panics::<S>(&input, &params).is_some() {
+            let input = shrink::<S>(input, &params);
+            let message = solve_panics::<S>(&input, &params).unwrap_or_default();
+            if !failures.iter().any(|f| f.message == message) {
+                failures.push(Failure { input, message });
+            }
+        }
+    }
+    failures
+}
+
+// removes lines, then single characters, as long as the day still panics
+fn shrink<S: Solution>(mut input: String, params: &Params) -> String {
+    let mut i = 0;
+    while i < input.split_inclusive('\n').count() {
+        let candidate: String = input
+            .split_inclusive('\n')
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, line)| line)
+            .collect();
+        if solve_panics::<S>(&candidate, params).is_some() {
+            input = candidate;
+        } else {
+            i += 1;
+        }
+    }
+    let mut i = 0;
+    while i < input.chars().count() {
+        let candidate: String = input
+            .chars()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, c)| c)
+            .collect();
+        if solve_panics::<S>(&candidate, params).is_some() {
+            input = candidate;
+        } else {
+            i += 1;
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mutate_is_deterministic() {
+        // given
+        let input = "1721\n979\n366\n";
+
+        // when
+        let mutated1 = mutate(&mut Rng::new(3), input);
+        let mutated2 = mutate(&mut Rng::new(3), input);
+
+        // then
+        assert_eq!(mutated1, mutated2);
+    }
+
+    #[test]
+    fn mutate_handles_empty_input() {
+        for seed in 0..20 {
+            // given
+            let mut rng = Rng::new(seed);
+
+            // when
+            let mutated = mutate(&mut rng, "");
+
+            // then
+            assert!(mutated.chars().count() <= 20);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{solve, Params, Solution};
+
+/// A small, seedable pseudo random number generator (splitmix64). Good enough to generate
+/// puzzle inputs, and the same seed always gives the same input.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` to `bound - 1`. `bound` must not be 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        // reject the top end of the range so that all results are equally likely
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let n = self.next_u64();
+            if n < zone {
+                return n % bound;
+            }
+        }
+    }
+
+    /// A number from `low` to `high`, both inclusive
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        let span = high.wrapping_sub(low) as u64;
+        if span == u64::MAX {
+            return self.next_u64() as i64;
+        }
+        low.wrapping_add(self.below(span + 1) as i64)
+    }
+
+    /// An index into a collection of length `len`, which must not be 0
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// `true` with the given probability
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/// A generated puzzle input and the answers planted in it. Answers are `None` if the generator
+/// can not know them without solving the puzzle.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Generated {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Generates random, syntactically valid puzzle inputs for a day.
+pub trait Generator {
+    /// `size` is a rough measure of the input size, usually the number of lines or records.
+    /// Each generator documents what it means for its day and clamps it to what the puzzle allows.
+    fn generate(rng: &mut Rng, size: usize) -> Generated;
+
+    /// The parameters to solve mutated inputs with when fuzzing, for days that take long to solve
+    /// with their defaults.
+    fn fuzz_params() -> Params {
+        Params::new()
+    }
+}
+
+/// Solves the generated input and compares the answers with the planted ones. Returns the
+/// mismatches, so an empty list means the solution agrees with the generator.
+pub fn check_planted<S: Solution>(generated: &Generated) -> Result<Vec<String>, String> {
+    let answers = solve::<S>(&generated.input, &Params::new())
+        .map_err(|e| format!("Unable to parse generated input:\n{}", e))?;
+    let mut mismatches = Vec::with_capacity(2);
+    let parts = [
+        (1, &generated.part1, Some(&answers.part1)),
+        (2, &generated.part2, answers.part2.as_ref()),
+    ];
+    for (part, planted, actual) in parts {
+        match (planted, actual.map(|part| &part.answer)) {
+            (None, _) => (),
+            (Some(planted), Some(Ok(answer))) if answer == planted => (),
+            (Some(planted), Some(Ok(answer))) => mismatches.push(format!(
+                "part {}: planted {}, got {}",
+                part, planted, answer
+            )),
+            (Some(planted), Some(Err(e))) => mismatches.push(format!(
+                "part {}: planted {}, but it failed: {}",
+                part, planted, e
+            )),
+            (Some(planted), None) => mismatches.push(format!(
+                "part {}: planted {}, but there is no part {}",
+                part, planted, part
+            )),
+        }
+    }
+    Ok(mismatches)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rng_is_deterministic() {
+        // given
+        let mut rng1 = Rng::new(42);
+        let mut rng2 = Rng::new(42);
+
+        // when
+        let numbers1: Vec<u64> = (0..10).map(|_| rng1.next_u64()).collect();
+        let numbers2: Vec<u64> = (0..10).map(|_| rng2.next_u64()).collect();
+
+        // then
+        assert_eq!(numbers1, numbers2);
+        assert_ne!(numbers1[0], numbers1[1]);
+    }
+
+    #[test]
+    fn between_stays_within_bounds() {
+        // given
+        let mut rng = Rng::new(7);
+
+        // when
+        let numbers: Vec<i64> = (0..1000).map(|_| rng.between(-3, 3)).collect();
+
+        // then
+        assert!(numbers.iter().all(|n| (-3..=3).contains(n)));
+        assert!(numbers.contains(&-3));
+        assert!(numbers.contains(&3));
+    }
+}
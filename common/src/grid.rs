@@ -0,0 +1,424 @@
+use crate::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// The offsets of the four neighbours that share an edge with a cell, as (dx, dy)
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The offsets of all eight neighbours of a cell, including the diagonal ones, as (dx, dy)
+pub const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A cell of a grid that is written as a single character in the puzzle input
+pub trait CharCell: Sized {
+    /// What the parser expects if a character is not a valid cell, e.g. "a tree ('#') or open
+    /// ground ('.')"
+    const EXPECTED: &'static str;
+
+    fn from_char(c: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+}
+
+/// `#` is `true`, `.` is `false`
+impl CharCell for bool {
+    const EXPECTED: &'static str = "'#' or '.'";
+
+    fn from_char(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+/// A rectangular grid of cells, stored row by row. `x` is the column, `y` the row, counted from the
+/// top left.
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells, row by row. The number of cells must be a multiple of the
+    /// width.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells do not fill rows of width {}",
+            cells.len(),
+            width
+        );
+        let height = cells.len() / width;
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid with the cell at (x, y) set to `cell(x, y)`.
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut cell: impl FnMut(usize, usize) -> T,
+    ) -> Grid<T> {
+        let cells = (0..width * height)
+            .map(|i| cell(i % width, i / width))
+            .collect();
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// All cells, row by row
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// The rows of the grid, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// All positions (x, y), row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// The cell at (x, y), `None` outside of the grid
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at (x, y) of the grid repeated infinitely in all directions. The grid must not be
+    /// empty.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self.cells[y * self.width + x]
+    }
+
+    /// The position (x + dx, y + dy), `None` if it is outside of the grid
+    pub fn offset(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx).filter(|x| *x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|y| *y < self.height)?;
+        Some((x, y))
+    }
+
+    /// The positions of the (up to) four neighbours that share an edge with (x, y)
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS4
+            .iter()
+            .filter_map(move |(dx, dy)| self.offset(x, y, *dx, *dy))
+    }
+
+    /// The positions of the (up to) eight neighbours of (x, y), including the diagonal ones
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS8
+            .iter()
+            .filter_map(move |(dx, dy)| self.offset(x, y, *dx, *dy))
+    }
+
+    /// The cells seen from (x, y) when looking in direction (dx, dy), up to the edge of the grid.
+    /// The cell at (x, y) itself is not included. The direction must not be (0, 0).
+    pub fn ray(&self, x: usize, y: usize, dx: isize, dy: isize) -> impl Iterator<Item = &T> + '_ {
+        assert!(dx != 0 || dy != 0, "a ray needs a direction");
+        let mut position = (x, y);
+        std::iter::from_fn(move || {
+            position = self.offset(position.0, position.1, dx, dy)?;
+            self.get(position.0, position.1)
+        })
+    }
+
+    /// Applies `f` to all cells.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with all cells set to `cell`.
+    pub fn new(width: usize, height: usize, cell: T) -> Grid<T> {
+        Grid {
+            cells: vec![cell; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// The grid rotated by 90 degrees counterclockwise
+    pub fn rotate_counterclockwise(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |x, y| {
+            self.cells[(self.width - 1 - y) + x * self.width].clone()
+        })
+    }
+
+    /// The grid mirrored at its vertical axis, i.e. left and right are swapped
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |x, y| {
+            self.cells[(self.width - 1 - x) + y * self.width].clone()
+        })
+    }
+
+    /// All eight rotations and flips of the grid: the four rotations by 90 degrees
+    /// counterclockwise, then the four rotations of the flipped grid
+    pub fn orientations(&self) -> [Grid<T>; 8] {
+        let rot90 = self.rotate_counterclockwise();
+        let rot180 = rot90.rotate_counterclockwise();
+        let rot270 = rot180.rotate_counterclockwise();
+
+        let flipped = self.flip_horizontal();
+        let flipped_rot90 = flipped.rotate_counterclockwise();
+        let flipped_rot180 = flipped_rot90.rotate_counterclockwise();
+        let flipped_rot270 = flipped_rot180.rotate_counterclockwise();
+
+        [
+            self.clone(),
+            rot90,
+            rot180,
+            rot270,
+            flipped,
+            flipped_rot90,
+            flipped_rot180,
+            flipped_rot270,
+        ]
+    }
+}
+
+impl<T: CharCell> Grid<T> {
+    /// Parses a character map, one row per line. All rows must have the same length, empty lines
+    /// at the end are ignored.
+    pub fn parse(input: &str) -> Result<Grid<T>, ParseError> {
+        let mut lines: Vec<&str> = input.split_terminator('\n').collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let width = lines
+            .first()
+            .filter(|line| !line.is_empty())
+            .ok_or_else(|| ParseError::missing_field(input, "rows of the map"))?
+            .chars()
+            .count();
+        if let Some(line) = lines.iter().find(|l| l.chars().count() != width) {
+            return Err(ParseError::bad_layout(
+                line,
+                format!("row has a different length than the first row ({})", width),
+            ));
+        }
+        let cells = lines
+            .iter()
+            .flat_map(|line| line.char_indices().map(move |(i, c)| (line, i, c)))
+            .map(|(line, i, c)| {
+                T::from_char(c).ok_or_else(|| {
+                    ParseError::unexpected_token(&line[i..i + c.len_utf8()], T::EXPECTED)
+                })
+            })
+            .collect::<Result<Vec<T>, ParseError>>()?;
+
+        Ok(Grid {
+            cells,
+            width,
+            height: lines.len(),
+        })
+    }
+}
+
+/// The cell at (x, y). Panics outside of the grid.
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the grid", x, y))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the grid", x, y))
+    }
+}
+
+/// Writes the grid as a character map, one line per row
+impl<T: CharCell> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell.to_char())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[test]
+    fn parse_inverts_display() {
+        // given
+        let input = "..#.\n###.\n...#\n";
+
+        // when
+        let grid = Grid::<bool>::parse(input).expect("Expected valid grid");
+
+        // then
+        assert_eq!(grid.width(), 4);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.get(2, 0), Some(&true));
+        assert_eq!(grid.get(3, 0), Some(&false));
+        assert_eq!(grid.get(4, 0), None);
+        assert_eq!(grid.to_string(), input);
+    }
+
+    #[test]
+    fn parse_ignores_trailing_empty_lines() {
+        // when
+        let grid = Grid::<bool>::parse("..#\n#..\n\n\n").expect("Expected valid grid");
+
+        // then
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+    }
+
+    #[test]
+    fn parse_fails_for_rows_of_different_length() {
+        // given
+        let input = "..#.\n##\n...#";
+
+        // when
+        let result = Grid::<bool>::parse(input);
+
+        // then
+        let error = result
+            .map_err(|e| e.locate(input))
+            .expect_err("Expected parsing to fail");
+        assert!(matches!(error.kind(), ErrorKind::BadLayout { .. }));
+        assert_eq!(error.location().map(|l| l.line), Some(2));
+    }
+
+    #[test]
+    fn get_wrapping_repeats_the_grid() {
+        // given
+        let grid = Grid::from_vec(2, vec![1, 2, 3, 4]);
+
+        // when
+        let cells = [
+            grid.get_wrapping(2, 0),
+            grid.get_wrapping(-1, 0),
+            grid.get_wrapping(0, 3),
+        ];
+
+        // then
+        assert_eq!(cells, [&1, &2, &3]);
+    }
+
+    #[test]
+    fn neighbours_stay_within_the_grid() {
+        // given
+        let grid = Grid::new(3, 3, 0);
+
+        // when
+        let corner: Vec<(usize, usize)> = grid.neighbours8(0, 0).collect();
+        let center4 = grid.neighbours4(1, 1).count();
+
+        // then
+        assert_eq!(corner, vec![(1, 0), (0, 1), (1, 1)]);
+        assert_eq!(center4, 4);
+    }
+
+    #[test]
+    fn ray_stops_at_the_edge() {
+        // given
+        let grid = Grid::from_fn(4, 4, |x, y| x + 10 * y);
+
+        // when
+        let diagonal: Vec<usize> = grid.ray(1, 1, 1, 1).copied().collect();
+        let left: Vec<usize> = grid.ray(1, 1, -1, 0).copied().collect();
+
+        // then
+        assert_eq!(diagonal, vec![22, 33]);
+        assert_eq!(left, vec![10]);
+    }
+
+    #[test]
+    fn rotate_counterclockwise_rotates_correctly() {
+        // given
+        let grid = Grid::from_vec(3, vec![1, 2, 3, 4, 5, 6]);
+
+        // when
+        let rotated = grid.rotate_counterclockwise();
+
+        // then
+        assert_eq!(rotated, Grid::from_vec(2, vec![3, 6, 2, 5, 1, 4]));
+        assert_eq!(
+            rotated
+                .rotate_counterclockwise()
+                .rotate_counterclockwise()
+                .rotate_counterclockwise(),
+            grid
+        );
+    }
+
+    #[test]
+    fn flip_horizontal_swaps_left_and_right() {
+        // given
+        let grid = Grid::from_vec(3, vec![1, 2, 3, 4, 5, 6]);
+
+        // when
+        let flipped = grid.flip_horizontal();
+
+        // then
+        assert_eq!(flipped, Grid::from_vec(3, vec![3, 2, 1, 6, 5, 4]));
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{print_answers, solve, Params, Solution};
+use std::fs::read_to_string;
+use std::io::{stdin, Read};
+use std::path::Path;
+
+/// The file name that stands for stdin
+pub const STDIN: &str = "-";
+
+/// Reads the puzzle input from the given file, or from stdin if the file name is `-`.
+pub fn read_input(path: &Path) -> Result<String, String> {
+    if path == Path::new(STDIN) {
+        let mut content = String::new();
+        stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Unable to read stdin: {}", e))?;
+        Ok(content)
+    } else {
+        read_to_string(path).map_err(|e| format!("Unable to read '{}': {}", path.display(), e))
+    }
+}
+
+/// The name of an input file for messages
+pub fn input_name(path: &Path) -> String {
+    if path == Path::new(STDIN) {
+        "<stdin>".to_owned()
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// Solves each of the input files given as arguments and prints the answers. If there is more
+/// than one file, the answers are preceded by the file name. Failing files do not stop the other
+/// files from being solved.
+///
+/// Arguments of the form `--name value` are parameters for the day. If only parameters are given,
+/// the day is solved once with an empty input.
+pub fn solve_files<S: Solution>(args: &[String]) -> Result<(), String> {
+    let (filenames, params) = parse_args(args)?;
+    if filenames.is_empty() {
+        if params.is_empty() {
+            return Err("No file name given.".to_owned());
+        }
+        let answers = solve::<S>("", &params).map_err(|e| e.in_file("<none>").to_string())?;
+        print_answers(&answers);
+        return Ok(());
+    }
+
+    for_each_file(&filenames, |path| solve_file::<S>(path, &params))
+}
+
+/// Calls `run` for each of the files. If there is more than one file, the output of each is
+/// preceded by the file name. Failing files do not stop the other files, their errors are printed
+/// to stderr and the failing files are listed in the error at the end.
+pub fn for_each_file(
+    filenames: &[&str],
+    mut run: impl FnMut(&Path) -> Result<(), String>,
+) -> Result<(), String> {
+    if filenames.is_empty() {
+        return Err("No file name given.".to_owned());
+    }
+    let mut failed: Vec<String> = Vec::with_capacity(filenames.len());
+    for filename in filenames {
+        let path = Path::new(filename);
+        if filenames.len() > 1 {
+            println!("{}", input_name(path));
+        }
+        if let Err(e) = run(path) {
+            eprintln!("{}", e);
+            failed.push(input_name(path));
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed inputs: {}", failed.join(", ")))
+    }
+}
+
+/// Another way to run a day than solving the puzzle, e.g. an audit of the input. It is selected
+/// by its flag among the arguments, see `run_day`.
+#[derive(Clone, Copy, Debug)]
+pub struct Mode {
+    /// the flag without `--`
+    pub flag: &'static str,
+    /// whether the flag has a value, like `--render 3/1`. The flag and its value are passed on as
+    /// a parameter. Flags without a value are removed from the arguments.
+    pub takes_value: bool,
+    /// runs the mode with the remaining arguments
+    pub run: fn(&[String]) -> Result<(), String>,
+}
+
+/// Runs the first of the modes whose flag is among the arguments, `None` if there is none.
+pub fn run_mode(modes: &[Mode], args: &[String]) -> Option<Result<(), String>> {
+    modes.iter().find_map(|mode| {
+        let flag = format!("--{}", mode.flag);
+        let position = args.iter().position(|arg| *arg == flag)?;
+        let mut args = args.to_vec();
+        if !mode.takes_value {
+            args.remove(position);
+        }
+        Some((mode.run)(&args))
+    })
+}
+
+/// Runs one of the modes of the day if its flag is given, see `Solution::MODES`, and otherwise
+/// solves the files like `solve_files`.
+pub fn run_day<S: Solution>(args: &[String]) -> Result<(), String> {
+    run_mode(S::MODES, args).unwrap_or_else(|| solve_files::<S>(args))
+}
+
+/// Splits the command line arguments of a day into file names and `--name value` parameters.
+pub fn parse_args(args: &[String]) -> Result<(Vec<&str>, Params), String> {
+    let mut filenames = Vec::with_capacity(args.len());
+    let mut params = Params::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.strip_prefix("--") {
+            Some(name) => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| format!("Parameter '{}' requires a value", arg))?;
+                if params.insert(name, value).is_some() {
+                    return Err(format!("Parameter '{}' is given more than once", arg));
+                }
+            }
+            None => filenames.push(arg.as_str()),
+        }
+    }
+    Ok((filenames, params))
+}
+
+fn solve_file<S: Solution>(path: &Path, params: &Params) -> Result<(), String> {
+    let content = read_input(path)?;
+    let answers =
+        solve::<S>(&content, params).map_err(|e| e.in_file(&input_name(path)).to_string())?;
+    print_answers(&answers);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn input_name_names_stdin() {
+        assert_eq!(input_name(Path::new("-")), "<stdin>");
+        assert_eq!(input_name(Path::new("input.txt")), "input.txt");
+    }
+
+    #[test]
+    fn parse_args_separates_parameters_from_file_names() {
+        // given
+        let args: Vec<String> = ["input.txt", "--turns", "10", "-"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        // when
+        let result = parse_args(&args);
+
+        // then
+        let (filenames, params) = result.expect("Expected valid arguments");
+        assert_eq!(filenames, vec!["input.txt", "-"]);
+        assert_eq!(params.get_str("turns"), Some("10"));
+    }
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn run_mode_removes_flags_without_value() {
+        // given
+        fn echo(args: &[String]) -> Result<(), String> {
+            Err(args.join(" "))
+        }
+        let modes = [
+            Mode {
+                flag: "audit",
+                takes_value: false,
+                run: echo,
+            },
+            Mode {
+                flag: "render",
+                takes_value: true,
+                run: echo,
+            },
+        ];
+
+        // when
+        let audit = run_mode(&modes, &strings(&["a.txt", "--audit", "--format", "csv"]));
+        let render = run_mode(&modes, &strings(&["--render", "3/1", "a.txt"]));
+        let none = run_mode(&modes, &strings(&["a.txt", "--policy", "p.txt"]));
+
+        // then
+        assert_eq!(audit, Some(Err("a.txt --format csv".to_owned())));
+        assert_eq!(render, Some(Err("--render 3/1 a.txt".to_owned())));
+        assert_eq!(none, None);
+    }
+
+    #[test]
+    fn solve_files_requires_a_file() {
+        struct Nothing;
+        impl Solution for Nothing {
+            type Input<'a> = ();
+            type Answer1 = u8;
+            type Answer2 = u8;
+
+            fn parse(_input: &str) -> Result<(), crate::ParseError> {
+                Ok(())
+            }
+            fn part1(_input: &()) -> Result<u8, String> {
+                Ok(0)
+            }
+            fn part2(_input: &()) -> Result<u8, String> {
+                Ok(0)
+            }
+        }
+
+        assert_eq!(
+            solve_files::<Nothing>(&[]),
+            Err("No file name given.".to_owned())
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+pub mod automaton;
+pub mod bench;
+mod error;
+pub mod fuzz;
+pub mod generate;
+pub mod grid;
+mod input;
+pub mod json;
+pub mod num;
+pub mod parallel;
+mod params;
+pub mod toml;
+pub mod trace;
+
+pub use error::{end_of, parse_int, ErrorKind, Location, ParseError};
+pub use input::{
+    for_each_file, input_name, parse_args, read_input, run_day, run_mode, solve_files, Mode, STDIN,
+};
+pub use params::Params;
+
+/// A solution for a single day: parses the puzzle input and solves both parts of the puzzle.
+///
+/// Parsing is done once, both parts work on the parsed input. The input may borrow from the raw
+/// puzzle text.
+pub trait Solution {
+    type Input<'a>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// `false` for days that only have a single puzzle (i.e. day 25)
+    const HAS_PART2: bool = true;
+
+    /// Names of the parameters the day accepts, see `parse_with_params`
+    const PARAMS: &'static [&'static str] = &[];
+
+    /// Those of `PARAMS` that name a file to read
+    const FILE_PARAMS: &'static [&'static str] = &[];
+
+    /// Other ways to run the day than solving the puzzle, see `run_day`
+    const MODES: &'static [Mode] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    /// Parses the input, taking parameters from the command line into account. Only called with
+    /// parameters listed in `PARAMS`. Days without parameters only need to implement `parse`.
+    fn parse_with_params<'a>(
+        input: &'a str,
+        _params: &Params,
+    ) -> Result<Self::Input<'a>, ParseError> {
+        Self::parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Answer1, String>;
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Answer2, String>;
+}
+
+thread_local! {
+    static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Reports something noteworthy that does not stop the solution, e.g. statistics about a brute
+/// force approach. Warnings are reported together with the answer of the part that emitted them.
+pub fn warn(message: impl Into<String>) {
+    WARNINGS.with(|warnings| warnings.borrow_mut().push(message.into()));
+}
+
+pub(crate) fn take_warnings() -> Vec<String> {
+    WARNINGS.with(|warnings| warnings.take())
+}
+
+/// The result of solving one part of a day.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Part {
+    pub answer: Result<String, String>,
+    /// time spent solving the part, without parsing
+    pub elapsed: Duration,
+    /// warnings emitted while solving the part. Warnings from parsing belong to part 1.
+    pub warnings: Vec<String>,
+}
+
+/// The answers for both parts of a day, converted to strings so days can be treated alike.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Answers {
+    pub part1: Part,
+    /// `None` if the day has no second part
+    pub part2: Option<Part>,
+}
+
+/// Parses the input and solves both parts. Fails only if the input can not be parsed,
+/// failures to solve one of the parts are reported in the answers. Unknown parameters are a
+/// parse error.
+pub fn solve<S: Solution>(input: &str, params: &Params) -> Result<Answers, ParseError> {
+    // drop leftovers of anything that ran before on this thread
+    take_warnings();
+    params.check_known(S::PARAMS)?;
+    let parsed = S::parse_with_params(input, params).map_err(|e| e.locate(input))?;
+    let part1 = solve_part(|| S::part1(&parsed));
+    let part2 = if S::HAS_PART2 {
+        Some(solve_part(|| S::part2(&parsed)))
+    } else {
+        None
+    };
+    Ok(Answers { part1, part2 })
+}
+
+fn solve_part<T: Display>(solver: impl FnOnce() -> Result<T, String>) -> Part {
+    let start = Instant::now();
+    let answer = solver().map(|answer| answer.to_string());
+    let elapsed = start.elapsed();
+    Part {
+        answer,
+        elapsed,
+        warnings: take_warnings(),
+    }
+}
+
+pub fn print_answers(answers: &Answers) {
+    print_part(1, &answers.part1);
+    if let Some(part2) = &answers.part2 {
+        print_part(2, part2);
+    }
+}
+
+fn print_part(part: u8, result: &Part) {
+    for warning in &result.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    match &result.answer {
+        Ok(answer) => println!("Part {}: {}", part, answer),
+        Err(e) => println!("Part {} failed: {}", part, e),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input<'a> = Vec<&'a str>;
+        type Answer1 = usize;
+        type Answer2 = u32;
+
+        fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+            Ok(input.split_terminator('\n').collect())
+        }
+
+        fn part1(input: &Vec<&str>) -> Result<usize, String> {
+            Ok(input.len())
+        }
+
+        fn part2(input: &Vec<&str>) -> Result<u32, String> {
+            input
+                .iter()
+                .map(|s| s.parse::<u32>().map_err(|e| e.to_string()))
+                .sum()
+        }
+    }
+
+    #[test]
+    fn solve_solves_both_parts() {
+        // given
+        let input = "1\n2\n3\n";
+
+        // when
+        let result = solve::<Sum>(input, &Params::new());
+
+        // then
+        let answers = result.expect("Expected parsing to succeed");
+        assert_eq!(answers.part1.answer, Ok("3".to_owned()));
+        assert_eq!(
+            answers.part2.map(|part| part.answer),
+            Some(Ok("6".to_owned()))
+        );
+    }
+
+    #[test]
+    fn solve_reports_failure_of_single_part() {
+        // given
+        let input = "1\nbanana\n";
+
+        // when
+        let result = solve::<Sum>(input, &Params::new());
+
+        // then
+        let answers = result.expect("Expected parsing to succeed");
+        assert_eq!(answers.part1.answer, Ok("2".to_owned()));
+        assert!(matches!(
+            answers.part2.map(|part| part.answer),
+            Some(Err(_))
+        ));
+    }
+
+    struct Noisy;
+
+    impl Solution for Noisy {
+        type Input<'a> = ();
+        type Answer1 = u8;
+        type Answer2 = u8;
+
+        fn parse(_input: &str) -> Result<(), ParseError> {
+            warn("parsed nothing");
+            Ok(())
+        }
+
+        fn part1(_input: &()) -> Result<u8, String> {
+            Ok(1)
+        }
+
+        fn part2(_input: &()) -> Result<u8, String> {
+            warn("part 2 is suspicious");
+            Ok(2)
+        }
+    }
+
+    #[test]
+    fn solve_reports_warnings_with_their_part() {
+        // given
+        warn("left over from before");
+
+        // when
+        let result = solve::<Noisy>("", &Params::new());
+
+        // then
+        let answers = result.expect("Expected parsing to succeed");
+        assert_eq!(answers.part1.warnings, vec!["parsed nothing".to_owned()]);
+        assert_eq!(
+            answers.part2.map(|part| part.warnings),
+            Some(vec!["part 2 is suspicious".to_owned()])
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use crate::{Params, ParseError};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The parameter that selects the arithmetic of a day, see `Arithmetic`
+pub const ARITHMETIC: &str = "arithmetic";
+
+/// How days that compute large numbers do their arithmetic
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Arithmetic {
+    /// fixed-width integers, an overflow is an error
+    #[default]
+    Checked,
+    /// integers that grow as needed
+    Big,
+}
+
+impl Arithmetic {
+    /// Reads `--arithmetic checked|big`, `Checked` if it is not given.
+    pub fn from_params(params: &Params) -> Result<Arithmetic, ParseError> {
+        match params.get_str(ARITHMETIC) {
+            None | Some("checked") => Ok(Arithmetic::Checked),
+            Some("big") => Ok(Arithmetic::Big),
+            Some(value) => Err(ParseError::unexpected_token(value, "'checked' or 'big'")),
+        }
+    }
+}
+
+/// An integer type the numeric solutions can compute with. The operations return `None` on
+/// overflow; use `add`, `sub` and `mul` to get an error that names the operation.
+pub trait Int: Clone + PartialEq + fmt::Display + fmt::Debug {
+    /// how the type is called in overflow errors
+    const NAME: &'static str;
+
+    /// `None` if the number does not fit
+    fn from_i128(n: i128) -> Option<Self>;
+    fn try_add(&self, other: &Self) -> Option<Self>;
+    fn try_sub(&self, other: &Self) -> Option<Self>;
+    fn try_mul(&self, other: &Self) -> Option<Self>;
+    /// The remainder of the division by `m`, never negative
+    fn rem_u64(&self, m: u64) -> u64;
+}
+
+macro_rules! impl_int {
+    ($t:ty) => {
+        impl Int for $t {
+            const NAME: &'static str = stringify!($t);
+
+            fn from_i128(n: i128) -> Option<Self> {
+                Self::try_from(n).ok()
+            }
+
+            fn try_add(&self, other: &Self) -> Option<Self> {
+                self.checked_add(*other)
+            }
+
+            fn try_sub(&self, other: &Self) -> Option<Self> {
+                self.checked_sub(*other)
+            }
+
+            fn try_mul(&self, other: &Self) -> Option<Self> {
+                self.checked_mul(*other)
+            }
+
+            fn rem_u64(&self, m: u64) -> u64 {
+                (*self as i128).rem_euclid(m as i128) as u64
+            }
+        }
+    };
+}
+
+impl_int!(i32);
+impl_int!(i64);
+impl_int!(u64);
+
+/// Converts a number into `N`, naming `what` it is if it does not fit.
+pub fn int<N: Int>(n: impl Into<i128>, what: &str) -> Result<N, String> {
+    let n = n.into();
+    N::from_i128(n).ok_or_else(|| format!("{} {} does not fit into {}", what, n, N::NAME))
+}
+
+pub fn add<N: Int>(a: &N, b: &N, operation: &str) -> Result<N, String> {
+    a.try_add(b).ok_or_else(|| overflow(operation, a, "+", b))
+}
+
+pub fn sub<N: Int>(a: &N, b: &N, operation: &str) -> Result<N, String> {
+    a.try_sub(b).ok_or_else(|| overflow(operation, a, "-", b))
+}
+
+pub fn mul<N: Int>(a: &N, b: &N, operation: &str) -> Result<N, String> {
+    a.try_mul(b).ok_or_else(|| overflow(operation, a, "*", b))
+}
+
+fn overflow<N: Int>(operation: &str, a: &N, operator: &str, b: &N) -> String {
+    format!(
+        "Overflow in {}: {} {} {} does not fit into {}, try --{} big",
+        operation,
+        a,
+        operator,
+        b,
+        N::NAME,
+        ARITHMETIC
+    )
+}
+
+/// An answer computed with fixed-width or with big integers
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Number<N> {
+    Fixed(N),
+    Big(BigInt),
+}
+
+impl<N: fmt::Display> fmt::Display for Number<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Number::Fixed(n) => write!(f, "{}", n),
+            Number::Big(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// A signed integer of any size
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    // base 2^32, least significant digit first, without leading zeros. Zero has no digits and is
+    // not negative.
+    digits: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    fn from_digits(negative: bool, mut digits: Vec<u32>) -> BigInt {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        BigInt {
+            negative: negative && !digits.is_empty(),
+            digits,
+        }
+    }
+
+    fn negated(&self) -> BigInt {
+        BigInt::from_digits(!self.negative, self.digits.clone())
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(n: i128) -> BigInt {
+        let mut magnitude = n.unsigned_abs();
+        let mut digits = Vec::with_capacity(4);
+        while magnitude > 0 {
+            digits.push(magnitude as u32);
+            magnitude >>= 32;
+        }
+        BigInt::from_digits(n < 0, digits)
+    }
+}
+
+fn compare_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let digit = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(digit as u32);
+        carry = digit >> 32;
+    }
+    sum.push(carry as u32);
+    sum
+}
+
+// `a` must not be smaller than `b`
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, digit) in a.iter().enumerate() {
+        let mut digit = *digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if digit < 0 {
+            digit += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(digit as u32);
+    }
+    difference
+}
+
+fn mul_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, y) in b.iter().enumerate() {
+            let digit = product[i + j] as u64 + *x as u64 * *y as u64 + carry;
+            product[i + j] = digit as u32;
+            carry = digit >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    product
+}
+
+// divides in place and returns the remainder
+fn div_rem_small(digits: &mut [u32], divisor: u64) -> u64 {
+    let mut remainder = 0u128;
+    for digit in digits.iter_mut().rev() {
+        let current = remainder << 32 | *digit as u128;
+        *digit = (current / divisor as u128) as u32;
+        remainder = current % divisor as u128;
+    }
+    remainder as u64
+}
+
+impl Int for BigInt {
+    const NAME: &'static str = "big integers";
+
+    fn from_i128(n: i128) -> Option<Self> {
+        Some(BigInt::from(n))
+    }
+
+    fn try_add(&self, other: &Self) -> Option<Self> {
+        if self.negative == other.negative {
+            return Some(BigInt::from_digits(
+                self.negative,
+                add_magnitudes(&self.digits, &other.digits),
+            ));
+        }
+        Some(match compare_magnitudes(&self.digits, &other.digits) {
+            Ordering::Less => {
+                BigInt::from_digits(other.negative, sub_magnitudes(&other.digits, &self.digits))
+            }
+            _ => BigInt::from_digits(self.negative, sub_magnitudes(&self.digits, &other.digits)),
+        })
+    }
+
+    fn try_sub(&self, other: &Self) -> Option<Self> {
+        self.try_add(&other.negated())
+    }
+
+    fn try_mul(&self, other: &Self) -> Option<Self> {
+        Some(BigInt::from_digits(
+            self.negative != other.negative,
+            mul_magnitudes(&self.digits, &other.digits),
+        ))
+    }
+
+    fn rem_u64(&self, m: u64) -> u64 {
+        let remainder = div_rem_small(&mut self.digits.clone(), m);
+        if self.negative && remainder != 0 {
+            m - remainder
+        } else {
+            remainder
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitudes(&self.digits, &other.digits),
+            (true, true) => compare_magnitudes(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: u64 = 1_000_000_000;
+        let mut magnitude = self.digits.clone();
+        // groups of nine decimal digits, least significant first
+        let mut chunks: Vec<u64> = Vec::new();
+        while magnitude.iter().any(|digit| *digit != 0) {
+            chunks.push(div_rem_small(&mut magnitude, CHUNK));
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((first, rest)) => {
+                write!(f, "{}", first)?;
+                for chunk in rest.iter().rev() {
+                    write!(f, "{:09}", chunk)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn big(n: i128) -> BigInt {
+        BigInt::from(n)
+    }
+
+    #[test]
+    fn big_int_computes_like_i128() {
+        // given
+        let numbers: [i128; 9] = [
+            0,
+            1,
+            -1,
+            4_294_967_295,
+            -4_294_967_296,
+            123_456_789_012_345,
+            -987_654_321_987_654_321,
+            i64::MAX as i128,
+            i64::MIN as i128,
+        ];
+
+        for a in numbers {
+            for b in numbers {
+                // when
+                let sum = big(a).try_add(&big(b));
+                let difference = big(a).try_sub(&big(b));
+                let product = big(a).try_mul(&big(b));
+
+                // then
+                assert_eq!(sum, Some(big(a + b)), "{} + {}", a, b);
+                assert_eq!(difference, Some(big(a - b)), "{} - {}", a, b);
+                assert_eq!(product, Some(big(a * b)), "{} * {}", a, b);
+                assert_eq!(big(a).cmp(&big(b)), a.cmp(&b), "{} <=> {}", a, b);
+            }
+            assert_eq!(big(a).to_string(), a.to_string());
+            assert_eq!(big(a).rem_u64(97), a.rem_euclid(97) as u64);
+        }
+    }
+
+    #[test]
+    fn big_int_grows_beyond_fixed_width() {
+        // given
+        let mut factorial = big(1);
+
+        // when
+        for n in 1..=30 {
+            factorial = factorial.try_mul(&big(n)).expect("Expected no overflow");
+        }
+
+        // then
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+        assert_eq!(factorial.rem_u64(1_000_000_007), 109_361_473);
+    }
+
+    #[test]
+    fn mul_names_operation_on_overflow() {
+        // when
+        let result = mul(&i32::MAX, &2, "product of the entries");
+
+        // then
+        assert_eq!(
+            result,
+            Err("Overflow in product of the entries: 2147483647 * 2 does not fit into i32, try --arithmetic big".to_owned())
+        );
+    }
+
+    #[test]
+    fn arithmetic_is_read_from_params() {
+        // given
+        let mut params = Params::new();
+
+        // when
+        let default = Arithmetic::from_params(&params);
+        params.insert(ARITHMETIC, "big");
+        let big = Arithmetic::from_params(&params);
+        params.insert(ARITHMETIC, "huge");
+        let unknown = Arithmetic::from_params(&params);
+
+        // then
+        assert_eq!(default, Ok(Arithmetic::Checked));
+        assert_eq!(big, Ok(Arithmetic::Big));
+        assert!(unknown.is_err());
+    }
+}
@@ -0,0 +1,297 @@
+//! Spreads work over threads. There are no persistent workers: each call of `par_map` or
+//! `par_find_first` starts its threads and joins them before it returns.
+
+use crate::{take_warnings, warn};
+use std::cell::Cell;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+static JOBS: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    // work started from a worker runs on that worker, instead of starting even more threads
+    static IN_WORKER: Cell<bool> = const { Cell::new(false) };
+    // the job count of `with_jobs`, overriding `JOBS` on this thread
+    static THREAD_JOBS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Sets how many threads `par_map` and `par_find_first` may use. 1 (the default) runs everything
+/// on the calling thread.
+pub fn set_jobs(jobs: usize) {
+    JOBS.store(jobs.max(1), Ordering::Relaxed);
+}
+
+pub fn jobs() -> usize {
+    THREAD_JOBS
+        .with(|jobs| jobs.get())
+        .unwrap_or_else(|| JOBS.load(Ordering::Relaxed))
+}
+
+/// Runs `f` with up to `jobs` threads for the work it starts on this thread, whatever `set_jobs`
+/// says. Other threads are not affected.
+pub fn with_jobs<R>(jobs: usize, f: impl FnOnce() -> R) -> R {
+    // restores the job count even if `f` panics
+    struct Restore(Option<usize>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            THREAD_JOBS.with(|jobs| jobs.set(self.0));
+        }
+    }
+    let _restore = Restore(THREAD_JOBS.with(|outer| outer.replace(Some(jobs.max(1)))));
+    f()
+}
+
+/// Applies `f` to all items, on up to `jobs()` threads. The results are in the order of the items,
+/// just like `items.iter().map(f).collect()`. Warnings of `f` are passed on to the calling thread,
+/// in the order of the items as well.
+pub fn par_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = threads_for(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let (mut results, warnings) = run(items.len(), threads, |i| Some(f(&items[i])));
+    forward(warnings, usize::MAX);
+    results.sort_unstable_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// The first `Some` result of `f` in the order of the items, like `items.iter().find_map(f)`, on
+/// up to `jobs()` threads. Items after an item that already has a result are skipped. Only the
+/// warnings of the items up to that one are passed on to the calling thread, as if they had been
+/// tried one after the other.
+pub fn par_find_first<T: Sync, R: Send>(
+    items: &[T],
+    f: impl Fn(&T) -> Option<R> + Sync,
+) -> Option<R> {
+    let threads = threads_for(items.len());
+    if threads <= 1 {
+        return items.iter().find_map(f);
+    }
+    let first_found = AtomicUsize::new(usize::MAX);
+    let (results, warnings) = run(items.len(), threads, |i| {
+        if i > first_found.load(Ordering::Relaxed) {
+            return None;
+        }
+        let result = f(&items[i])?;
+        first_found.fetch_min(i, Ordering::Relaxed);
+        Some(result)
+    });
+    let first = results.into_iter().min_by_key(|(i, _)| *i);
+    forward(warnings, first.as_ref().map_or(usize::MAX, |(i, _)| *i));
+    first.map(|(_, result)| result)
+}
+
+fn threads_for(items: usize) -> usize {
+    if IN_WORKER.with(|in_worker| in_worker.get()) {
+        1
+    } else {
+        jobs().min(items)
+    }
+}
+
+// warns about the warnings of the indices up to `last`, ordered by index
+fn forward(mut warnings: Vec<(usize, String)>, last: usize) {
+    warnings.sort_by_key(|(i, _)| *i);
+    warnings
+        .into_iter()
+        .take_while(|(i, _)| *i <= last)
+        .for_each(|(_, warning)| warn(warning));
+}
+
+// the indices with a result and the warnings of each index
+type Done<R> = (Vec<(usize, R)>, Vec<(usize, String)>);
+
+// Runs `task` for all indices in 0..len, in no particular order. Each thread starts with an equal
+// share of the indices. A thread that runs out of work steals the upper half of the remaining
+// indices of another thread.
+fn run<R: Send>(len: usize, threads: usize, task: impl Fn(usize) -> Option<R> + Sync) -> Done<R> {
+    let shares: Vec<Mutex<Range<usize>>> = (0..threads)
+        .map(|t| Mutex::new(len * t / threads..len * (t + 1) / threads))
+        .collect();
+    let shares = &shares;
+    let task = &task;
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|own| {
+                scope.spawn(move || {
+                    IN_WORKER.with(|in_worker| in_worker.set(true));
+                    let mut results: Vec<(usize, R)> = Vec::new();
+                    let mut warnings: Vec<(usize, String)> = Vec::new();
+                    while let Some(i) = next_index(shares, own) {
+                        if let Some(result) = task(i) {
+                            results.push((i, result));
+                        }
+                        warnings.extend(take_warnings().into_iter().map(|warning| (i, warning)));
+                    }
+                    (results, warnings)
+                })
+            })
+            .collect();
+        let mut results: Vec<(usize, R)> = Vec::with_capacity(len);
+        let mut warnings: Vec<(usize, String)> = Vec::new();
+        for worker in workers {
+            let (mut own_results, mut own_warnings) = worker
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e));
+            results.append(&mut own_results);
+            warnings.append(&mut own_warnings);
+        }
+        (results, warnings)
+    })
+}
+
+fn next_index(shares: &[Mutex<Range<usize>>], own: usize) -> Option<usize> {
+    if let Some(i) = lock(&shares[own]).next() {
+        return Some(i);
+    }
+    for victim in (1..shares.len()).map(|k| (own + k) % shares.len()) {
+        let stolen = {
+            let mut share = lock(&shares[victim]);
+            let middle = share.start + share.len() / 2;
+            let stolen = middle..share.end;
+            share.end = middle;
+            stolen
+        };
+        if !stolen.is_empty() {
+            let mut share = lock(&shares[own]);
+            *share = stolen;
+            return share.next();
+        }
+    }
+    None
+}
+
+// a panicking task never holds a lock, so the shares are fine even if a lock is poisoned
+fn lock(share: &Mutex<Range<usize>>) -> std::sync::MutexGuard<'_, Range<usize>> {
+    share.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn run_visits_each_index_once() {
+        for threads in 1..=5 {
+            // given
+            let len = 1000;
+
+            // when
+            let (mut results, _) = run(len, threads, Some);
+
+            // then
+            results.sort_unstable();
+            let indices: Vec<usize> = results.into_iter().map(|(i, _)| i).collect();
+            assert_eq!(indices, (0..len).collect::<Vec<usize>>());
+        }
+    }
+
+    #[test]
+    fn run_shares_uneven_work() {
+        // given
+        // the first indices take a lot longer than the others, so they have to be stolen
+        let task = |i: usize| {
+            if i < 4 {
+                thread::sleep(std::time::Duration::from_millis(20));
+            }
+            Some(thread::current().id())
+        };
+
+        // when
+        let (results, _) = run(8, 2, task);
+
+        // then
+        let slow_threads: Vec<thread::ThreadId> = results
+            .iter()
+            .filter(|(i, _)| *i < 4)
+            .map(|(_, id)| *id)
+            .collect();
+        assert!(slow_threads.iter().any(|id| *id != slow_threads[0]));
+    }
+
+    #[test]
+    fn run_finds_results_in_nested_runs() {
+        // given
+        let items: Vec<usize> = (0..20).collect();
+
+        // when
+        let (results, _) = run(items.len(), 3, |i| Some(run(i, 3, Some).0.len()));
+
+        // then
+        let mut lengths: Vec<usize> = results.into_iter().map(|(_, len)| len).collect();
+        lengths.sort_unstable();
+        assert_eq!(lengths, items);
+    }
+
+    #[test]
+    fn par_find_first_finds_first_match() {
+        // given
+        let items: Vec<u64> = (0..10_000).collect();
+
+        // when
+        let found = with_jobs(4, || {
+            par_find_first(&items, |n| Some(*n).filter(|n| n % 997 == 996))
+        });
+
+        // then
+        assert_eq!(found, Some(996));
+    }
+
+    #[test]
+    fn par_map_keeps_order_of_items() {
+        // given
+        let items: Vec<u64> = (0..10_000).collect();
+
+        // when
+        let mapped = with_jobs(4, || par_map(&items, |n| n * 2));
+
+        // then
+        assert_eq!(mapped, items.iter().map(|n| n * 2).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn par_map_forwards_warnings_in_order_of_items() {
+        // given
+        let items: Vec<usize> = (0..100).collect();
+        take_warnings();
+
+        // when
+        with_jobs(4, || par_map(&items, |n| warn(n.to_string())));
+
+        // then
+        let expected: Vec<String> = items.iter().map(|n| n.to_string()).collect();
+        assert_eq!(take_warnings(), expected);
+    }
+
+    #[test]
+    fn par_find_first_forwards_warnings_up_to_first_match() {
+        // given
+        let items: Vec<usize> = (0..100).collect();
+        take_warnings();
+
+        // when
+        let found = with_jobs(4, || {
+            par_find_first(&items, |n| {
+                warn(n.to_string());
+                Some(*n).filter(|n| *n == 10)
+            })
+        });
+
+        // then
+        let expected: Vec<String> = (0..=10).map(|n: usize| n.to_string()).collect();
+        assert_eq!(found, Some(10));
+        assert_eq!(take_warnings(), expected);
+    }
+
+    #[test]
+    fn with_jobs_restores_job_count() {
+        // when
+        let inner = with_jobs(3, || with_jobs(5, jobs));
+
+        // then
+        assert_eq!(inner, 5);
+        assert_eq!(THREAD_JOBS.with(|jobs| jobs.get()), None);
+    }
+}
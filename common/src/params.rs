@@ -0,0 +1,101 @@
+use crate::error::{parse_int, ParseError};
+use std::collections::BTreeMap;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+/// Named parameters from the command line (`--name value`) for days that are not fully
+/// described by their input, e.g. the number of turns to play.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params::default()
+    }
+
+    /// Returns the previous value if the parameter was already set
+    pub fn insert(&mut self, name: &str, value: &str) -> Option<String> {
+        self.values.insert(name.to_owned(), value.to_owned())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(|value| value.as_str())
+    }
+
+    /// Parses the parameter as integer, or returns the default if it is not set.
+    pub fn get_int<T>(&self, name: &str, default: T) -> Result<T, ParseError>
+    where
+        T: FromStr<Err = ParseIntError>,
+    {
+        match self.get_str(name) {
+            Some(value) => parse_int(value),
+            None => Ok(default),
+        }
+    }
+
+    /// Fails for the first parameter that is not in `known`.
+    pub fn check_known(&self, known: &[&str]) -> Result<(), ParseError> {
+        match self
+            .values
+            .keys()
+            .find(|name| !known.contains(&name.as_str()))
+        {
+            None => Ok(()),
+            Some(name) if known.is_empty() => Err(ParseError::unexpected_token(
+                name,
+                "no parameters for this day",
+            )),
+            Some(name) => Err(ParseError::unexpected_token(
+                name,
+                format!("one of the parameters {}", known.join(", ")),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[test]
+    fn get_int_falls_back_to_default() {
+        // given
+        let mut params = Params::new();
+        params.insert("turns", "2020");
+
+        // when
+        let turns = params.get_int("turns", 10usize);
+        let moves = params.get_int("moves", 100usize);
+
+        // then
+        assert_eq!(turns, Ok(2020));
+        assert_eq!(moves, Ok(100));
+    }
+
+    #[test]
+    fn check_known_rejects_unknown_parameters() {
+        // given
+        let mut params = Params::new();
+        params.insert("turns", "2020");
+        params.insert("banana", "yes");
+
+        // when
+        let result = params.check_known(&["turns"]);
+
+        // then
+        assert_eq!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::UnexpectedToken {
+                found: "banana".to_owned(),
+                expected: "one of the parameters turns".to_owned()
+            })
+        );
+    }
+}
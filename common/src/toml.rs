@@ -0,0 +1,344 @@
+use crate::error::{end_of, ParseError};
+
+/// A parsed value. Only the parts of TOML this project needs are supported.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+/// A `key = value` line. `raw` is the value as written in the file, for error messages.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Entry<'a> {
+    pub key: &'a str,
+    pub value: Value,
+    pub raw: &'a str,
+}
+
+/// A `[name]` or `[[name]]` table. Keys before the first header end up in a table with an
+/// empty name.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Table<'a> {
+    pub name: &'a str,
+    pub is_array: bool,
+    pub entries: Vec<Entry<'a>>,
+    /// the header line, or the empty start of the document for the unnamed table
+    pub header: &'a str,
+}
+
+impl<'a> Table<'a> {
+    pub fn get(&self, key: &str) -> Option<&Entry<'a>> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    pub fn get_str(&self, key: &str) -> Result<Option<&str>, ParseError> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Entry {
+                value: Value::String(s),
+                ..
+            }) => Ok(Some(s)),
+            Some(entry) => Err(ParseError::unexpected_token(entry.raw, "a string")),
+        }
+    }
+
+    pub fn get_int(&self, key: &str) -> Result<Option<i64>, ParseError> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Entry {
+                value: Value::Integer(i),
+                ..
+            }) => Ok(Some(*i)),
+            Some(entry) => Err(ParseError::unexpected_token(entry.raw, "an integer")),
+        }
+    }
+
+    /// Fails with an error pointing to the header if the key is missing.
+    pub fn require_int(&self, key: &str) -> Result<i64, ParseError> {
+        self.get_int(key)?
+            .ok_or_else(|| ParseError::missing_field(self.header, format!("key '{}'", key)))
+    }
+}
+
+/// Parses a small subset of TOML: comments, `[table]` and `[[array-of-table]]` headers and
+/// `key = value` pairs where the value is a string (basic or literal), an integer, a boolean
+/// or a single-line array of those. Dotted keys, inline tables, multi-line strings, floats and
+/// dates are not supported.
+pub fn parse_toml(input: &str) -> Result<Vec<Table<'_>>, ParseError> {
+    let mut tables = vec![Table {
+        name: "",
+        is_array: false,
+        entries: Vec::new(),
+        header: &input[..0],
+    }];
+
+    for line in input.lines() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[') {
+            let (name, is_array) = match header.strip_prefix('[') {
+                Some(name) => (
+                    name.strip_suffix("]]")
+                        .ok_or_else(|| ParseError::missing_field(end_of(line), "']]'"))?,
+                    true,
+                ),
+                None => (
+                    header
+                        .strip_suffix(']')
+                        .ok_or_else(|| ParseError::missing_field(end_of(line), "']'"))?,
+                    false,
+                ),
+            };
+            let name = parse_key(name.trim())?;
+            if !is_array && tables.iter().any(|t| t.name == name) {
+                return Err(ParseError::bad_layout(line, "table is defined twice"));
+            }
+            tables.push(Table {
+                name,
+                is_array,
+                entries: Vec::new(),
+                header: line,
+            });
+        } else {
+            let (key, raw) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::missing_field(end_of(line), "'= value'"))?;
+            let key = parse_key(key.trim())?;
+            let raw = raw.trim();
+            let (value, rest) = parse_value(raw)?;
+            if !rest.trim().is_empty() {
+                return Err(ParseError::unexpected_token(rest.trim(), "end of line"));
+            }
+            // there is always at least the unnamed table
+            let table = tables.last_mut().unwrap();
+            if table.get(key).is_some() {
+                return Err(ParseError::bad_layout(key, "key is defined twice"));
+            }
+            table.entries.push(Entry { key, value, raw });
+        }
+    }
+
+    Ok(tables)
+}
+
+// a '#' starts a comment unless it is inside of a string
+fn strip_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), _) if c == q && !escaped => quote = None,
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => (),
+        }
+        escaped = false;
+    }
+    line
+}
+
+fn parse_key(key: &str) -> Result<&str, ParseError> {
+    if let Some(quoted) = key.strip_prefix('"') {
+        return quoted
+            .strip_suffix('"')
+            .filter(|k| !k.contains('"') && !k.contains('\\'))
+            .ok_or_else(|| ParseError::unexpected_token(key, "a simple quoted key"));
+    }
+    if key.is_empty() {
+        return Err(ParseError::missing_field(key, "key"));
+    }
+    if let Some((i, c)) = key
+        .char_indices()
+        .find(|(_, c)| !(c.is_ascii_alphanumeric() || *c == '_' || *c == '-'))
+    {
+        return Err(ParseError::unexpected_token(
+            &key[i..i + c.len_utf8()],
+            "a bare key (letters, digits, '_' or '-')",
+        ));
+    }
+    Ok(key)
+}
+
+// parses a value at the beginning of `s` and returns the rest of `s`
+fn parse_value(s: &str) -> Result<(Value, &str), ParseError> {
+    if let Some(rest) = s.strip_prefix('"') {
+        parse_basic_string(rest)
+    } else if let Some(rest) = s.strip_prefix('\'') {
+        let end = rest
+            .find('\'')
+            .ok_or_else(|| ParseError::missing_field(end_of(s), "closing \"'\""))?;
+        Ok((Value::String(rest[..end].to_owned()), &rest[end + 1..]))
+    } else if let Some(rest) = s.strip_prefix('[') {
+        parse_array(rest)
+    } else {
+        let end = s
+            .find(|c: char| c == ',' || c == ']' || c.is_whitespace())
+            .unwrap_or(s.len());
+        let (token, rest) = s.split_at(end);
+        let value = match token {
+            "" => return Err(ParseError::missing_field(token, "value")),
+            "true" => Value::Boolean(true),
+            "false" => Value::Boolean(false),
+            _ => {
+                // TOML allows '_' between digits and an explicit '+'
+                let digits: String = token
+                    .trim_start_matches('+')
+                    .chars()
+                    .filter(|c| *c != '_')
+                    .collect();
+                if digits.is_empty()
+                    || !digits.trim_start_matches('-').starts_with(char::is_numeric)
+                {
+                    return Err(ParseError::unexpected_token(
+                        token,
+                        "a string, an integer, a boolean or an array",
+                    ));
+                }
+                let value = digits
+                    .parse::<i64>()
+                    .map_err(|e| ParseError::invalid_integer::<i64>(token, &e))?;
+                Value::Integer(value)
+            }
+        };
+        Ok((value, rest))
+    }
+}
+
+fn parse_basic_string(s: &str) -> Result<(Value, &str), ParseError> {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((Value::String(result), &s[i + 1..])),
+            '\\' => {
+                let (j, escaped) = chars
+                    .next()
+                    .ok_or_else(|| ParseError::missing_field(end_of(s), "escaped character"))?;
+                result.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    '"' => '"',
+                    '\\' => '\\',
+                    _ => {
+                        return Err(ParseError::unexpected_token(
+                            &s[i..j + escaped.len_utf8()],
+                            "one of the escapes \\n, \\t, \\r, \\\" or \\\\",
+                        ))
+                    }
+                });
+            }
+            _ => result.push(c),
+        }
+    }
+    Err(ParseError::missing_field(end_of(s), "closing '\"'"))
+}
+
+fn parse_array(s: &str) -> Result<(Value, &str), ParseError> {
+    let mut values = Vec::new();
+    let mut rest = s.trim_start();
+    loop {
+        if let Some(after) = rest.strip_prefix(']') {
+            return Ok((Value::Array(values), after));
+        }
+        let (value, after) = parse_value(rest)?;
+        values.push(value);
+        rest = after.trim_start();
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if !rest.starts_with(']') {
+            return Err(match rest.is_empty() {
+                true => ParseError::missing_field(rest, "']'"),
+                false => ParseError::unexpected_token(rest, "',' or ']'"),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    #[test]
+    fn parse_toml_parses_tables_and_values() {
+        // given
+        let input = r#"# a comment
+title = "answers" # trailing comment
+
+[[answer]]
+day = 1
+input = 'example-inputs/day-01.txt'
+part1 = "514579"
+
+[[answer]]
+day = 15
+part2 = 30_000_000
+tags = ["slow", "hash # not a comment", 3]
+"#;
+
+        // when
+        let result = parse_toml(input);
+
+        // then
+        let tables = result.expect("Expected valid toml");
+        assert_eq!(tables.len(), 3);
+        assert_eq!(tables[0].get_str("title"), Ok(Some("answers")));
+        assert_eq!(tables[1].name, "answer");
+        assert!(tables[1].is_array);
+        assert_eq!(tables[1].get_int("day"), Ok(Some(1)));
+        assert_eq!(
+            tables[1].get_str("input"),
+            Ok(Some("example-inputs/day-01.txt"))
+        );
+        assert_eq!(tables[2].get_int("part2"), Ok(Some(30_000_000)));
+        assert_eq!(
+            tables[2].get("tags").map(|e| &e.value),
+            Some(&Value::Array(vec![
+                Value::String("slow".to_owned()),
+                Value::String("hash # not a comment".to_owned()),
+                Value::Integer(3)
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_toml_fails_for_unterminated_string() {
+        // given
+        let input = "[day]\nname = \"oops\n";
+
+        // when
+        let result = parse_toml(input);
+
+        // then
+        let error = result
+            .map_err(|e| e.locate(input))
+            .expect_err("Expected parsing to fail");
+        assert!(matches!(error.kind(), ErrorKind::MissingField { .. }));
+        assert_eq!(error.location().map(|l| l.line), Some(2));
+    }
+
+    #[test]
+    fn get_int_fails_for_wrong_type() {
+        // given
+        let input = "day = \"seven\"\n";
+        let tables = parse_toml(input).expect("Expected valid toml");
+
+        // when
+        let result = tables[0].get_int("day");
+
+        // then
+        assert!(matches!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::UnexpectedToken { .. })
+        ));
+    }
+}
@@ -0,0 +1,357 @@
+use crate::grid::{CharCell, Grid};
+use crate::{Params, ParseError, Solution};
+use std::fmt::Write;
+
+/// The size of a map cell or a line of text in SVG pictures, in pixels
+const CELL_SIZE: usize = 10;
+const LINE_HEIGHT: usize = 16;
+/// The largest ASCII drawing of a path, in characters
+const PATH_WIDTH: i64 = 79;
+const PATH_HEIGHT: i64 = 39;
+
+/// A day that simulates something step by step. `aoc run --trace` records the steps.
+pub trait Trace: Solution {
+    /// The states of the given part (1 or 2), one frame per step, starting with the initial state
+    fn trace(input: &Self::Input<'_>, part: usize) -> Result<Vec<Frame>, String>;
+}
+
+/// A snapshot of the state after one step
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Frame {
+    /// what the frame shows, e.g. "round 3"
+    pub label: String,
+    pub picture: Picture,
+}
+
+impl Frame {
+    pub fn new(label: impl Into<String>, picture: Picture) -> Frame {
+        Frame {
+            label: label.into(),
+            picture,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Picture {
+    /// A map with one character per cell, row by row. Spaces are left empty.
+    Map(Vec<String>),
+    /// Free text, e.g. the cards of a deck
+    Text(String),
+    /// The positions visited so far as (x, y), with y pointing up
+    Path(Vec<(i64, i64)>),
+}
+
+impl Picture {
+    /// The grid as a map, with the same characters as in the puzzle input
+    pub fn map<T: CharCell>(grid: &Grid<T>) -> Picture {
+        Picture::Map(
+            grid.rows()
+                .map(|row| row.iter().map(|cell| cell.to_char()).collect())
+                .collect(),
+        )
+    }
+}
+
+/// Parses the input and records the frames of each part. Fails only if the input can not be
+/// parsed, failures to trace one of the parts are reported per part.
+pub fn record<S: Trace>(
+    input: &str,
+    params: &Params,
+) -> Result<Vec<Result<Vec<Frame>, String>>, ParseError> {
+    params.check_known(S::PARAMS)?;
+    let parsed = S::parse_with_params(input, params).map_err(|e| e.locate(input))?;
+    let parts = if S::HAS_PART2 { 2 } else { 1 };
+    Ok((1..=parts).map(|part| S::trace(&parsed, part)).collect())
+}
+
+/// All frames as text, each one below a header with its label
+pub fn to_ascii(frames: &[Frame]) -> String {
+    let mut ascii = String::new();
+    for frame in frames {
+        ascii.push_str("== ");
+        ascii.push_str(&frame.label);
+        ascii.push_str(" ==\n");
+        match &frame.picture {
+            Picture::Map(rows) => {
+                for row in rows {
+                    ascii.push_str(row);
+                    ascii.push('\n');
+                }
+            }
+            Picture::Text(text) => {
+                ascii.push_str(text);
+                if !text.ends_with('\n') {
+                    ascii.push('\n');
+                }
+            }
+            Picture::Path(points) => ascii.push_str(&draw_path(points)),
+        }
+        ascii.push('\n');
+    }
+    ascii
+}
+
+// scales the path down to at most PATH_WIDTH x PATH_HEIGHT characters. `S` is the start, `@` the
+// end of the path.
+fn draw_path(points: &[(i64, i64)]) -> String {
+    if points.is_empty() {
+        return String::new();
+    }
+    let (min_x, max_x, min_y, max_y) = bounds(points);
+    let scale = ((max_x - min_x) / (PATH_WIDTH - 1))
+        .max((max_y - min_y) / (PATH_HEIGHT - 1))
+        .max(0)
+        + 1;
+    let width = ((max_x - min_x) / scale + 1) as usize;
+    let height = ((max_y - min_y) / scale + 1) as usize;
+    let mut canvas = vec![vec![' '; width]; height];
+    let mut plot = |(x, y): (i64, i64), c: char| {
+        let column = ((x - min_x) / scale) as usize;
+        let row = ((max_y - y) / scale) as usize;
+        canvas[row][column] = c;
+    };
+
+    for segment in points.windows(2) {
+        let (x1, y1) = segment[0];
+        let (x2, y2) = segment[1];
+        let steps = ((x2 - x1).abs().max((y2 - y1).abs()) / scale).max(1);
+        for i in 0..=steps {
+            plot(
+                (x1 + (x2 - x1) * i / steps, y1 + (y2 - y1) * i / steps),
+                '*',
+            );
+        }
+    }
+    if let (Some(start), Some(end)) = (points.first(), points.last()) {
+        plot(*start, 'S');
+        plot(*end, '@');
+    }
+
+    canvas
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>().trim_end().to_owned() + "\n")
+        .collect()
+}
+
+fn bounds(points: &[(i64, i64)]) -> (i64, i64, i64, i64) {
+    points.iter().fold(
+        (i64::MAX, i64::MIN, i64::MAX, i64::MIN),
+        |(min_x, max_x, min_y, max_y), (x, y)| {
+            (min_x.min(*x), max_x.max(*x), min_y.min(*y), max_y.max(*y))
+        },
+    )
+}
+
+/// The frame as an SVG image: maps as coloured cells, paths as lines and text as text.
+pub fn to_svg(frame: &Frame) -> String {
+    let (width, height, body) = match &frame.picture {
+        Picture::Map(rows) => svg_map(rows),
+        Picture::Text(text) => svg_text(text),
+        Picture::Path(points) => svg_path(points),
+    };
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height + LINE_HEIGHT,
+    );
+    let _ = writeln!(svg, "<title>{}</title>", escape(&frame.label));
+    let _ = writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#ffffff"/>"##
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="2" y="12" font-family="monospace" font-size="12">{}</text>"#,
+        escape(&frame.label)
+    );
+    let _ = writeln!(svg, r#"<g transform="translate(0 {})">"#, LINE_HEIGHT);
+    svg.push_str(&body);
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+fn svg_map(rows: &[String]) -> (usize, usize, String) {
+    let width = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut body = String::new();
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate().filter(|(_, c)| *c != ' ') {
+            let _ = writeln!(
+                body,
+                r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}"><title>{}</title></rect>"#,
+                x * CELL_SIZE,
+                y * CELL_SIZE,
+                cell_colour(c),
+                escape(&c.to_string()),
+                s = CELL_SIZE,
+            );
+        }
+    }
+    (width * CELL_SIZE, rows.len() * CELL_SIZE, body)
+}
+
+// dark for '#', light for '.', and a fixed colour for each other character
+fn cell_colour(c: char) -> &'static str {
+    const PALETTE: [&str; 6] = [
+        "#4e79a7", "#f28e2b", "#59a14f", "#e15759", "#b07aa1", "#edc948",
+    ];
+    match c {
+        '#' => "#333333",
+        '.' => "#eeeeee",
+        _ => PALETTE[c as usize % PALETTE.len()],
+    }
+}
+
+fn svg_text(text: &str) -> (usize, usize, String) {
+    let mut body = String::new();
+    let mut width = 0;
+    for (i, line) in text.lines().enumerate() {
+        width = width.max(line.chars().count() * 8);
+        let _ = writeln!(
+            body,
+            r#"<text x="2" y="{}" font-family="monospace" font-size="13" xml:space="preserve">{}</text>"#,
+            (i + 1) * LINE_HEIGHT - 4,
+            escape(line)
+        );
+    }
+    (width + 4, text.lines().count() * LINE_HEIGHT, body)
+}
+
+fn svg_path(points: &[(i64, i64)]) -> (usize, usize, String) {
+    const SIZE: usize = 400;
+    if points.is_empty() {
+        return (SIZE, SIZE, String::new());
+    }
+    let (min_x, max_x, min_y, max_y) = bounds(points);
+    let extent = (max_x - min_x).max(max_y - min_y).max(1) as f64;
+    let scale = |(x, y): (i64, i64)| {
+        (
+            (x - min_x) as f64 / extent * (SIZE - 20) as f64 + 10.0,
+            (max_y - y) as f64 / extent * (SIZE - 20) as f64 + 10.0,
+        )
+    };
+    let mut body = String::new();
+    let polyline: Vec<String> = points
+        .iter()
+        .map(|point| {
+            let (x, y) = scale(*point);
+            format!("{:.1},{:.1}", x, y)
+        })
+        .collect();
+    let _ = writeln!(
+        body,
+        r##"<polyline points="{}" fill="none" stroke="#4e79a7" stroke-width="1.5"/>"##,
+        polyline.join(" ")
+    );
+    for (point, colour) in [(points.first(), "#59a14f"), (points.last(), "#e15759")] {
+        if let Some(point) = point {
+            let (x, y) = scale(*point);
+            let _ = writeln!(
+                body,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="4" fill="{}"/>"#,
+                x, y, colour
+            );
+        }
+    }
+    (SIZE, SIZE, body)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn to_ascii_writes_labels_and_pictures() {
+        // given
+        let frames = vec![
+            Frame::new(
+                "round 0",
+                Picture::Map(vec!["#.".to_owned(), ".#".to_owned()]),
+            ),
+            Frame::new("round 1", Picture::Text("Player 1: 3, 1".to_owned())),
+        ];
+
+        // when
+        let ascii = to_ascii(&frames);
+
+        // then
+        assert_eq!(
+            ascii,
+            "== round 0 ==\n#.\n.#\n\n== round 1 ==\nPlayer 1: 3, 1\n\n"
+        );
+    }
+
+    #[test]
+    fn to_ascii_draws_path_from_start_to_end() {
+        // given
+        let frames = vec![Frame::new(
+            "step 2",
+            Picture::Path(vec![(0, 0), (3, 0), (3, 2)]),
+        )];
+
+        // when
+        let ascii = to_ascii(&frames);
+
+        // then
+        assert_eq!(ascii, "== step 2 ==\n   @\n   *\nS***\n\n");
+    }
+
+    #[test]
+    fn to_ascii_scales_down_long_paths() {
+        // given
+        let frames = vec![Frame::new(
+            "far away",
+            Picture::Path(vec![(0, 0), (10_000, -5_000)]),
+        )];
+
+        // when
+        let ascii = to_ascii(&frames);
+
+        // then
+        let lines: Vec<&str> = ascii.lines().skip(1).filter(|l| !l.is_empty()).collect();
+        assert!(lines.len() as i64 <= PATH_HEIGHT);
+        assert!(lines.iter().all(|l| l.len() as i64 <= PATH_WIDTH));
+        assert!(lines[0].starts_with('S'));
+        assert!(lines[lines.len() - 1].ends_with('@'));
+    }
+
+    #[test]
+    fn to_svg_escapes_text() {
+        // given
+        let frame = Frame::new("a < b", Picture::Text("x & y".to_owned()));
+
+        // when
+        let svg = to_svg(&frame);
+
+        // then
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("<title>a &lt; b</title>"));
+        assert!(svg.contains(">x &amp; y</text>"));
+        assert!(!svg.contains("x & y"));
+    }
+
+    #[test]
+    fn to_svg_draws_a_cell_for_each_character() {
+        // given
+        let frame = Frame::new("map", Picture::Map(vec!["#L".to_owned(), " .".to_owned()]));
+
+        // when
+        let svg = to_svg(&frame);
+
+        // then
+        assert_eq!(svg.matches("<rect x=").count(), 3);
+    }
+}
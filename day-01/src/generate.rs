@@ -0,0 +1,82 @@
+use crate::Day01;
+use common::generate::{Generated, Generator, Rng};
+
+/// `size` is the number of expense entries (at least 5). Two of them sum up to 2020, three
+/// others as well. All other entries are larger than 2020, so they can not be part of a sum.
+impl Generator for Day01 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let (planted, pair, triple) = loop {
+            let a = rng.between(1011, 2000);
+            let c = rng.between(100, 600);
+            let d = rng.between(100, 600);
+            let planted = [a, 2020 - a, c, d, 2020 - c - d];
+            if is_unique(&planted) {
+                break (planted, a * (2020 - a), c * d * (2020 - c - d));
+            }
+        };
+        let mut entries: Vec<i64> = planted.to_vec();
+        while entries.len() < size {
+            entries.push(rng.between(2021, 999_999));
+        }
+        rng.shuffle(&mut entries);
+
+        Generated {
+            input: entries.iter().map(|e| format!("{}\n", e)).collect(),
+            part1: Some(pair.to_string()),
+            part2: Some(triple.to_string()),
+        }
+    }
+}
+
+// the planted numbers must be distinct, and the planted pair and triple the only ones summing up
+// to 2020
+fn is_unique(planted: &[i64; 5]) -> bool {
+    let mut pairs = 0;
+    let mut triples = 0;
+    for i in 0..planted.len() {
+        for j in (i + 1)..planted.len() {
+            if planted[i] == planted[j] {
+                return false;
+            }
+            if planted[i] + planted[j] == 2020 {
+                pairs += 1;
+            }
+            for k in (j + 1)..planted.len() {
+                if planted[i] + planted[j] + planted[k] == 2020 {
+                    triples += 1;
+                }
+            }
+        }
+    }
+    pairs == 1 && triples == 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day01::generate(&mut Rng::new(seed), 50);
+
+            // when
+            let mismatches = check_planted::<Day01>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day01>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,434 @@
+//! Day 1: Report Repair
+
+use common::num::{self, Arithmetic, Int, Number};
+use common::{parse_int, Mode, Params, ParseError, Solution};
+use std::fmt;
+
+mod generate;
+pub mod stream;
+
+pub struct Day01;
+
+/// The expense report and what to look for in it
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Report {
+    pub entries: Vec<i32>,
+    /// the sum the entries have to add up to
+    pub target: i64,
+    /// the number of entries to add up in part 1 and part 2
+    pub k: (usize, usize),
+    /// whether to list all combinations of entries or only the product of the first one
+    pub all: bool,
+    pub arithmetic: Arithmetic,
+}
+
+/// The product of the first combination of entries, or all combinations with their products
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Answer {
+    Product(Number<i32>),
+    All(Vec<(Vec<i32>, Number<i32>)>),
+}
+
+/// Writes all combinations as `979 * 366 * 675 = 241861950`, separated by commas
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Product(product) => write!(f, "{}", product),
+            Answer::All(combinations) => {
+                for (i, (entries, product)) in combinations.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    let entries: Vec<String> = entries.iter().map(|e| e.to_string()).collect();
+                    write!(f, "{} = {}", entries.join(" * "), product)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Solution for Day01 {
+    type Input<'a> = Report;
+    type Answer1 = Answer;
+    type Answer2 = Answer;
+
+    const PARAMS: &'static [&'static str] =
+        &["target", "k1", "k2", "combinations", num::ARITHMETIC];
+
+    const MODES: &'static [Mode] = &[Mode {
+        flag: "stream",
+        takes_value: false,
+        run: stream::stream_files,
+    }];
+
+    fn parse(input: &str) -> Result<Report, ParseError> {
+        Self::parse_with_params(input, &Params::new())
+    }
+
+    fn parse_with_params(input: &str, params: &Params) -> Result<Report, ParseError> {
+        let all = match params.get_str("combinations") {
+            None | Some("first") => false,
+            Some("all") => true,
+            Some(value) => return Err(ParseError::unexpected_token(value, "'first' or 'all'")),
+        };
+        let entries: Vec<i32> = input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(parse_int)
+            .collect::<Result<_, _>>()?;
+        let k = (
+            get_k(params, "k1", 2, entries.len())?,
+            get_k(params, "k2", 3, entries.len())?,
+        );
+        Ok(Report {
+            entries,
+            target: params.get_int("target", 2020)?,
+            k,
+            all,
+            arithmetic: Arithmetic::from_params(params)?,
+        })
+    }
+
+    fn part1(report: &Report) -> Result<Answer, String> {
+        audit(report, report.k.0)
+    }
+
+    fn part2(report: &Report) -> Result<Answer, String> {
+        audit(report, report.k.1)
+    }
+}
+
+// A `k` given on the command line may not exceed the number of entries.
+fn get_k(params: &Params, name: &str, default: usize, entries: usize) -> Result<usize, ParseError> {
+    let k = params.get_int(name, default)?;
+    let value = params.get_str(name);
+    if k == 0 {
+        return Err(ParseError::unexpected_token(
+            value.unwrap_or_default(),
+            "at least one entry",
+        ));
+    }
+    if let Some(value) = value.filter(|_| k > entries) {
+        return Err(ParseError::unexpected_token(
+            value,
+            format!("at most {} entries, the size of the report", entries),
+        ));
+    }
+    Ok(k)
+}
+
+fn audit(report: &Report, k: usize) -> Result<Answer, String> {
+    let no_answer = || {
+        format!(
+            "There are no {} entries that sum up to {}",
+            k, report.target
+        )
+    };
+    if report.all {
+        let combinations = find_all_k_sums(&report.entries, k, report.target);
+        if combinations.is_empty() {
+            return Err(no_answer());
+        }
+        combinations
+            .into_iter()
+            .map(|entries| {
+                let product = multiply(&entries, report.arithmetic)?;
+                Ok((entries, product))
+            })
+            .collect::<Result<_, String>>()
+            .map(Answer::All)
+    } else {
+        let entries = find_k_sum(&report.entries, k, report.target).ok_or_else(no_answer)?;
+        multiply(&entries, report.arithmetic).map(Answer::Product)
+    }
+}
+
+/// Finds `k` entries that sum up to `target`. If there are several combinations, it returns the
+/// one with the smallest entries, in ascending order.
+pub fn find_k_sum(numbers: &[i32], k: usize, target: i64) -> Option<Vec<i32>> {
+    if k > numbers.len() {
+        return None;
+    }
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    let mut first = None;
+    k_sums(
+        &sorted,
+        k,
+        target,
+        &mut Vec::with_capacity(k),
+        &mut |entries| {
+            first = Some(entries.to_vec());
+            false
+        },
+    );
+    first
+}
+
+/// All distinct combinations of `k` entries that sum up to `target`. Each combination is in
+/// ascending order, and the combinations are sorted.
+pub fn find_all_k_sums(numbers: &[i32], k: usize, target: i64) -> Vec<Vec<i32>> {
+    if k > numbers.len() {
+        return Vec::new();
+    }
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    let mut all = Vec::new();
+    k_sums(
+        &sorted,
+        k,
+        target,
+        &mut Vec::with_capacity(k),
+        &mut |entries| {
+            all.push(entries.to_vec());
+            true
+        },
+    );
+    all
+}
+
+// Calls `found` with each distinct combination of `k` of the sorted numbers that sums up to
+// `target` (after the numbers already `chosen`), smallest first. Stops as soon as `found` returns
+// false, and returns false itself then. Takes O(n^(k-1)) steps: all entries but the last two are
+// tried one by one, the last two are found with two pointers moving towards each other.
+fn k_sums(
+    sorted: &[i32],
+    k: usize,
+    target: i64,
+    chosen: &mut Vec<i32>,
+    found: &mut dyn FnMut(&[i32]) -> bool,
+) -> bool {
+    if k == 1 {
+        if sorted
+            .binary_search_by(|n| (*n as i64).cmp(&target))
+            .is_ok()
+        {
+            chosen.push(target as i32);
+            let go_on = found(chosen);
+            chosen.pop();
+            return go_on;
+        }
+        return true;
+    }
+    if k == 2 {
+        let (mut low, mut high) = (0, sorted.len());
+        while low + 1 < high {
+            let sum = sorted[low] as i64 + sorted[high - 1] as i64;
+            if sum < target {
+                low += 1;
+            } else if sum > target {
+                high -= 1;
+            } else {
+                chosen.extend_from_slice(&[sorted[low], sorted[high - 1]]);
+                let go_on = found(chosen);
+                chosen.truncate(chosen.len() - 2);
+                if !go_on {
+                    return false;
+                }
+                // skip equal entries, they would give the same combination again
+                let previous = sorted[low];
+                while low < high && sorted[low] == previous {
+                    low += 1;
+                }
+            }
+        }
+        return true;
+    }
+
+    for i in 0..sorted.len().saturating_sub(k - 1) {
+        if i > 0 && sorted[i] == sorted[i - 1] {
+            continue;
+        }
+        let smallest: i64 = sorted[i..i + k].iter().map(|n| *n as i64).sum();
+        if smallest > target {
+            break;
+        }
+        let largest: i64 = sorted[i] as i64
+            + sorted[sorted.len() - (k - 1)..]
+                .iter()
+                .map(|n| *n as i64)
+                .sum::<i64>();
+        if largest < target {
+            continue;
+        }
+        chosen.push(sorted[i]);
+        let go_on = k_sums(
+            &sorted[i + 1..],
+            k - 1,
+            target - sorted[i] as i64,
+            chosen,
+            found,
+        );
+        chosen.pop();
+        if !go_on {
+            return false;
+        }
+    }
+    true
+}
+
+/// The product of the entries. Fails if it does not fit into an `i32` with checked arithmetic.
+pub fn multiply(entries: &[i32], arithmetic: Arithmetic) -> Result<Number<i32>, String> {
+    match arithmetic {
+        Arithmetic::Checked => product(entries).map(Number::Fixed),
+        Arithmetic::Big => product(entries).map(Number::Big),
+    }
+}
+
+fn product<N: Int>(entries: &[i32]) -> Result<N, String> {
+    entries
+        .iter()
+        .try_fold(num::int(1, "one")?, |product, entry| {
+            num::mul(
+                &product,
+                &num::int(*entry, "entry")?,
+                "product of the entries",
+            )
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::num::BigInt;
+
+    const EXAMPLE: [i32; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    #[test]
+    fn find_k_sum_works_for_example() {
+        assert_eq!(find_k_sum(&EXAMPLE, 2, 2020), Some(vec![299, 1721]));
+        assert_eq!(find_k_sum(&EXAMPLE, 3, 2020), Some(vec![366, 675, 979]));
+        assert_eq!(find_k_sum(&EXAMPLE, 4, 2020), None);
+    }
+
+    #[test]
+    fn find_k_sum_finds_more_than_three_entries() {
+        // given
+        let numbers = [7, -3, 12, 5, 5, 40, 1, -8];
+
+        // when
+        let result = find_k_sum(&numbers, 5, 20);
+
+        // then
+        assert_eq!(result, Some(vec![-3, 1, 5, 5, 12]));
+    }
+
+    #[test]
+    fn find_all_k_sums_lists_distinct_combinations() {
+        // given
+        let numbers = [1, 9, 5, 5, 3, 7, 5, 1];
+
+        // when
+        let result = find_all_k_sums(&numbers, 2, 10);
+
+        // then
+        assert_eq!(result, vec![vec![1, 9], vec![3, 7], vec![5, 5]]);
+    }
+
+    #[test]
+    fn find_all_k_sums_matches_brute_force() {
+        // given
+        let numbers = [4, -2, 9, 0, 4, 7, 13, -6, 2, 4, 11, 5];
+
+        for k in 1..=4 {
+            // when
+            let result = find_all_k_sums(&numbers, k, 13);
+
+            // then
+            let mut expected: Vec<Vec<i32>> = subsets(&numbers, k)
+                .into_iter()
+                .filter(|entries| entries.iter().sum::<i32>() == 13)
+                .collect();
+            expected.sort_unstable();
+            expected.dedup();
+            assert_eq!(result, expected, "k = {}", k);
+        }
+    }
+
+    // all sorted subsets of `k` entries
+    fn subsets(numbers: &[i32], k: usize) -> Vec<Vec<i32>> {
+        if k == 0 {
+            return vec![vec![]];
+        }
+        (0..numbers.len())
+            .flat_map(|i| {
+                subsets(&numbers[i + 1..], k - 1)
+                    .into_iter()
+                    .map(move |mut rest| {
+                        rest.push(numbers[i]);
+                        rest.sort_unstable();
+                        rest
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_with_params_reads_target_and_k() {
+        // given
+        let mut params = Params::new();
+        params.insert("target", "1000");
+        params.insert("k2", "4");
+        params.insert("combinations", "all");
+
+        // when
+        let report = Day01::parse_with_params("1\n2\n3\n4\n", &params);
+
+        // then
+        let report = report.expect("Expected valid report");
+        assert_eq!(report.target, 1000);
+        assert_eq!(report.k, (2, 4));
+        assert!(report.all);
+    }
+
+    #[test]
+    fn parse_with_params_rejects_k_above_number_of_entries() {
+        // given
+        let mut params = Params::new();
+        params.insert("k1", "18446744073709551615");
+
+        // when
+        let result = Day01::parse_with_params("1\n2\n3\n", &params);
+
+        // then
+        assert!(result.is_err());
+        assert_eq!(find_k_sum(&[1, 2, 3], usize::MAX, 6), None);
+    }
+
+    #[test]
+    fn answer_lists_all_combinations() {
+        // given
+        let report = Report {
+            entries: vec![1, 9, 5, 3, 7],
+            target: 10,
+            k: (2, 3),
+            all: true,
+            arithmetic: Arithmetic::Checked,
+        };
+
+        // when
+        let answer = Day01::part1(&report);
+
+        // then
+        assert_eq!(
+            answer.map(|a| a.to_string()),
+            Ok("1 * 9 = 9, 3 * 7 = 21".to_owned())
+        );
+    }
+
+    #[test]
+    fn multiply_reports_overflow() {
+        // given
+        let entries = [100_000, 100_000];
+
+        // when
+        let checked = multiply(&entries, Arithmetic::Checked);
+        let big = multiply(&entries, Arithmetic::Big);
+
+        // then
+        let error = checked.expect_err("Expected the product to overflow");
+        assert!(error.contains("product of the entries: 100000 * 100000"));
+        assert_eq!(big, Ok(Number::Big(BigInt::from(10_000_000_000))));
+    }
+}
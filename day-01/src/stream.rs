@@ -0,0 +1,143 @@
+use crate::multiply;
+use common::num::Arithmetic;
+use common::{for_each_file, input_name, parse_args, parse_int, ParseError, STDIN};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, stdin, BufRead, BufReader};
+use std::path::Path;
+
+/// A line of the expense report that is not a number
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BadLine {
+    /// counting from 1
+    pub number: usize,
+    pub error: ParseError,
+}
+
+/// Reads the entries one line at a time and returns the first two that sum up to `target`, as
+/// soon as the second one is read. Only the distinct entries before it are kept in memory. Lines
+/// that are not numbers are passed to `on_bad_line` and skipped, empty lines are ignored.
+pub fn find_pair_in_stream(
+    mut reader: impl BufRead,
+    target: i64,
+    mut on_bad_line: impl FnMut(BadLine),
+) -> io::Result<Option<[i32; 2]>> {
+    let mut seen: HashSet<i32> = HashSet::new();
+    let mut line = String::new();
+    let mut number = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        number += 1;
+        let text = line.trim_end_matches(['\n', '\r']);
+        if text.is_empty() {
+            continue;
+        }
+        let entry: i32 = match parse_int(text) {
+            Ok(entry) => entry,
+            Err(error) => {
+                on_bad_line(BadLine { number, error });
+                continue;
+            }
+        };
+        let partner = target - entry as i64;
+        if partner >= i32::MIN as i64
+            && partner <= i32::MAX as i64
+            && seen.contains(&(partner as i32))
+        {
+            return Ok(Some([partner as i32, entry]));
+        }
+        seen.insert(entry);
+    }
+}
+
+/// Runs part 1 on each file without reading it into memory first: `aoc run 1 <file>... --stream
+/// [--target <n>] [--arithmetic checked|big]`. Lines that are not numbers are reported on
+/// stderr.
+pub fn stream_files(args: &[String]) -> Result<(), String> {
+    let (filenames, params) = parse_args(args)?;
+    params
+        .check_known(&["target", common::num::ARITHMETIC])
+        .map_err(|e| e.to_string())?;
+    let target: i64 = params.get_int("target", 2020).map_err(|e| e.to_string())?;
+    let arithmetic = Arithmetic::from_params(&params).map_err(|e| e.to_string())?;
+    for_each_file(&filenames, |path| {
+        stream_file(path, target, arithmetic).map_err(|e| format!("Part 1 failed: {}", e))
+    })
+}
+
+fn stream_file(path: &Path, target: i64, arithmetic: Arithmetic) -> Result<(), String> {
+    let name = input_name(path);
+    let reader: Box<dyn BufRead> = if path == Path::new(STDIN) {
+        Box::new(stdin().lock())
+    } else {
+        let file =
+            File::open(path).map_err(|e| format!("Unable to read '{}': {}", path.display(), e))?;
+        Box::new(BufReader::new(file))
+    };
+
+    let mut bad_lines = 0;
+    let pair = find_pair_in_stream(reader, target, |bad_line| {
+        bad_lines += 1;
+        eprintln!(
+            "warning: {}:{}: {}",
+            name,
+            bad_line.number,
+            bad_line.error.kind()
+        );
+    })
+    .map_err(|e| format!("Unable to read '{}': {}", name, e))?;
+    if bad_lines > 0 {
+        eprintln!("warning: skipped {} lines that are not numbers", bad_lines);
+    }
+
+    let pair = pair.ok_or_else(|| format!("There are no 2 entries that sum up to {}", target))?;
+    println!("Part 1: {}", multiply(&pair, arithmetic)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn find_pair_in_stream_works_for_example() {
+        // given
+        let input = Cursor::new("1721\n979\n366\n299\n675\n1456\n");
+
+        // when
+        let result = find_pair_in_stream(input, 2020, |_| {});
+
+        // then
+        assert_eq!(result.ok(), Some(Some([1721, 299])));
+    }
+
+    #[test]
+    fn find_pair_in_stream_reports_bad_lines_and_stops_at_pair() {
+        // given
+        let input = Cursor::new("1000\nabc\n\n12x\n1020\nnever read\n");
+        let mut bad_lines: Vec<usize> = Vec::new();
+
+        // when
+        let result = find_pair_in_stream(input, 2020, |bad_line| bad_lines.push(bad_line.number));
+
+        // then
+        assert_eq!(result.ok(), Some(Some([1000, 1020])));
+        assert_eq!(bad_lines, vec![2, 4]);
+    }
+
+    #[test]
+    fn find_pair_in_stream_does_not_pair_entry_with_itself() {
+        // given
+        let input = Cursor::new("1010\n7\n");
+
+        // when
+        let result = find_pair_in_stream(input, 2020, |_| {});
+
+        // then
+        assert_eq!(result.ok(), Some(None));
+    }
+}
@@ -0,0 +1,366 @@
+//! An audit of a password database: every violation of a policy and statistics about them.
+
+use crate::policy::{Policy, Rule};
+use crate::{parse_line, read_policy, Password};
+use common::json::Json;
+use common::{for_each_file, input_name, parse_args, read_input, ParseError};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// A password that violates a rule of the policy
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Violation<'a> {
+    /// counting from 1
+    pub line: usize,
+    pub password: Password<'a>,
+    /// the name of the violated rule
+    pub rule: String,
+    pub reason: String,
+}
+
+/// How many passwords there are in a group, and how many of them violate the policy
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Count {
+    pub passwords: usize,
+    pub violations: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Audit<'a> {
+    pub passwords: usize,
+    /// in the order of the lines, and of the rules for each line
+    pub violations: Vec<Violation<'a>>,
+    /// for each rule of the policy, in the order of the rules
+    pub per_rule: Vec<(String, Count)>,
+    /// by the letter of the line
+    pub per_letter: BTreeMap<char, Count>,
+    /// by the length of the password in chars
+    pub per_length: BTreeMap<usize, Count>,
+}
+
+impl<'a> Audit<'a> {
+    /// Checks every line of the database against every rule of the policy.
+    pub fn new(input: &'a str, policy: &Policy) -> Result<Audit<'a>, ParseError> {
+        let mut audit = Audit {
+            passwords: 0,
+            violations: Vec::new(),
+            per_rule: policy
+                .rules()
+                .iter()
+                .map(|rule| (rule.name.clone(), Count::default()))
+                .collect(),
+            per_letter: BTreeMap::new(),
+            per_length: BTreeMap::new(),
+        };
+        for (index, line) in input.split('\n').enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let password = parse_line(line)?;
+            audit.passwords += 1;
+            let before = audit.violations.len();
+            for ((rule, count), Rule { policy, .. }) in
+                audit.per_rule.iter_mut().zip(policy.rules())
+            {
+                count.passwords += 1;
+                if let Some(reason) = policy.violation(&password) {
+                    count.violations += 1;
+                    audit.violations.push(Violation {
+                        line: index + 1,
+                        password: password.clone(),
+                        rule: rule.clone(),
+                        reason,
+                    });
+                }
+            }
+            let violated = (audit.violations.len() > before) as usize;
+            for count in [
+                audit.per_letter.entry(password.letter).or_default(),
+                audit
+                    .per_length
+                    .entry(password.pwd.chars().count())
+                    .or_default(),
+            ] {
+                count.passwords += 1;
+                count.violations += violated;
+            }
+        }
+        Ok(audit)
+    }
+
+    /// The passwords that violate at least one rule
+    pub fn violating_passwords(&self) -> usize {
+        self.per_letter.values().map(|count| count.violations).sum()
+    }
+
+    /// The statistics as rows of statistic, key and count
+    fn statistics(&self) -> Vec<(&'static str, String, Count)> {
+        let rules = self
+            .per_rule
+            .iter()
+            .map(|(rule, count)| ("rule", rule.clone(), *count));
+        let letters = self
+            .per_letter
+            .iter()
+            .map(|(letter, count)| ("letter", letter.to_string(), *count));
+        let lengths = self
+            .per_length
+            .iter()
+            .map(|(length, count)| ("length", length.to_string(), *count));
+        rules.chain(letters).chain(lengths).collect()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for violation in &self.violations {
+            let _ = writeln!(
+                text,
+                "line {}: {} fails {}: {}",
+                violation.line, violation.password.pwd, violation.rule, violation.reason
+            );
+        }
+        let _ = writeln!(
+            text,
+            "{} of {} passwords violate the policy",
+            self.violating_passwords(),
+            self.passwords
+        );
+        let statistics = self.statistics();
+        let width = statistics
+            .iter()
+            .map(|(_, key, _)| key.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("key".len());
+        let _ = writeln!(
+            text,
+            "{:<9} {:<width$} {:>9} {:>10}",
+            "statistic",
+            "key",
+            "passwords",
+            "violations",
+            width = width
+        );
+        for (statistic, key, count) in &statistics {
+            let _ = writeln!(
+                text,
+                "{:<9} {:<width$} {:>9} {:>10}",
+                statistic,
+                key,
+                count.passwords,
+                count.violations,
+                width = width
+            );
+        }
+        text
+    }
+
+    /// Two tables, separated by an empty line: the violations and the statistics
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("line,rule,min,max,letter,password,reason\n");
+        for violation in &self.violations {
+            let pw = &violation.password;
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                violation.line,
+                csv_field(&violation.rule),
+                pw.min,
+                pw.max,
+                csv_field(&pw.letter.to_string()),
+                csv_field(pw.pwd),
+                csv_field(&violation.reason)
+            );
+        }
+        csv.push_str("\nstatistic,key,passwords,violations\n");
+        for (statistic, key, count) in self.statistics() {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{}",
+                statistic,
+                csv_field(&key),
+                count.passwords,
+                count.violations
+            );
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Json {
+        let violations: Vec<Json> = self
+            .violations
+            .iter()
+            .map(|violation| {
+                let pw = &violation.password;
+                Json::object(vec![
+                    ("line", violation.line.into()),
+                    ("rule", violation.rule.as_str().into()),
+                    ("min", pw.min.into()),
+                    ("max", pw.max.into()),
+                    ("letter", pw.letter.to_string().into()),
+                    ("password", pw.pwd.into()),
+                    ("reason", violation.reason.as_str().into()),
+                ])
+            })
+            .collect();
+        let group = |key: &str, counts: Vec<(Json, Count)>| -> Json {
+            let rows: Vec<Json> = counts
+                .into_iter()
+                .map(|(value, count)| {
+                    Json::object(vec![
+                        (key, value),
+                        ("passwords", count.passwords.into()),
+                        ("violations", count.violations.into()),
+                    ])
+                })
+                .collect();
+            rows.into()
+        };
+        let per_rule = self
+            .per_rule
+            .iter()
+            .map(|(rule, count)| (rule.as_str().into(), *count))
+            .collect();
+        let per_letter = self
+            .per_letter
+            .iter()
+            .map(|(letter, count)| (letter.to_string().into(), *count))
+            .collect();
+        let per_length = self
+            .per_length
+            .iter()
+            .map(|(length, count)| ((*length).into(), *count))
+            .collect();
+        Json::object(vec![
+            ("passwords", self.passwords.into()),
+            ("violating_passwords", self.violating_passwords().into()),
+            ("violations", violations.into()),
+            ("per_rule", group("rule", per_rule)),
+            ("per_letter", group("letter", per_letter)),
+            ("per_length", group("length", per_length)),
+        ])
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Audits each file and prints the report: `aoc run 2 <file>... --audit [--policy <file>]
+/// [--format text|csv|json]`. Without a policy file, the passwords are audited against both
+/// policies of the puzzle.
+pub fn audit_files(args: &[String]) -> Result<(), String> {
+    let (filenames, params) = parse_args(args)?;
+    params
+        .check_known(&["policy", "format"])
+        .map_err(|e| e.to_string())?;
+    let policy = match params.get_str("policy") {
+        Some(path) => read_policy(path).map_err(|e| e.to_string())?,
+        None => Policy::puzzle(),
+    };
+    let render: fn(&Audit) -> String = match params.get_str("format") {
+        None | Some("text") => |audit| audit.to_text(),
+        Some("csv") => |audit| audit.to_csv(),
+        Some("json") => |audit| format!("{}\n", audit.to_json()),
+        Some(format) => return Err(format!("Unknown format '{}'", format)),
+    };
+    for_each_file(&filenames, |path| {
+        let input = read_input(path)?;
+        let audit = Audit::new(&input, &policy)
+            .map_err(|e| e.locate(&input).in_file(&input_name(path)).to_string())?;
+        print!("{}", render(&audit));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "1-3 a: abcde\n1-3 b: cdefg\n\n2-9 c: ccccccccc\n";
+
+    #[test]
+    fn audit_finds_violations_of_puzzle_policies() {
+        // when
+        let audit = Audit::new(EXAMPLE, &Policy::puzzle()).expect("Expected valid input");
+
+        // then
+        let violations: Vec<(usize, &str, &str)> = audit
+            .violations
+            .iter()
+            .map(|v| (v.line, v.rule.as_str(), v.reason.as_str()))
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                (2, "sled-rental", "char 'b' occurs 0 times, allowed 1-3"),
+                (2, "toboggan", "char 'b' is at none of the positions 1, 3"),
+                (
+                    4,
+                    "toboggan",
+                    "char 'c' is at positions 2, 9, allowed at exactly one of 2, 9"
+                ),
+            ]
+        );
+        assert_eq!(audit.passwords, 3);
+        assert_eq!(audit.violating_passwords(), 2);
+        let count = |passwords, violations| Count {
+            passwords,
+            violations,
+        };
+        assert_eq!(
+            audit.per_rule,
+            vec![
+                ("sled-rental".to_owned(), count(3, 1)),
+                ("toboggan".to_owned(), count(3, 2))
+            ]
+        );
+        assert_eq!(audit.per_letter.get(&'a'), Some(&count(1, 0)));
+        assert_eq!(audit.per_letter.get(&'c'), Some(&count(1, 1)));
+        assert_eq!(audit.per_length.get(&5), Some(&count(2, 1)));
+        assert_eq!(audit.per_length.get(&9), Some(&count(1, 1)));
+    }
+
+    #[test]
+    fn to_csv_quotes_fields() {
+        // given
+        let audit =
+            Audit::new("2-9 c: ccccccccc\n", &Policy::puzzle()).expect("Expected valid input");
+
+        // when
+        let csv = audit.to_csv();
+
+        // then
+        assert_eq!(
+            csv,
+            "line,rule,min,max,letter,password,reason\n\
+             1,toboggan,2,9,c,ccccccccc,\"char 'c' is at positions 2, 9, allowed at exactly one of 2, 9\"\n\
+             \n\
+             statistic,key,passwords,violations\n\
+             rule,sled-rental,1,0\n\
+             rule,toboggan,1,1\n\
+             letter,c,1,1\n\
+             length,9,1,1\n"
+        );
+    }
+
+    #[test]
+    fn to_json_lists_violations_and_statistics() {
+        // given
+        let audit =
+            Audit::new("1-3 b: cdefg\n", &Policy::sled_rental()).expect("Expected valid input");
+
+        // when
+        let json = audit.to_json().to_string();
+
+        // then
+        assert_eq!(
+            json,
+            r#"{"passwords":1,"violating_passwords":1,"violations":[{"line":1,"rule":"letter-count","min":1,"max":3,"letter":"b","password":"cdefg","reason":"char 'b' occurs 0 times, allowed 1-3"}],"per_rule":[{"rule":"letter-count","passwords":1,"violations":1}],"per_letter":[{"letter":"b","passwords":1,"violations":1}],"per_length":[{"length":5,"passwords":1,"violations":1}]}"#
+        );
+    }
+}
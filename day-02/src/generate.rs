@@ -0,0 +1,88 @@
+use crate::Day02;
+use common::generate::{Generated, Generator, Rng};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// `size` is the number of passwords. The letter of each policy is placed at chosen positions of
+/// the password, so it is known which passwords are valid under either policy.
+impl Generator for Day02 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut input = String::with_capacity(size * 30);
+        let mut valid_old = 0;
+        let mut valid_new = 0;
+        for _ in 0..size {
+            let min = rng.between(1, 10) as usize;
+            let max = rng.between(min as i64 + 1, 16) as usize;
+            let length = rng.between(max as i64, 20) as usize;
+            let letter = *rng.choose(LETTERS);
+
+            let mut positions: Vec<usize> = (0..length).collect();
+            rng.shuffle(&mut positions);
+            // mostly counts around the limits of the policy
+            let count = rng
+                .between(min as i64 - 1, max as i64 + 1)
+                .clamp(0, length as i64);
+            let mut password: Vec<u8> = (0..length)
+                .map(|_| loop {
+                    let other = *rng.choose(LETTERS);
+                    if other != letter {
+                        break other;
+                    }
+                })
+                .collect();
+            for position in &positions[..count as usize] {
+                password[*position] = letter;
+            }
+
+            if (min..=max).contains(&(count as usize)) {
+                valid_old += 1;
+            }
+            if (password[min - 1] == letter) != (password[max - 1] == letter) {
+                valid_new += 1;
+            }
+            input.push_str(&format!(
+                "{}-{} {}: {}\n",
+                min,
+                max,
+                letter as char,
+                String::from_utf8_lossy(&password)
+            ));
+        }
+
+        Generated {
+            input,
+            part1: Some(valid_old.to_string()),
+            part2: Some(valid_new.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day02::generate(&mut Rng::new(seed), 100);
+
+            // when
+            let mismatches = check_planted::<Day02>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day02>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
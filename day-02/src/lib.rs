@@ -0,0 +1,261 @@
+//! Day 2: Password Philosophy
+
+use common::{end_of, parse_int, Mode, Params, ParseError, Solution};
+use policy::{parse_policy, PasswordPolicy, Policy};
+use std::fs::read_to_string;
+
+pub mod audit;
+mod generate;
+pub mod policy;
+
+pub struct Day02;
+
+/// The password database, and the policy given with `--policy`, which replaces the policies of
+/// both parts
+pub struct Database<'a> {
+    pub passwords: Vec<Password<'a>>,
+    pub policy: Option<Policy>,
+}
+
+/// Part 1 and part 2 count the passwords that are valid under the sled rental and the toboggan
+/// policy. With `--policy`, both count the passwords that comply with the policy file instead.
+impl Solution for Day02 {
+    type Input<'a> = Database<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const PARAMS: &'static [&'static str] = &["policy"];
+    const FILE_PARAMS: &'static [&'static str] = &["policy"];
+
+    const MODES: &'static [Mode] = &[Mode {
+        flag: "audit",
+        takes_value: false,
+        run: audit::audit_files,
+    }];
+
+    fn parse(input: &str) -> Result<Database<'_>, ParseError> {
+        let passwords = input
+            .split('\n')
+            .filter(|l| !l.is_empty())
+            .map(parse_line)
+            .collect::<Result<_, _>>()?;
+        Ok(Database {
+            passwords,
+            policy: None,
+        })
+    }
+
+    fn parse_with_params<'a>(input: &'a str, params: &Params) -> Result<Database<'a>, ParseError> {
+        let mut database = Day02::parse(input)?;
+        if let Some(path) = params.get_str("policy") {
+            database.policy = Some(read_policy(path)?);
+        }
+        Ok(database)
+    }
+
+    fn part1(database: &Database) -> Result<usize, String> {
+        Ok(count_valid(database, Policy::sled_rental))
+    }
+
+    fn part2(database: &Database) -> Result<usize, String> {
+        Ok(count_valid(database, Policy::toboggan))
+    }
+}
+
+// under the policy given with `--policy`, or else under the policy of the part
+fn count_valid(database: &Database, puzzle_policy: fn() -> Policy) -> usize {
+    let count = |policy: &Policy| {
+        database
+            .passwords
+            .iter()
+            .filter(|pw| policy.is_valid(pw))
+            .count()
+    };
+    match &database.policy {
+        Some(policy) => count(policy),
+        None => count(&puzzle_policy()),
+    }
+}
+
+/// Reads a policy file, errors in the file are located in it.
+pub fn read_policy(path: &str) -> Result<Policy, ParseError> {
+    let text = read_to_string(path).map_err(|e| {
+        ParseError::unexpected_token(path, format!("a readable policy file ({})", e))
+    })?;
+    parse_policy(&text).map_err(|e| e.locate(&text).in_file(path))
+}
+
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A line of the password database: the policy and the password it applies to
+pub struct Password<'a> {
+    pub min: usize,
+    pub max: usize,
+    pub letter: char,
+    pub pwd: &'a str,
+}
+
+/// The sled rental policy: the letter must appear `min` to `max` times.
+pub fn check_pw(pw: &Password) -> bool {
+    let count = pw.pwd.chars().filter(|c| *c == pw.letter).count();
+    count >= pw.min && count <= pw.max
+}
+
+/// The toboggan policy: the letter must appear at exactly one of the (1-based) positions `min`
+/// and `max`. A position of 0 never holds the letter, and if `min` and `max` are the same position,
+/// the letter can not be at exactly one of them.
+pub fn check_pw_new(pw: &Password) -> bool {
+    // indices start with 1
+    let letter_at =
+        |position: usize| position > 0 && pw.pwd.chars().nth(position - 1) == Some(pw.letter);
+    pw.min != pw.max && letter_at(pw.min) != letter_at(pw.max)
+}
+
+/// Parses a line like `1-3 a: abcde`.
+pub fn parse_line(line: &str) -> Result<Password<'_>, ParseError> {
+    // I dont want to add external dependencies. Screw you, regular expressions!
+    let mut first_splitter = line.splitn(2, ' ');
+    let numbers = first_splitter
+        .next()
+        .ok_or_else(|| ParseError::missing_field(line, "min/max"))?;
+    let rest = first_splitter
+        .next()
+        .ok_or_else(|| ParseError::missing_field(end_of(line), "letter and password"))?;
+
+    let mut numbers_split = numbers.splitn(2, '-');
+    let min: usize = numbers_split
+        .next()
+        .ok_or_else(|| ParseError::missing_field(numbers, "min"))
+        .and_then(parse_int)?;
+    let max: usize = numbers_split
+        .next()
+        .ok_or_else(|| ParseError::missing_field(end_of(numbers), "'-' and max"))
+        .and_then(parse_int)?;
+
+    let mut rest_split = rest.splitn(2, ':');
+    let letter = rest_split
+        .next()
+        .ok_or_else(|| ParseError::missing_field(rest, "letter"))?
+        .chars()
+        .next()
+        .ok_or_else(|| ParseError::missing_field(rest, "letter before the colon"))?;
+    let pwd = rest_split
+        .next()
+        .ok_or_else(|| ParseError::missing_field(end_of(rest), "':' and password"))?
+        .trim();
+
+    Ok(Password {
+        min,
+        max,
+        letter,
+        pwd,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{remove_file, write};
+
+    const EXAMPLE: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+
+    #[test]
+    fn parts_count_valid_passwords_under_puzzle_policies() {
+        // given
+        let database = Day02::parse(EXAMPLE).expect("Expected valid input");
+
+        // then
+        assert_eq!(Day02::part1(&database), Ok(2));
+        assert_eq!(Day02::part2(&database), Ok(1));
+    }
+
+    #[test]
+    fn policy_file_replaces_puzzle_policies() {
+        // given
+        let path = temp_dir().join("day-02-policy-file-replaces-puzzle-policies.txt");
+        write(&path, "min-distinct-chars 5\nmax-run 3\n").expect("Expected to write policy");
+        let mut params = Params::new();
+        params.insert("policy", &path.display().to_string());
+
+        // when
+        let database = Day02::parse_with_params(EXAMPLE, &params);
+        remove_file(&path).expect("Expected to remove policy");
+
+        // then
+        let database = database.expect("Expected valid input and policy");
+        assert_eq!(Day02::part1(&database), Ok(2));
+        assert_eq!(Day02::part2(&database), Ok(2));
+    }
+
+    #[test]
+    fn parse_line_parses_valid_line() {
+        // given
+        let line = "4-11 g: abcdef";
+
+        // when
+        let pr = parse_line(line);
+
+        // then
+        let p = pr.expect("Expected successful parsing");
+        assert_eq!(
+            p,
+            Password {
+                min: 4,
+                max: 11,
+                letter: 'g',
+                pwd: "abcdef"
+            }
+        );
+    }
+
+    #[test]
+    fn check_pw_new_should_accept_valid_passwords() {
+        // given
+        let valid = Password {
+            min: 1,
+            max: 3,
+            letter: 'a',
+            pwd: "abcde",
+        };
+
+        // when
+        let result = check_pw_new(&valid);
+
+        // then
+        assert!(result);
+    }
+
+    #[test]
+    fn check_pw_new_should_reject_invalid_passwords_with_no_occurences() {
+        // given
+        let password = Password {
+            min: 1,
+            max: 3,
+            letter: 'b',
+            pwd: "cdefg",
+        };
+
+        // when
+        let result = check_pw_new(&password);
+
+        // then
+        assert!(!result);
+    }
+
+    #[test]
+    fn check_pw_new_should_reject_invalid_passwords_with_two_occurences() {
+        // given
+        let password = Password {
+            min: 2,
+            max: 9,
+            letter: 'c',
+            pwd: "ccccccccc",
+        };
+
+        // when
+        let result = check_pw_new(&password);
+
+        // then
+        assert!(!result);
+    }
+}
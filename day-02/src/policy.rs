@@ -0,0 +1,452 @@
+//! Password policies beyond the two of the puzzle, described in a policy file.
+//!
+//! A policy file has one rule per line, a password complies with the policy if it complies with
+//! every rule. `#` starts a comment. The rules are:
+//!
+//! ```text
+//! letter-count               # the letter of the line occurs min to max times (part 1)
+//! letter-positions           # the letter is at exactly one of the positions min and max (part 2)
+//! letter-positions 1,4,7     # the letter is at exactly one of the positions 1, 4 and 7
+//! min-distinct-chars 5       # at least 5 different chars
+//! max-run 2                  # no char more than 2 times in a row
+//! length 8-64                # 8 to 64 chars long
+//! ```
+
+use crate::Password;
+use common::{end_of, parse_int, ParseError};
+
+const RULES: &[&str] = &[
+    "letter-count",
+    "letter-positions",
+    "min-distinct-chars",
+    "max-run",
+    "length",
+];
+
+/// A rule for the passwords of the database
+pub trait PasswordPolicy {
+    /// Describes why the password violates the policy, or `None` if it complies.
+    fn violation(&self, pw: &Password) -> Option<String>;
+
+    fn is_valid(&self, pw: &Password) -> bool {
+        self.violation(pw).is_none()
+    }
+}
+
+/// The letter of the line must occur `min` to `max` times, the sled rental policy.
+pub struct LetterCount;
+
+impl PasswordPolicy for LetterCount {
+    fn violation(&self, pw: &Password) -> Option<String> {
+        let count = pw.pwd.chars().filter(|c| *c == pw.letter).count();
+        if count >= pw.min && count <= pw.max {
+            None
+        } else {
+            Some(format!(
+                "char '{}' occurs {} times, allowed {}-{}",
+                pw.letter, count, pw.min, pw.max
+            ))
+        }
+    }
+}
+
+/// The letter of the line must be at exactly one of the (1-based) positions. Without positions,
+/// these are `min` and `max` of the line, the toboggan policy.
+pub struct LetterPositions {
+    pub positions: Option<Vec<usize>>,
+}
+
+impl PasswordPolicy for LetterPositions {
+    fn violation(&self, pw: &Password) -> Option<String> {
+        let mut positions = match &self.positions {
+            Some(positions) => positions.clone(),
+            // `min` and `max` count as two positions even if they are the same
+            None => vec![pw.min, pw.max],
+        };
+        positions.sort_unstable();
+        if self.positions.is_some() {
+            positions.dedup();
+        }
+        let chars: Vec<char> = pw.pwd.chars().collect();
+        let found: Vec<usize> = positions
+            .iter()
+            .copied()
+            .filter(|p| *p > 0 && chars.get(p - 1) == Some(&pw.letter))
+            .collect();
+        match found.len() {
+            1 => None,
+            0 => Some(format!(
+                "char '{}' is at none of the positions {}",
+                pw.letter,
+                join(&positions)
+            )),
+            _ => Some(format!(
+                "char '{}' is at positions {}, allowed at exactly one of {}",
+                pw.letter,
+                join(&found),
+                join(&positions)
+            )),
+        }
+    }
+}
+
+/// The password must consist of at least this many different chars.
+pub struct MinDistinctChars(pub usize);
+
+impl PasswordPolicy for MinDistinctChars {
+    fn violation(&self, pw: &Password) -> Option<String> {
+        let mut chars: Vec<char> = pw.pwd.chars().collect();
+        chars.sort_unstable();
+        chars.dedup();
+        if chars.len() >= self.0 {
+            None
+        } else {
+            Some(format!(
+                "{} distinct chars, required at least {}",
+                chars.len(),
+                self.0
+            ))
+        }
+    }
+}
+
+/// No char may repeat more than this many times in a row.
+pub struct MaxRun(pub usize);
+
+impl PasswordPolicy for MaxRun {
+    fn violation(&self, pw: &Password) -> Option<String> {
+        let mut longest: Option<(char, usize)> = None;
+        let mut run: Option<(char, usize)> = None;
+        for c in pw.pwd.chars() {
+            run = match run {
+                Some((previous, length)) if previous == c => Some((c, length + 1)),
+                _ => Some((c, 1)),
+            };
+            if run.map(|(_, length)| length) > longest.map(|(_, length)| length) {
+                longest = run;
+            }
+        }
+        match longest {
+            Some((c, length)) if length > self.0 => Some(format!(
+                "char '{}' repeats {} times in a row, allowed {}",
+                c, length, self.0
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// The password must have `min` to `max` chars.
+pub struct Length {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl PasswordPolicy for Length {
+    fn violation(&self, pw: &Password) -> Option<String> {
+        let length = pw.pwd.chars().count();
+        if length >= self.min && length <= self.max {
+            None
+        } else {
+            Some(format!(
+                "{} chars long, allowed {}-{}",
+                length, self.min, self.max
+            ))
+        }
+    }
+}
+
+/// A rule of a policy file, together with its text to name it in reports
+pub struct Rule {
+    pub name: String,
+    pub policy: Box<dyn PasswordPolicy>,
+}
+
+/// A set of rules that a password must all comply with
+pub struct Policy {
+    rules: Vec<Rule>,
+}
+
+impl Policy {
+    /// The policy of part 1
+    pub fn sled_rental() -> Policy {
+        Policy::single("letter-count", LetterCount)
+    }
+
+    /// The policy of part 2
+    pub fn toboggan() -> Policy {
+        Policy::single("letter-positions", LetterPositions { positions: None })
+    }
+
+    /// Both policies of the puzzle, named `sled-rental` and `toboggan`, to audit a database
+    /// against them
+    pub fn puzzle() -> Policy {
+        let mut rules = Policy::single("sled-rental", LetterCount).rules;
+        rules.append(&mut Policy::single("toboggan", LetterPositions { positions: None }).rules);
+        Policy { rules }
+    }
+
+    fn single(name: &str, policy: impl PasswordPolicy + 'static) -> Policy {
+        Policy {
+            rules: vec![Rule {
+                name: name.to_owned(),
+                policy: Box::new(policy),
+            }],
+        }
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// The rules the password violates and why
+    pub fn violations<'p>(
+        &'p self,
+        pw: &'p Password,
+    ) -> impl Iterator<Item = (&'p Rule, String)> + 'p {
+        self.rules
+            .iter()
+            .filter_map(move |rule| rule.policy.violation(pw).map(|reason| (rule, reason)))
+    }
+}
+
+impl PasswordPolicy for Policy {
+    fn violation(&self, pw: &Password) -> Option<String> {
+        let reasons: Vec<String> = self
+            .violations(pw)
+            .map(|(rule, reason)| format!("{}: {}", rule.name, reason))
+            .collect();
+        if reasons.is_empty() {
+            None
+        } else {
+            Some(reasons.join("; "))
+        }
+    }
+}
+
+/// Parses the rules of a policy file, see the module documentation.
+pub fn parse_policy(text: &str) -> Result<Policy, ParseError> {
+    let mut rules: Vec<Rule> = Vec::new();
+    for line in text.split('\n') {
+        let rule = match line.find('#') {
+            Some(comment) => &line[..comment],
+            None => line,
+        }
+        .trim();
+        let mut tokens = rule.split_whitespace();
+        let name = match tokens.next() {
+            Some(name) => name,
+            None => continue,
+        };
+        let policy: Box<dyn PasswordPolicy> = match name {
+            "letter-count" => Box::new(LetterCount),
+            "letter-positions" => Box::new(LetterPositions {
+                positions: tokens.next().map(parse_positions).transpose()?,
+            }),
+            "min-distinct-chars" => Box::new(MinDistinctChars(parse_int(argument(
+                tokens.next(),
+                rule,
+                "number of chars",
+            )?)?)),
+            "max-run" => Box::new(MaxRun(parse_int(argument(
+                tokens.next(),
+                rule,
+                "length of the longest run",
+            )?)?)),
+            "length" => {
+                let (min, max) = parse_range(argument(tokens.next(), rule, "range like 8-64")?)?;
+                Box::new(Length { min, max })
+            }
+            _ => {
+                return Err(ParseError::unexpected_token(
+                    name,
+                    format!("one of the rules {}", RULES.join(", ")),
+                ))
+            }
+        };
+        if let Some(extra) = tokens.next() {
+            return Err(ParseError::unexpected_token(extra, "the end of the rule"));
+        }
+        rules.push(Rule {
+            name: rule.to_owned(),
+            policy,
+        });
+    }
+    if rules.is_empty() {
+        return Err(ParseError::bad_layout(text, "the policy has no rules"));
+    }
+    Ok(Policy { rules })
+}
+
+fn argument<'a>(token: Option<&'a str>, rule: &'a str, field: &str) -> Result<&'a str, ParseError> {
+    token.ok_or_else(|| ParseError::missing_field(end_of(rule), field))
+}
+
+fn parse_positions(positions: &str) -> Result<Vec<usize>, ParseError> {
+    positions
+        .split(',')
+        .map(|position| match parse_int(position)? {
+            0 => Err(ParseError::unexpected_token(
+                position,
+                "a position, starting at 1",
+            )),
+            position => Ok(position),
+        })
+        .collect()
+}
+
+fn parse_range(range: &str) -> Result<(usize, usize), ParseError> {
+    let (min, max) = range
+        .split_once('-')
+        .ok_or_else(|| ParseError::missing_field(end_of(range), "'-' and max"))?;
+    let (min, max): (usize, usize) = (parse_int(min)?, parse_int(max)?);
+    if min > max {
+        return Err(ParseError::unexpected_token(
+            range,
+            "a range with min not above max",
+        ));
+    }
+    Ok((min, max))
+}
+
+fn join(positions: &[usize]) -> String {
+    positions
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{check_pw, check_pw_new, Day02};
+    use common::generate::{Generator, Rng};
+    use common::Solution;
+
+    const POLICY: &str = "# corporate rules\nlength 6-20\nmin-distinct-chars 4 \nmax-run 2 # no aaa\n\nletter-positions 1,3\n";
+
+    fn password(pwd: &str) -> Password<'_> {
+        Password {
+            min: 1,
+            max: 3,
+            letter: 'a',
+            pwd,
+        }
+    }
+
+    #[test]
+    fn parse_policy_reads_all_rules() {
+        // when
+        let policy = parse_policy(POLICY).expect("Expected valid policy");
+
+        // then
+        let names: Vec<&str> = policy.rules().iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "length 6-20",
+                "min-distinct-chars 4",
+                "max-run 2",
+                "letter-positions 1,3"
+            ]
+        );
+    }
+
+    #[test]
+    fn policy_explains_violations() {
+        // given
+        let policy = parse_policy(POLICY).expect("Expected valid policy");
+
+        // when
+        let valid = policy.violation(&password("abcdefg"));
+        let invalid: Vec<String> = policy
+            .violations(&password("abaaab"))
+            .map(|(_, reason)| reason)
+            .collect();
+
+        // then
+        assert_eq!(valid, None);
+        assert_eq!(
+            invalid,
+            vec![
+                "2 distinct chars, required at least 4",
+                "char 'a' repeats 3 times in a row, allowed 2",
+                "char 'a' is at positions 1, 3, allowed at exactly one of 1, 3"
+            ]
+        );
+    }
+
+    #[test]
+    fn builtin_policies_match_puzzle_policies() {
+        // given
+        let input = Day02::generate(&mut Rng::new(2), 500).input;
+        let passwords = Day02::parse(&input).expect("Expected valid input");
+
+        // then
+        for pw in &passwords.passwords {
+            assert_eq!(Policy::sled_rental().is_valid(pw), check_pw(pw));
+            assert_eq!(Policy::toboggan().is_valid(pw), check_pw_new(pw));
+        }
+    }
+
+    #[test]
+    fn toboggan_policy_handles_max_not_above_min() {
+        // given
+        let lines = [
+            "1-1 a: a",
+            "1-1 a: b",
+            "3-1 a: abc",
+            "3-1 a: cba",
+            "3-1 a: aba",
+        ];
+
+        // when
+        let results: Vec<(bool, bool)> = lines
+            .iter()
+            .map(|line| {
+                let pw = crate::parse_line(line).expect("Expected valid line");
+                (Policy::toboggan().is_valid(&pw), check_pw_new(&pw))
+            })
+            .collect();
+
+        // then
+        assert_eq!(
+            results,
+            vec![
+                (false, false),
+                (false, false),
+                (true, true),
+                (true, true),
+                (false, false)
+            ]
+        );
+        let database = Day02::parse("1-1 a: a\n3-1 a: abc\n").expect("Expected valid input");
+        assert_eq!(Day02::part2(&database), Ok(1));
+    }
+
+    #[test]
+    fn parse_policy_rejects_bad_rules() {
+        // given
+        let unknown = "length 1-5\nmax-runs 2\n";
+        let zero = "letter-positions 0,2\n";
+        let extra = "max-run 2 3\n";
+
+        // when
+        let unknown_error = parse_policy(unknown)
+            .map(|_| ())
+            .map_err(|e| e.locate(unknown));
+        let zero_error = parse_policy(zero).map(|_| ());
+        let extra_error = parse_policy(extra).map(|_| ());
+
+        // then
+        let location = unknown_error
+            .expect_err("Expected unknown rule to fail")
+            .location()
+            .map(|l| (l.line, l.column));
+        assert_eq!(location, Some((2, 1)));
+        assert!(zero_error.is_err());
+        assert!(extra_error.is_err());
+        assert!(parse_policy("# nothing\n").is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::route::{parse_moves, plan_route, Move};
+use crate::{find_slope, parse_input, parse_slope, render_cells, render_path, Goal};
+use common::grid::Grid;
+use common::{for_each_file, input_name, parse_args, parse_int, read_input, Params, ParseError};
+use std::ops::RangeInclusive;
+
+/// Searches or draws slopes and routes on each map instead of solving the puzzle, one of:
+///
+/// * `aoc run 3 <file>... --search fewest|most [--right <min>-<max>] [--down <min>-<max>]` prints
+///   the slope with the fewest or most trees, trying all slopes in the ranges (default: 0-10
+///   right and 1-5 down)
+/// * `aoc run 3 <file>... --render <slope>` draws the map with the path of the slope
+/// * `aoc run 3 <file>... --route <moves>` plans the route to the bottom with the fewest trees and
+///   draws it, see `route::parse_moves` for the moves
+pub fn explore_files(args: &[String]) -> Result<(), String> {
+    let (filenames, params) = parse_args(args)?;
+    params
+        .check_known(&["search", "right", "down", "render", "route"])
+        .map_err(|e| e.to_string())?;
+    let modes: Vec<String> = ["search", "render", "route"]
+        .iter()
+        .filter(|mode| params.get_str(mode).is_some())
+        .map(|mode| format!("--{}", mode))
+        .collect();
+    if modes.len() > 1 {
+        return Err(format!(
+            "Only one of --search, --render and --route can be given, not {}",
+            modes.join(" and ")
+        ));
+    }
+    let explore = explorer(&params).map_err(|e| e.to_string())?;
+    for_each_file(&filenames, |path| {
+        let input = read_input(path)?;
+        let map = parse_input(&input)
+            .map_err(|e| e.locate(&input).in_file(&input_name(path)).to_string())?;
+        print!("{}", explore(&map)?);
+        Ok(())
+    })
+}
+
+type Explorer = Box<dyn Fn(&Grid<bool>) -> Result<String, String>>;
+
+fn explorer(params: &Params) -> Result<Explorer, ParseError> {
+    if let Some(slope) = params.get_str("render") {
+        let slope = parse_slope(slope)?;
+        return Ok(Box::new(move |map| Ok(render_path(map, slope))));
+    }
+    if let Some(moves) = params.get_str("route") {
+        let moves = moves.to_owned();
+        // the moves depend on the width of the map
+        return Ok(Box::new(move |map| {
+            let moves = parse_moves(&moves, map.width()).map_err(|e| e.to_string())?;
+            Ok(route_text(map, &moves))
+        }));
+    }
+    let goal = match params.get_str("search") {
+        Some("fewest") => Goal::Fewest,
+        Some("most") => Goal::Most,
+        Some(goal) => return Err(ParseError::unexpected_token(goal, "fewest or most")),
+        None => {
+            return Err(ParseError::missing_field(
+                "",
+                "--search, --render or --route",
+            ))
+        }
+    };
+    let rights = parse_range(params.get_str("right").unwrap_or("0-10"))?;
+    let downs = parse_range(params.get_str("down").unwrap_or("1-5"))?;
+    Ok(Box::new(move |map| {
+        Ok(match find_slope(map, rights.clone(), downs.clone(), goal) {
+            Some((slope, trees)) => format!(
+                "Slope {} hits {} trees on {} cells\n",
+                slope,
+                trees,
+                slope.path(map).count()
+            ),
+            None => "There is no slope in the ranges\n".to_owned(),
+        })
+    }))
+}
+
+fn route_text(map: &Grid<bool>, moves: &[Move]) -> String {
+    match plan_route(map, moves) {
+        Some(route) => format!(
+            "Route with {} trees in {} moves: {}\n{}",
+            route.trees,
+            route.cells.len() - 1,
+            route
+                .cells
+                .iter()
+                .map(|(col, row)| format!("{},{}", col, row))
+                .collect::<Vec<String>>()
+                .join(" "),
+            render_cells(map, &route.cells)
+        ),
+        None => "There is no route to the bottom with these moves\n".to_owned(),
+    }
+}
+
+fn parse_range(range: &str) -> Result<RangeInclusive<usize>, ParseError> {
+    let (min, max) = range
+        .split_once('-')
+        .ok_or_else(|| ParseError::unexpected_token(range, "a range like 1-5"))?;
+    Ok(parse_int(min)?..=parse_int(max)?)
+}
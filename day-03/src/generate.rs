@@ -0,0 +1,71 @@
+use crate::Day03;
+use common::generate::{Generated, Generator, Rng};
+
+const WIDTH: usize = 31;
+
+/// `size` is the number of rows of the map (at least 1), which is 31 columns wide like the real
+/// puzzle input.
+impl Generator for Day03 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let rows: Vec<Vec<bool>> = (0..size.max(1))
+            .map(|_| (0..WIDTH).map(|_| rng.chance(0.25)).collect())
+            .collect();
+
+        let trees = |right: usize, down: usize| -> u64 {
+            (0..rows.len())
+                .step_by(down)
+                .enumerate()
+                .filter(|(step, row)| rows[*row][(step * right) % WIDTH])
+                .count() as u64
+        };
+        let product = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+            .iter()
+            .try_fold(1u64, |product, (right, down)| {
+                product.checked_mul(trees(*right, *down))
+            });
+
+        Generated {
+            input: rows
+                .iter()
+                .map(|row| {
+                    let mut line: String = row.iter().map(|t| if *t { '#' } else { '.' }).collect();
+                    line.push('\n');
+                    line
+                })
+                .collect(),
+            part1: Some(trees(3, 1).to_string()),
+            // the product does not fit into the answer for huge maps
+            part2: product.map(|p| p.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day03::generate(&mut Rng::new(seed), 100);
+
+            // when
+            let mismatches = check_planted::<Day03>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day03>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
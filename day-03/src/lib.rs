@@ -0,0 +1,399 @@
+//! Day 3: Toboggan Trajectory
+
+use common::grid::Grid;
+use common::{end_of, num, parse_int, Mode, Params, ParseError, Solution};
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+pub mod explore;
+mod generate;
+pub mod route;
+
+pub struct Day03;
+
+/// The slopes of part 2 of the puzzle
+pub const PART2_SLOPES: [Slope; 5] = [
+    Slope { right: 1, down: 1 },
+    Slope { right: 3, down: 1 },
+    Slope { right: 5, down: 1 },
+    Slope { right: 7, down: 1 },
+    Slope { right: 1, down: 2 },
+];
+
+/// The area map, `true` for a tree, and the slopes to go down on. The map repeats to the right
+/// indefinitely.
+pub struct Trip {
+    pub map: Grid<bool>,
+    /// the slope of part 1, `--slope` (default: 3/1)
+    pub slope: Slope,
+    /// the slopes of part 2, `--slopes` (default: 1/1,3/1,5/1,7/1,1/2)
+    pub slopes: Vec<Slope>,
+}
+
+impl Solution for Day03 {
+    type Input<'a> = Trip;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    const PARAMS: &'static [&'static str] = &["slope", "slopes"];
+
+    const MODES: &'static [Mode] = &[
+        Mode {
+            flag: "search",
+            takes_value: true,
+            run: explore::explore_files,
+        },
+        Mode {
+            flag: "render",
+            takes_value: true,
+            run: explore::explore_files,
+        },
+        Mode {
+            flag: "route",
+            takes_value: true,
+            run: explore::explore_files,
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Trip, ParseError> {
+        Day03::parse_with_params(input, &Params::new())
+    }
+
+    fn parse_with_params(input: &str, params: &Params) -> Result<Trip, ParseError> {
+        Ok(Trip {
+            map: parse_input(input)?,
+            slope: params
+                .get_str("slope")
+                .map(parse_slope)
+                .transpose()?
+                .unwrap_or(Slope { right: 3, down: 1 }),
+            slopes: params
+                .get_str("slopes")
+                .map(parse_slopes)
+                .transpose()?
+                .unwrap_or_else(|| PART2_SLOPES.to_vec()),
+        })
+    }
+
+    fn part1(trip: &Trip) -> Result<usize, String> {
+        Ok(count_trees(&trip.map, trip.slope))
+    }
+
+    fn part2(trip: &Trip) -> Result<u64, String> {
+        multiply_trees(&trip.map, &trip.slopes)
+    }
+}
+
+/// How far the toboggan goes right and down with each step. It only passes the cells it lands on,
+/// e.g. every other row for 1/2. Slopes are not reduced: 2/2 lands on every other cell of 1/1.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Slope {
+    pub right: usize,
+    /// at least 1
+    pub down: usize,
+}
+
+impl Slope {
+    /// `None` if `down` is 0
+    pub fn new(right: usize, down: usize) -> Option<Slope> {
+        if down == 0 {
+            return None;
+        }
+        Some(Slope { right, down })
+    }
+
+    /// The cells the toboggan passes from the top left to the bottom of the map, as column and
+    /// row. Columns go beyond the width of the map, which repeats to the right.
+    pub fn path(self, map: &Grid<bool>) -> impl Iterator<Item = (usize, usize)> {
+        (0..map.height())
+            .step_by(self.down)
+            .enumerate()
+            .map(move |(step, row)| (step * self.right, row))
+    }
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.right, self.down)
+    }
+}
+
+/// Parses a slope like `3/2` (3 right for every 2 down) or `3` (3 right for every row).
+pub fn parse_slope(slope: &str) -> Result<Slope, ParseError> {
+    let (right, down) = match slope.split_once('/') {
+        Some((right, down)) => (parse_int(right)?, parse_int(down)?),
+        None => (parse_int(slope)?, 1),
+    };
+    Slope::new(right, down)
+        .ok_or_else(|| ParseError::unexpected_token(slope, "a slope that goes down"))
+}
+
+/// Parses a comma separated list of slopes, like `1/1,3/1,1/2`.
+pub fn parse_slopes(slopes: &str) -> Result<Vec<Slope>, ParseError> {
+    if slopes.is_empty() {
+        return Err(ParseError::missing_field(end_of(slopes), "a slope"));
+    }
+    slopes.split(',').map(parse_slope).collect()
+}
+
+/// Counts the trees hit when going down `row_step` rows and right `col_step` columns at a time,
+/// starting at the top left. The map repeats to the right.
+pub fn count_trees_on_path(map: &Grid<bool>, row_step: usize, col_step: usize) -> usize {
+    let mut current_row = 0;
+    let mut current_col = 0;
+    let mut tree_count = 0;
+
+    while current_row < map.height() {
+        tree_count += if *map.get_wrapping(current_col as isize, current_row as isize) {
+            1
+        } else {
+            0
+        };
+        current_row += row_step;
+        current_col += col_step;
+    }
+
+    tree_count
+}
+
+/// Counts the trees hit on the slope, starting at the top left.
+pub fn count_trees(map: &Grid<bool>, slope: Slope) -> usize {
+    count_trees_on_path(map, slope.down, slope.right)
+}
+
+/// The product of the trees hit on all slopes
+pub fn multiply_trees(map: &Grid<bool>, slopes: &[Slope]) -> Result<u64, String> {
+    slopes.iter().try_fold(1, |product, slope| {
+        num::mul(
+            &product,
+            &(count_trees(map, *slope) as u64),
+            "product of trees",
+        )
+    })
+}
+
+/// The product of the trees hit on all slopes of part 2. Fails if it does not fit into an `u64`.
+pub fn solve_part_2(map: &Grid<bool>) -> Result<u64, String> {
+    multiply_trees(map, &PART2_SLOPES)
+}
+
+/// Whether `find_slope` looks for the fewest or the most trees
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Goal {
+    Fewest,
+    Most,
+}
+
+/// Tries all slopes with `right` and `down` in the ranges and returns the one that hits the
+/// fewest or most trees, with the number of trees. Of equally good slopes, the one with the
+/// smallest `down` and then `right` wins. `None` if there is no slope in the ranges.
+pub fn find_slope(
+    map: &Grid<bool>,
+    rights: RangeInclusive<usize>,
+    downs: RangeInclusive<usize>,
+    goal: Goal,
+) -> Option<(Slope, usize)> {
+    let mut best: Option<(Slope, usize)> = None;
+    for down in downs.filter(|down| *down > 0) {
+        for right in rights.clone() {
+            let slope = Slope { right, down };
+            let trees = count_trees(map, slope);
+            let better = match (best, goal) {
+                (None, _) => true,
+                (Some((_, best)), Goal::Fewest) => trees < best,
+                (Some((_, best)), Goal::Most) => trees > best,
+            };
+            if better {
+                best = Some((slope, trees));
+            }
+        }
+    }
+    best
+}
+
+/// Draws the map with the path of the slope, like the puzzle description: `O` for open ground
+/// and `X` for a tree on the path. The map is repeated to the right as often as the path needs.
+pub fn render_path(map: &Grid<bool>, slope: Slope) -> String {
+    let path: Vec<(isize, usize)> = slope
+        .path(map)
+        .map(|(col, row)| (col as isize, row))
+        .collect();
+    render_cells(map, &path)
+}
+
+/// Draws the map with `O` and `X` on the given cells (column and row), repeating the map to the
+/// left and right as often as the cells need.
+pub fn render_cells(map: &Grid<bool>, cells: &[(isize, usize)]) -> String {
+    let width = map.width().max(1) as isize;
+    let on_path: HashSet<(isize, usize)> = cells.iter().copied().collect();
+    let first = cells.iter().map(|(col, _)| col.div_euclid(width)).min();
+    let last = cells.iter().map(|(col, _)| col.div_euclid(width)).max();
+    let columns = first.unwrap_or(0) * width..(last.unwrap_or(0) + 1) * width;
+    let mut rendered = String::with_capacity((columns.len() + 1) * map.height());
+    for row in 0..map.height() {
+        for col in columns.clone() {
+            let tree = *map.get_wrapping(col, row as isize);
+            rendered.push(match (on_path.contains(&(col, row)), tree) {
+                (true, true) => 'X',
+                (true, false) => 'O',
+                (false, true) => '#',
+                (false, false) => '.',
+            });
+        }
+        rendered.push('\n');
+    }
+    rendered
+}
+
+/// Parses the map, `#` is a tree and `.` open ground.
+pub fn parse_input(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::parse(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r"..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    #[test]
+    fn test_parse() {
+        // given
+        let input = "..#.\n###.\n...#";
+
+        // when
+        let result = parse_input(input);
+
+        // then
+        assert_eq!(
+            result,
+            Ok(Grid::from_vec(
+                4,
+                vec![
+                    false, false, true, false, //
+                    true, true, true, false, //
+                    false, false, false, true,
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solution_2() {
+        // given
+        let map = parse_input(EXAMPLE).expect("Expected valid example input");
+
+        // when
+        let result = solve_part_2(&map);
+
+        // then
+        assert_eq!(result, Ok(336));
+    }
+
+    #[test]
+    fn parse_slopes_keeps_steps() {
+        // when
+        let slopes = parse_slopes("3,4/2,1/2,0/7");
+
+        // then
+        assert_eq!(
+            slopes,
+            Ok(vec![
+                Slope { right: 3, down: 1 },
+                Slope { right: 4, down: 2 },
+                Slope { right: 1, down: 2 },
+                Slope { right: 0, down: 7 },
+            ])
+        );
+        assert!(parse_slope("1/0").is_err());
+        assert!(parse_slopes("").is_err());
+    }
+
+    #[test]
+    fn slopes_are_taken_from_params() {
+        // given
+        let mut params = Params::new();
+        params.insert("slope", "6/2");
+        params.insert("slopes", "3,7");
+
+        // when
+        let trip = Day03::parse_with_params(EXAMPLE, &params).expect("Expected valid input");
+
+        // then
+        assert_eq!(Day03::part1(&trip), Ok(4));
+        assert_eq!(Day03::part2(&trip), Ok(7 * 4));
+    }
+
+    #[test]
+    fn unreduced_slope_lands_on_fewer_cells() {
+        // given
+        let map = parse_input(EXAMPLE).expect("Expected valid example input");
+
+        // when
+        let reduced = Slope::new(3, 2).expect("Expected a slope");
+        let unreduced = Slope::new(6, 4).expect("Expected a slope");
+
+        // then
+        assert_eq!(unreduced.path(&map).count(), 3);
+        assert_eq!(reduced.path(&map).count(), 6);
+        assert_eq!(count_trees(&map, unreduced), 1);
+        assert_eq!(count_trees(&map, reduced), 2);
+    }
+
+    #[test]
+    fn rational_slope_hits_lattice_points_only() {
+        // given
+        let map = parse_input(EXAMPLE).expect("Expected valid example input");
+
+        // when
+        let path: Vec<(usize, usize)> = Slope::new(3, 2).map(|s| s.path(&map).collect()).unwrap();
+
+        // then
+        assert_eq!(
+            path,
+            vec![(0, 0), (3, 2), (6, 4), (9, 6), (12, 8), (15, 10)]
+        );
+    }
+
+    #[test]
+    fn find_slope_finds_fewest_and_most_trees() {
+        // given
+        let map = parse_input(EXAMPLE).expect("Expected valid example input");
+
+        // when
+        let fewest = find_slope(&map, 0..=7, 1..=1, Goal::Fewest);
+        let most = find_slope(&map, 0..=7, 1..=2, Goal::Most);
+
+        // then
+        assert_eq!(fewest, Some((Slope { right: 2, down: 1 }, 1)));
+        assert_eq!(most, Some((Slope { right: 3, down: 1 }, 7)));
+    }
+
+    #[test]
+    fn render_path_marks_path_like_puzzle() {
+        // given
+        let map = parse_input(EXAMPLE).expect("Expected valid example input");
+
+        // when
+        let rendered = render_path(&map, Slope { right: 3, down: 1 });
+
+        // then
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], "O.##.........##.........##.......");
+        assert_eq!(lines[1], "#..O#...#..#...#...#..#...#...#..");
+        assert_eq!(lines[10], ".#..#...#.#.#..#...#.#.#..#...X.#");
+        assert_eq!(rendered.matches('X').count(), 7);
+        assert_eq!(rendered.matches('O').count(), 4);
+    }
+}
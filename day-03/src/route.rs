@@ -0,0 +1,232 @@
+//! Routes from the top to the bottom of the map that hit as few trees as possible, for a toboggan
+//! that is not bound to a single slope.
+
+use common::grid::Grid;
+use common::{end_of, parse_int, ParseError};
+use std::collections::VecDeque;
+
+/// A step of the toboggan. Negative `right` goes left, negative `down` goes up.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Move {
+    pub right: isize,
+    pub down: isize,
+}
+
+/// The cells of a route as column and row, starting at the top left. Columns are not wrapped,
+/// they go beyond the map on either side, which repeats indefinitely.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Route {
+    pub cells: Vec<(isize, usize)>,
+    pub trees: usize,
+}
+
+/// Down one row, and up to `k` columns to the left or right. As the map repeats, shifts of more
+/// than `width - 1` columns reach no other columns of the map than the shorter ones, so `k` is
+/// capped there.
+pub fn shifts(k: usize, width: usize) -> Vec<Move> {
+    let k = k.min(width.saturating_sub(1)) as isize;
+    (-k..=k).map(|right| Move { right, down: 1 }).collect()
+}
+
+/// Parses either a number `k` for `shifts(k, width)` or a comma separated list of moves like
+/// `-1/1,0/1,1/1`.
+pub fn parse_moves(moves: &str, width: usize) -> Result<Vec<Move>, ParseError> {
+    if !moves.contains('/') {
+        return Ok(shifts(parse_int(moves)?, width));
+    }
+    moves
+        .split(',')
+        .map(|m| {
+            let (right, down) = m
+                .split_once('/')
+                .ok_or_else(|| ParseError::missing_field(end_of(m), "'/' and down"))?;
+            match (parse_int(right)?, parse_int(down)?) {
+                (0, 0) => Err(ParseError::unexpected_token(
+                    m,
+                    "a move that goes somewhere",
+                )),
+                (right, down) => Ok(Move { right, down }),
+            }
+        })
+        .collect()
+}
+
+/// Finds a route from the top left cell to the bottom that hits the fewest trees, taking only the
+/// given moves. The route ends on a cell from which a move leaves the map at the bottom. `None`
+/// if the bottom can not be reached.
+pub fn plan_route(map: &Grid<bool>, moves: &[Move]) -> Option<Route> {
+    let (width, height) = (map.width() as isize, map.height() as isize);
+    if width == 0 || height == 0 {
+        return None;
+    }
+    // 0-1 BFS over the cells of a single copy of the map, entering a tree costs 1
+    let index = |col: isize, row: isize| (row * width + col.rem_euclid(width)) as usize;
+    let tree = |col: isize, row: isize| *map.get_wrapping(col, row) as usize;
+    let mut trees: Vec<usize> = vec![usize::MAX; (width * height) as usize];
+    // the index of the move that led to each cell
+    let mut came_by: Vec<Option<usize>> = vec![None; trees.len()];
+    let mut queue: VecDeque<(isize, isize)> = VecDeque::new();
+    trees[0] = tree(0, 0);
+    queue.push_back((0, 0));
+    let mut end: Option<(isize, isize)> = None;
+
+    while let Some((col, row)) = queue.pop_front() {
+        // cells come out of the queue ordered by their trees, the first cell at the bottom wins.
+        // A move too large to compute leaves the map.
+        let leaves_at_bottom = |m: &Move| match row.checked_add(m.down) {
+            Some(next_row) => next_row >= height,
+            None => m.down > 0,
+        };
+        if moves.iter().any(leaves_at_bottom) {
+            end = Some((col, row));
+            break;
+        }
+        let here = trees[index(col, row)];
+        for (i, m) in moves.iter().enumerate() {
+            let (next_col, next_row) = match (col.checked_add(m.right), row.checked_add(m.down)) {
+                (Some(next_col), Some(next_row)) if next_row >= 0 => (next_col, next_row),
+                _ => continue,
+            };
+            let next = index(next_col, next_row);
+            let cost = here + tree(next_col, next_row);
+            if cost < trees[next] {
+                trees[next] = cost;
+                came_by[next] = Some(i);
+                if cost == here {
+                    queue.push_front((next_col, next_row));
+                } else {
+                    queue.push_back((next_col, next_row));
+                }
+            }
+        }
+    }
+
+    let (mut col, mut row) = end?;
+    let total = trees[index(col, row)];
+    let mut cells = vec![(col, row as usize)];
+    while let Some(i) = came_by[index(col, row)] {
+        col -= moves[i].right;
+        row -= moves[i].down;
+        cells.push((col, row as usize));
+    }
+    cells.reverse();
+    Some(Route {
+        cells,
+        trees: total,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn map(input: &str) -> Grid<bool> {
+        Grid::parse(input).expect("Expected valid map")
+    }
+
+    // checks that the route only takes the moves and hits as many trees as it claims
+    fn assert_valid(map: &Grid<bool>, moves: &[Move], route: &Route) {
+        assert_eq!(route.cells[0], (0, 0));
+        for step in route.cells.windows(2) {
+            let (from, to) = (step[0], step[1]);
+            let taken = Move {
+                right: to.0 - from.0,
+                down: to.1 as isize - from.1 as isize,
+            };
+            assert!(moves.contains(&taken), "Unexpected move {:?}", taken);
+        }
+        let trees = route
+            .cells
+            .iter()
+            .filter(|(col, row)| *map.get_wrapping(*col, *row as isize))
+            .count();
+        assert_eq!(trees, route.trees);
+    }
+
+    #[test]
+    fn plan_route_avoids_trees() {
+        // given
+        let map = map("...\n.#.\n.##\n#.#\n");
+        let moves = shifts(1, map.width());
+
+        // when
+        let route = plan_route(&map, &moves).expect("Expected a route");
+
+        // then
+        assert_eq!(route.trees, 0);
+        assert_eq!(route.cells.last().map(|(_, row)| *row), Some(3));
+        assert_valid(&map, &moves, &route);
+    }
+
+    #[test]
+    fn plan_route_counts_unavoidable_trees() {
+        // given
+        let map = map("..\n##\n..\n");
+        let moves = shifts(1, map.width());
+
+        // when
+        let route = plan_route(&map, &moves).expect("Expected a route");
+
+        // then
+        assert_eq!(route.trees, 1);
+        assert_valid(&map, &moves, &route);
+    }
+
+    #[test]
+    fn plan_route_takes_sideways_moves() {
+        // given
+        let map = map("....\n##.#\n....\n");
+        let moves = parse_moves("0/1,1/0", map.width()).expect("Expected valid moves");
+
+        // when
+        let route = plan_route(&map, &moves);
+
+        // then
+        assert_eq!(
+            route,
+            Some(Route {
+                cells: vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+                trees: 0
+            })
+        );
+    }
+
+    #[test]
+    fn plan_route_fails_without_way_down() {
+        // given
+        let map = map("..\n..\n");
+        let moves = parse_moves("1/0,-1/0", map.width()).expect("Expected valid moves");
+
+        // when
+        let route = plan_route(&map, &moves);
+
+        // then
+        assert_eq!(route, None);
+        assert!(parse_moves("0/0", 2).is_err());
+        assert_eq!(parse_moves("2", 3).map(|m| m.len()), Ok(5));
+    }
+
+    #[test]
+    fn parse_moves_caps_shifts_at_map_width() {
+        assert_eq!(parse_moves("2", 2), Ok(shifts(1, 2)));
+        assert_eq!(
+            parse_moves("18446744073709551615", 3).map(|m| m.len()),
+            Ok(5)
+        );
+    }
+
+    #[test]
+    fn plan_route_treats_huge_moves_as_leaving_the_map() {
+        // given
+        let map = map("..\n#.\n..\n");
+        let moves = parse_moves(&format!("{}/1,1/{},0/1", isize::MAX, isize::MIN), 2)
+            .expect("Expected valid moves");
+
+        // when
+        let route = plan_route(&map, &moves).expect("Expected a route");
+
+        // then
+        assert_eq!(route.trees, 0);
+        assert_valid(&map, &moves, &route);
+    }
+}
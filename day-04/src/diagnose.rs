@@ -0,0 +1,180 @@
+//! Why passports are not valid: the failures of each passport and the most common reasons.
+
+use crate::schema::{puzzle_schema, Failure, Schema};
+use crate::{check_keys, parse_pass_map, passports, read_schema, LineCounter};
+use common::{for_each_file, parse_args, read_input};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A passport that is not valid
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rejected {
+    /// the line the passport starts at, counting from 1
+    pub line: usize,
+    pub failures: Vec<Failure>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Diagnosis {
+    pub passports: usize,
+    pub rejected: Vec<Rejected>,
+    /// how many passports fail for each reason, most common first
+    pub reasons: Vec<(String, usize)>,
+    /// duplicate and unknown keys, see `check_keys`
+    pub warnings: Vec<String>,
+}
+
+impl Diagnosis {
+    pub fn new(input: &str, schema: &Schema) -> Diagnosis {
+        let mut lines = LineCounter::new(input);
+        let mut passport_count = 0;
+        let mut rejected = Vec::new();
+        let mut reasons: HashMap<String, usize> = HashMap::new();
+        for passport in passports(input) {
+            passport_count += 1;
+            let failures = schema.validate(&parse_pass_map(passport));
+            if failures.is_empty() {
+                continue;
+            }
+            for failure in &failures {
+                *reasons.entry(failure.reason()).or_default() += 1;
+            }
+            rejected.push(Rejected {
+                line: lines.line_of(passport),
+                failures,
+            });
+        }
+        let mut reasons: Vec<(String, usize)> = reasons.into_iter().collect();
+        reasons.sort_unstable_by(|(a, a_count), (b, b_count)| {
+            b_count.cmp(a_count).then_with(|| a.cmp(b))
+        });
+        Diagnosis {
+            passports: passport_count,
+            rejected,
+            reasons,
+            warnings: check_keys(input, schema),
+        }
+    }
+
+    /// The failures of each rejected passport, followed by a table of the reasons
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for rejected in &self.rejected {
+            let failures: Vec<String> = rejected.failures.iter().map(|f| f.to_string()).collect();
+            let _ = writeln!(text, "line {}: {}", rejected.line, failures.join("; "));
+        }
+        let _ = writeln!(
+            text,
+            "{} of {} passports are valid",
+            self.passports - self.rejected.len(),
+            self.passports
+        );
+        if !self.reasons.is_empty() {
+            let _ = writeln!(text, "passports  reason");
+            for (reason, count) in &self.reasons {
+                let _ = writeln!(text, "{:>9}  {}", count, reason);
+            }
+        }
+        text
+    }
+}
+
+/// Prints the diagnosis of each file: `aoc run 4 <file>... --diagnose [--schema <file>]`. Duplicate
+/// and unknown keys are reported on stderr.
+pub fn diagnose_files(args: &[String]) -> Result<(), String> {
+    let (filenames, params) = parse_args(args)?;
+    params.check_known(&["schema"]).map_err(|e| e.to_string())?;
+    let schema = params
+        .get_str("schema")
+        .map(read_schema)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    let schema = schema.as_ref().unwrap_or_else(|| puzzle_schema());
+    for_each_file(&filenames, |path| {
+        let diagnosis = Diagnosis::new(&read_input(path)?, schema);
+        for warning in &diagnosis.warnings {
+            eprintln!("warning: {}", warning);
+        }
+        print!("{}", diagnosis.to_text());
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in
+";
+
+    #[test]
+    fn diagnosis_lists_failures_and_reasons() {
+        // when
+        let diagnosis = Diagnosis::new(EXAMPLE, puzzle_schema());
+
+        // then
+        assert_eq!(diagnosis.passports, 4);
+        assert_eq!(
+            diagnosis.rejected,
+            vec![
+                Rejected {
+                    line: 4,
+                    failures: vec![Failure::Missing {
+                        field: "hgt".to_owned()
+                    }],
+                },
+                Rejected {
+                    line: 12,
+                    failures: vec![Failure::Missing {
+                        field: "byr".to_owned()
+                    }],
+                },
+            ]
+        );
+        assert_eq!(
+            diagnosis.reasons,
+            vec![
+                ("byr: missing".to_owned(), 1),
+                ("hgt: missing".to_owned(), 1)
+            ]
+        );
+        assert!(diagnosis.warnings.is_empty());
+    }
+
+    #[test]
+    fn to_text_counts_reasons() {
+        // given
+        let input = "pid:1 byr:1990\n\npid:2 byr:3000\n\nbyr:2000\n";
+        let schema = crate::schema::parse_schema(
+            "[[field]]\nname = \"pid\"\nrequired = true\npattern = '[0-9]'\n\n[[field]]\nname = \"byr\"\nrange = [1900, 2020]\n",
+        )
+        .expect("Expected valid schema");
+
+        // when
+        let text = Diagnosis::new(input, &schema).to_text();
+
+        // then
+        assert_eq!(
+            text,
+            "line 3: byr: '3000' is not an integer from 1900 to 2020
+line 5: pid: missing
+1 of 3 passports are valid
+passports  reason
+        1  byr: not an integer from 1900 to 2020
+        1  pid: missing
+"
+        );
+    }
+}
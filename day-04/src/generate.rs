@@ -0,0 +1,115 @@
+use crate::Day04;
+use common::generate::{Generated, Generator, Rng};
+
+const REQUIRED: &[&str] = &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+const EYE_COLORS: &[&str] = &["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+const HEX_DIGITS: &[u8] = b"0123456789abcdef";
+
+/// `size` is the number of passports. Each passport is either missing a required field, has a
+/// field with an invalid value, or is valid.
+impl Generator for Day04 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut passports: Vec<String> = Vec::with_capacity(size);
+        let mut complete = 0;
+        let mut valid = 0;
+        for _ in 0..size {
+            let mut fields: Vec<(&str, String)> = REQUIRED
+                .iter()
+                .map(|field| (*field, valid_value(rng, field)))
+                .collect();
+            match rng.below(3) {
+                0 => {
+                    fields.remove(rng.index(fields.len()));
+                }
+                1 => {
+                    let (field, value) = &mut fields[rng.index(REQUIRED.len())];
+                    *value = invalid_value(rng, field);
+                    complete += 1;
+                }
+                _ => {
+                    complete += 1;
+                    valid += 1;
+                }
+            }
+            if rng.chance(0.5) {
+                fields.push(("cid", rng.between(1, 350).to_string()));
+            }
+            rng.shuffle(&mut fields);
+
+            let mut passport = String::with_capacity(100);
+            for (i, (field, value)) in fields.iter().enumerate() {
+                if i > 0 {
+                    passport.push(if rng.chance(0.3) { '\n' } else { ' ' });
+                }
+                passport.push_str(&format!("{}:{}", field, value));
+            }
+            passports.push(passport);
+        }
+
+        Generated {
+            input: passports.join("\n\n") + "\n",
+            part1: Some(complete.to_string()),
+            part2: Some(valid.to_string()),
+        }
+    }
+}
+
+fn valid_value(rng: &mut Rng, field: &str) -> String {
+    match field {
+        "byr" => rng.between(1920, 2002).to_string(),
+        "iyr" => rng.between(2010, 2020).to_string(),
+        "eyr" => rng.between(2020, 2030).to_string(),
+        "hgt" if rng.chance(0.5) => format!("{}cm", rng.between(150, 193)),
+        "hgt" => format!("{}in", rng.between(59, 76)),
+        "hcl" => format!("#{}", hex_digits(rng, 6)),
+        "ecl" => rng.choose(EYE_COLORS).to_string(),
+        _ => format!("{:09}", rng.between(0, 999_999_999)),
+    }
+}
+
+fn invalid_value(rng: &mut Rng, field: &str) -> String {
+    match field {
+        "byr" => rng.between(1900, 1919).to_string(),
+        "iyr" => rng.between(2021, 2030).to_string(),
+        "eyr" => rng.between(2000, 2019).to_string(),
+        "hgt" if rng.chance(0.5) => format!("{}cm", rng.between(194, 250)),
+        "hgt" => rng.between(150, 193).to_string(),
+        "hcl" => hex_digits(rng, 6),
+        "ecl" => "zzz".to_owned(),
+        _ => format!("{:08}", rng.between(0, 99_999_999)),
+    }
+}
+
+fn hex_digits(rng: &mut Rng, n: usize) -> String {
+    (0..n).map(|_| *rng.choose(HEX_DIGITS) as char).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day04::generate(&mut Rng::new(seed), 100);
+
+            // when
+            let mismatches = check_planted::<Day04>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day04>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,219 @@
+//! Day 4: Passport Processing
+
+use common::{warn, Mode, Params, ParseError, Solution};
+use schema::{parse_schema, puzzle_schema, Schema};
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+pub mod diagnose;
+mod generate;
+pub mod regex;
+pub mod schema;
+
+pub struct Day04;
+
+/// The passports, and the schema given with `--schema`
+pub struct Batch<'a> {
+    pub passports: Vec<HashMap<&'a str, &'a str>>,
+    pub schema: Option<Schema>,
+}
+
+impl Batch<'_> {
+    /// The schema to check the passports against, the rules of the puzzle by default
+    pub fn schema(&self) -> &Schema {
+        self.schema.as_ref().unwrap_or_else(|| puzzle_schema())
+    }
+}
+
+impl Solution for Day04 {
+    type Input<'a> = Batch<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const PARAMS: &'static [&'static str] = &["schema"];
+    const FILE_PARAMS: &'static [&'static str] = &["schema"];
+    const MODES: &'static [Mode] = &[Mode {
+        flag: "diagnose",
+        takes_value: false,
+        run: diagnose::diagnose_files,
+    }];
+
+    fn parse(input: &str) -> Result<Batch<'_>, ParseError> {
+        Ok(Batch {
+            passports: parse_pass_maps(input),
+            schema: None,
+        })
+    }
+
+    fn parse_with_params<'a>(input: &'a str, params: &Params) -> Result<Batch<'a>, ParseError> {
+        let mut batch = Day04::parse(input)?;
+        if let Some(path) = params.get_str("schema") {
+            batch.schema = Some(read_schema(path)?);
+        }
+        check_keys(input, batch.schema()).into_iter().for_each(warn);
+        Ok(batch)
+    }
+
+    fn part1(batch: &Batch) -> Result<usize, String> {
+        let schema = batch.schema();
+        Ok(batch
+            .passports
+            .iter()
+            .filter(|pass| schema.is_complete(pass))
+            .count())
+    }
+
+    fn part2(batch: &Batch) -> Result<usize, String> {
+        let schema = batch.schema();
+        Ok(batch
+            .passports
+            .iter()
+            .filter(|pass| schema.is_valid(pass))
+            .count())
+    }
+}
+
+/// Reads a schema file, errors in the file are located in it.
+pub fn read_schema(path: &str) -> Result<Schema, ParseError> {
+    let text = read_to_string(path).map_err(|e| {
+        ParseError::unexpected_token(path, format!("a readable schema file ({})", e))
+    })?;
+    parse_schema(&text).map_err(|e| e.locate(&text).in_file(path))
+}
+
+/// Are all required fields present? `cid` is optional.
+pub fn is_pass_map_valid(pass: &HashMap<&str, &str>) -> bool {
+    puzzle_schema().is_complete(pass)
+}
+
+/// Are all required fields present and valid?
+pub fn is_pass_map_data_valid(pass: &HashMap<&str, &str>) -> bool {
+    puzzle_schema().is_valid(pass)
+}
+
+fn parse_name_value_pair(input: &str) -> Option<(&str, &str)> {
+    let (name, value) = input.split_once(':')?;
+
+    Some((name, value))
+}
+
+/// Parses the `key:value` pairs of a single passport.
+pub fn parse_pass_map(input: &str) -> HashMap<&str, &str> {
+    input
+        .split_whitespace()
+        .filter_map(parse_name_value_pair)
+        .collect()
+}
+
+/// Parses all passports, they are separated by blank lines.
+pub fn parse_pass_maps(input: &str) -> Vec<HashMap<&str, &str>> {
+    passports(input).map(parse_pass_map).collect()
+}
+
+/// The text of each passport
+pub fn passports(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+}
+
+/// Finds the keys that `parse_pass_map` would not check: keys that occur more than once in a
+/// passport, of which only the last value is kept, and keys that the schema does not declare.
+pub fn check_keys(input: &str, schema: &Schema) -> Vec<String> {
+    let mut lines = LineCounter::new(input);
+    let mut warnings = Vec::new();
+    for passport in passports(input) {
+        let mut values: HashMap<&str, &str> = HashMap::new();
+        for (key, value) in passport
+            .split_whitespace()
+            .filter_map(parse_name_value_pair)
+        {
+            let line = lines.line_of(key);
+            if let Some(previous) = values.insert(key, value) {
+                warnings.push(format!(
+                    "line {}: duplicate key '{}', '{}' replaces '{}'",
+                    line, key, value, previous
+                ));
+            }
+            if schema.field(key).is_none() {
+                warnings.push(format!("line {}: unknown key '{}'", line, key));
+            }
+        }
+    }
+    warnings
+}
+
+/// Finds the lines of slices of the input, which have to be looked up in the order of the input.
+pub struct LineCounter<'a> {
+    input: &'a str,
+    offset: usize,
+    line: usize,
+}
+
+impl<'a> LineCounter<'a> {
+    pub fn new(input: &'a str) -> LineCounter<'a> {
+        LineCounter {
+            input,
+            offset: 0,
+            line: 1,
+        }
+    }
+
+    /// The line (counting from 1) at which `slice` starts
+    pub fn line_of(&mut self, slice: &str) -> usize {
+        let offset = slice.as_ptr() as usize - self.input.as_ptr() as usize;
+        self.line += self.input[self.offset..offset].matches('\n').count();
+        self.offset = offset;
+        self.line
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::solve;
+    use std::env::temp_dir;
+    use std::fs::{remove_file, write};
+
+    #[test]
+    fn schema_file_replaces_puzzle_rules() {
+        // given
+        let path = temp_dir().join("day-04-schema-file-replaces-puzzle-rules.toml");
+        write(
+            &path,
+            "[[field]]\nname = \"pid\"\nrequired = true\npattern = '[0-9]+'\n",
+        )
+        .expect("Expected to write schema");
+        let mut params = Params::new();
+        params.insert("schema", &path.display().to_string());
+        let input = "pid:123 byr:1\n\npid:12a\n\nhgt:1cm\n";
+
+        // when
+        let batch = Day04::parse_with_params(input, &params);
+        remove_file(&path).expect("Expected to remove schema");
+
+        // then
+        let batch = batch.expect("Expected valid input and schema");
+        assert_eq!(Day04::part1(&batch), Ok(2));
+        assert_eq!(Day04::part2(&batch), Ok(1));
+    }
+
+    #[test]
+    fn check_keys_warns_about_duplicate_and_unknown_keys() {
+        // given
+        let input = "pid:1 byr:1990\nbyr:1991\n\nzip:123 pid:2\n";
+
+        // when
+        let answers = solve::<Day04>(input, &Params::new()).expect("Expected valid input");
+
+        // then
+        assert_eq!(
+            answers.part1.warnings,
+            vec![
+                "line 2: duplicate key 'byr', '1991' replaces '1990'",
+                "line 4: unknown key 'zip'"
+            ]
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! A small regular expression engine for the `pattern` validator of schemas.
+//!
+//! Supported are literals, `.`, classes like `[a-f0-9]` and `[^#]`, the escapes `\d`, `\w`, `\s`
+//! and escaped special chars, groups with alternatives like `(cm|in)`, and the quantifiers `*`,
+//! `+`, `?`, `{n}`, `{n,}` and `{n,m}`. A pattern always has to match the complete value.
+
+use std::fmt;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Node {
+    Char(char),
+    Any,
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    /// alternatives, each a sequence of nodes
+    Group(Vec<Vec<Node>>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+/// A compiled pattern
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Regex {
+    pattern: String,
+    root: Node,
+}
+
+impl Regex {
+    /// Compiles the pattern, or describes what is wrong with it.
+    pub fn new(pattern: &str) -> Result<Regex, String> {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut parser = Parser { chars, pos: 0 };
+        let alternatives = parser.alternatives()?;
+        if parser.pos < parser.chars.len() {
+            return Err(format!("unmatched ')' at position {}", parser.pos + 1));
+        }
+        Ok(Regex {
+            pattern: pattern.to_owned(),
+            root: Node::Group(alternatives),
+        })
+    }
+
+    /// Does the pattern match all of `value`?
+    pub fn is_match(&self, value: &str) -> bool {
+        let chars: Vec<char> = value.chars().collect();
+        match_node(&self.root, &chars, 0, &mut |end| end == chars.len())
+    }
+}
+
+impl fmt::Display for Regex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.pattern)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn alternatives(&mut self) -> Result<Vec<Vec<Node>>, String> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            alternatives.push(self.sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Node>, String> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantified(atom)?);
+        }
+        Ok(nodes)
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        let position = self.pos + 1;
+        match self.next() {
+            Some('(') => {
+                let alternatives = self.alternatives()?;
+                match self.next() {
+                    Some(')') => Ok(Node::Group(alternatives)),
+                    _ => Err(format!("unclosed '(' at position {}", position)),
+                }
+            }
+            Some('[') => self.class(position),
+            Some('.') => Ok(Node::Any),
+            Some('\\') => self.escape(position),
+            Some(c @ ('*' | '+' | '?' | '{')) => Err(format!(
+                "'{}' at position {} does not follow anything to repeat",
+                c, position
+            )),
+            Some(c) => Ok(Node::Char(c)),
+            None => Err("unexpected end of pattern".to_owned()),
+        }
+    }
+
+    fn escape(&mut self, position: usize) -> Result<Node, String> {
+        let class = |ranges: &[(char, char)]| Node::Class {
+            ranges: ranges.to_vec(),
+            negated: false,
+        };
+        match self.next() {
+            Some('d') => Ok(class(&[('0', '9')])),
+            Some('w') => Ok(class(&[('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')])),
+            Some('s') => Ok(class(&[
+                (' ', ' '),
+                ('\t', '\t'),
+                ('\n', '\n'),
+                ('\r', '\r'),
+            ])),
+            Some(c) if !c.is_alphanumeric() => Ok(Node::Char(c)),
+            Some(c) => Err(format!("unknown escape '\\{}' at position {}", c, position)),
+            None => Err("pattern ends with '\\'".to_owned()),
+        }
+    }
+
+    fn class(&mut self, position: usize) -> Result<Node, String> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut ranges = Vec::new();
+        loop {
+            let from = match self.next() {
+                Some(']') if !ranges.is_empty() => break,
+                Some('\\') => self
+                    .next()
+                    .ok_or_else(|| "pattern ends with '\\'".to_owned())?,
+                Some(c) => c,
+                None => return Err(format!("unclosed '[' at position {}", position)),
+            };
+            let to = if self.peek() == Some('-') && self.chars.get(self.pos + 1) != Some(&']') {
+                self.pos += 1;
+                match self.next() {
+                    Some(to) if to >= from => to,
+                    Some(to) => {
+                        return Err(format!("empty range '{}-{}' in class", from, to));
+                    }
+                    None => return Err(format!("unclosed '[' at position {}", position)),
+                }
+            } else {
+                from
+            };
+            ranges.push((from, to));
+        }
+        Ok(Node::Class { ranges, negated })
+    }
+
+    fn quantified(&mut self, atom: Node) -> Result<Node, String> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => return self.counted(atom),
+            _ => return Ok(atom),
+        };
+        self.pos += 1;
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+        })
+    }
+
+    // `{n}`, `{n,}` or `{n,m}`
+    fn counted(&mut self, atom: Node) -> Result<Node, String> {
+        let position = self.pos + 1;
+        let end = self.chars[self.pos..]
+            .iter()
+            .position(|c| *c == '}')
+            .ok_or_else(|| format!("unclosed '{{' at position {}", position))?;
+        let counts: String = self.chars[self.pos + 1..self.pos + end].iter().collect();
+        self.pos += end + 1;
+        let number = |s: &str| {
+            s.trim()
+                .parse::<usize>()
+                .map_err(|_| format!("invalid count '{{{}}}' at position {}", counts, position))
+        };
+        let (min, max) = match counts.split_once(',') {
+            None => (number(&counts)?, Some(number(&counts)?)),
+            Some((min, max)) if max.trim().is_empty() => (number(min)?, None),
+            Some((min, max)) => (number(min)?, Some(number(max)?)),
+        };
+        if max.is_some_and(|max| max < min) {
+            return Err(format!(
+                "invalid count '{{{}}}' at position {}",
+                counts, position
+            ));
+        }
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+        })
+    }
+}
+
+// Backtracking: calls `rest` with each position at which `node` can end when it starts at `pos`,
+// until `rest` accepts one.
+fn match_node(
+    node: &Node,
+    chars: &[char],
+    pos: usize,
+    rest: &mut dyn FnMut(usize) -> bool,
+) -> bool {
+    match node {
+        Node::Group(alternatives) => alternatives
+            .iter()
+            .any(|sequence| match_sequence(sequence, chars, pos, rest)),
+        Node::Repeat { node, min, max } => match matches_char(node, chars.get(pos)) {
+            Some(_) => match_char_repeat(node, *min, *max, chars, pos, rest),
+            None => match_repeat(node, *min, *max, 0, chars, pos, rest),
+        },
+        _ => matches_char(node, chars.get(pos)) == Some(true) && rest(pos + 1),
+    }
+}
+
+// Does a node that matches a single char match `c`? `None` for the other nodes.
+fn matches_char(node: &Node, c: Option<&char>) -> Option<bool> {
+    let matches = match node {
+        Node::Char(expected) => c == Some(expected),
+        Node::Any => c.is_some(),
+        Node::Class { ranges, negated } => {
+            c.is_some_and(|c| ranges.iter().any(|(from, to)| from <= c && c <= to) != *negated)
+        }
+        Node::Group(_) | Node::Repeat { .. } => return None,
+    };
+    Some(matches)
+}
+
+// The repetition of a single char, without recursing for each char: a long value would overflow
+// the stack otherwise. Greedy, gives back one char at a time.
+fn match_char_repeat(
+    node: &Node,
+    min: usize,
+    max: Option<usize>,
+    chars: &[char],
+    pos: usize,
+    rest: &mut dyn FnMut(usize) -> bool,
+) -> bool {
+    let count = chars[pos.min(chars.len())..]
+        .iter()
+        .take(max.unwrap_or(usize::MAX))
+        .take_while(|c| matches_char(node, Some(c)) == Some(true))
+        .count();
+    (min..=count).rev().any(|count| rest(pos + count))
+}
+
+fn match_sequence(
+    sequence: &[Node],
+    chars: &[char],
+    pos: usize,
+    rest: &mut dyn FnMut(usize) -> bool,
+) -> bool {
+    match sequence.split_first() {
+        None => rest(pos),
+        Some((first, others)) => match_node(first, chars, pos, &mut |next| {
+            match_sequence(others, chars, next, rest)
+        }),
+    }
+}
+
+// greedy: tries one more repetition before giving up on it
+fn match_repeat(
+    node: &Node,
+    min: usize,
+    max: Option<usize>,
+    count: usize,
+    chars: &[char],
+    pos: usize,
+    rest: &mut dyn FnMut(usize) -> bool,
+) -> bool {
+    if max.is_none_or(|max| count < max)
+        && match_node(node, chars, pos, &mut |next| {
+            // a repetition that matches nothing would repeat forever
+            next > pos && match_repeat(node, min, max, count + 1, chars, next, rest)
+        })
+    {
+        return true;
+    }
+    count >= min && rest(pos)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn matches(pattern: &str, value: &str) -> bool {
+        Regex::new(pattern)
+            .expect("Expected valid pattern")
+            .is_match(value)
+    }
+
+    #[test]
+    fn is_match_matches_complete_value() {
+        assert!(matches("#[0-9a-f]{6}", "#123abc"));
+        assert!(!matches("#[0-9a-f]{6}", "#123abz"));
+        assert!(!matches("#[0-9a-f]{6}", "#123abcd"));
+        assert!(matches(r"\d{9}", "000000001"));
+        assert!(!matches(r"\d{9}", "0123456789"));
+        assert!(matches("(amb|blu|brn)", "blu"));
+        assert!(!matches("amb|blu", "ambblu"));
+        assert!(matches("1(5[0-9]|[6-8][0-9]|9[0-3])cm", "193cm"));
+        assert!(matches("a*b+c?", "bb"));
+        assert!(matches("(a|ab)(c|bcd)d*", "abcd"));
+        assert!(matches("[^#]+", "abc"));
+        assert!(!matches("[^#]+", "a#c"));
+        assert!(matches(r"\.x{2,}", ".xxx"));
+        assert!(matches("(a*)*b", "aaab"));
+        assert!(matches("a*ab", "aaab"));
+        assert!(!matches("a{2,3}", "a"));
+    }
+
+    #[test]
+    fn is_match_handles_long_values() {
+        // given
+        let value = format!("#{}", "a".repeat(200_000));
+
+        // then
+        assert!(matches("#[0-9a-f]+", &value));
+        assert!(matches("#.*a", &value));
+        assert!(!matches("#a*b", &value));
+    }
+
+    #[test]
+    fn new_rejects_invalid_patterns() {
+        for pattern in ["(ab", "ab)", "[a-", "*a", "a{3,1}", r"\q", "[z-a]"] {
+            assert!(Regex::new(pattern).is_err(), "{}", pattern);
+        }
+    }
+}
@@ -0,0 +1,424 @@
+//! Declarative rules for documents made of `key:value` fields, read from a TOML file. The rules
+//! of the puzzle are the default schema in `schema.toml`, which also describes the format.
+
+use crate::regex::Regex;
+use common::toml::{parse_toml, Entry, Table, Value};
+use common::ParseError;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+const KEYS: &[&str] = &["name", "required", "range", "units", "pattern", "one_of"];
+
+/// A check of the value of a field
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Validator {
+    /// an integer from `min` to `max`
+    Range { min: i64, max: i64 },
+    /// an integer followed by one of the units, in the range of that unit
+    Units(Vec<Unit>),
+    /// a regular expression that has to match the complete value
+    Pattern(Regex),
+    /// one of the values
+    OneOf(Vec<String>),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Unit {
+    pub unit: String,
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Validator {
+    pub fn check(&self, value: &str) -> bool {
+        match self {
+            Validator::Range { min, max } => in_range(value, *min, *max),
+            Validator::Units(units) => units.iter().any(|unit| {
+                value
+                    .strip_suffix(unit.unit.as_str())
+                    .is_some_and(|number| in_range(number, unit.min, unit.max))
+            }),
+            Validator::Pattern(regex) => regex.is_match(value),
+            Validator::OneOf(values) => values.iter().any(|v| v == value),
+        }
+    }
+}
+
+fn in_range(number: &str, min: i64, max: i64) -> bool {
+    number.parse::<i64>().is_ok_and(|n| n >= min && n <= max)
+}
+
+/// Describes what the validator expects, e.g. "an integer from 1920 to 2002"
+impl fmt::Display for Validator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Validator::Range { min, max } => write!(f, "an integer from {} to {}", min, max),
+            Validator::Units(units) => {
+                let units: Vec<String> = units
+                    .iter()
+                    .map(|u| format!("{} to {} {}", u.min, u.max, u.unit))
+                    .collect();
+                write!(f, "{}", units.join(" or "))
+            }
+            Validator::Pattern(regex) => write!(f, "a value matching '{}'", regex),
+            Validator::OneOf(values) => write!(f, "one of {}", values.join(", ")),
+        }
+    }
+}
+
+/// A key of the document, and the checks of its value
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Field {
+    pub name: String,
+    pub required: bool,
+    pub validators: Vec<Validator>,
+}
+
+/// Why a document is not valid
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Failure {
+    /// a required field is missing
+    Missing { field: String },
+    /// the value of the field does not pass a validator, `expected` describes the validator
+    Invalid {
+        field: String,
+        value: String,
+        expected: String,
+    },
+}
+
+impl Failure {
+    /// The failure without the value, so equal failures of different documents can be counted
+    pub fn reason(&self) -> String {
+        match self {
+            Failure::Missing { field } => format!("{}: missing", field),
+            Failure::Invalid {
+                field, expected, ..
+            } => format!("{}: not {}", field, expected),
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Missing { field } => write!(f, "{}: missing", field),
+            Failure::Invalid {
+                field,
+                value,
+                expected,
+            } => write!(f, "{}: '{}' is not {}", field, value, expected),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Schema {
+    pub fields: Vec<Field>,
+}
+
+impl Schema {
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Does the document have all required fields?
+    pub fn is_complete(&self, document: &HashMap<&str, &str>) -> bool {
+        self.fields
+            .iter()
+            .filter(|field| field.required)
+            .all(|field| document.contains_key(field.name.as_str()))
+    }
+
+    /// Is the document complete, and do the values of all declared fields pass their validators?
+    pub fn is_valid(&self, document: &HashMap<&str, &str>) -> bool {
+        self.validate(document).is_empty()
+    }
+
+    /// Every missing required field and every validator that a value does not pass, in the order
+    /// of the fields in the schema
+    pub fn validate(&self, document: &HashMap<&str, &str>) -> Vec<Failure> {
+        let mut failures = Vec::new();
+        for field in &self.fields {
+            match document.get(field.name.as_str()) {
+                None if field.required => failures.push(Failure::Missing {
+                    field: field.name.clone(),
+                }),
+                None => (),
+                Some(value) => failures.extend(
+                    field
+                        .validators
+                        .iter()
+                        .filter(|validator| !validator.check(value))
+                        .map(|validator| Failure::Invalid {
+                            field: field.name.clone(),
+                            value: value.to_string(),
+                            expected: validator.to_string(),
+                        }),
+                ),
+            }
+        }
+        failures
+    }
+}
+
+/// The rules of the puzzle
+pub fn puzzle_schema() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        parse_schema(include_str!("../schema.toml")).expect("Expected valid default schema")
+    })
+}
+
+/// Parses a schema: a `[[field]]` table for each field of the document.
+pub fn parse_schema(text: &str) -> Result<Schema, ParseError> {
+    let mut fields: Vec<Field> = Vec::new();
+    for table in parse_toml(text)? {
+        if table.name.is_empty() {
+            if let Some(entry) = table.entries.first() {
+                return Err(ParseError::unexpected_token(entry.key, "a [[field]] table"));
+            }
+            continue;
+        }
+        if table.name != "field" || !table.is_array {
+            return Err(ParseError::unexpected_token(table.header, "[[field]]"));
+        }
+        let field = parse_field(&table)?;
+        if fields.iter().any(|f| f.name == field.name) {
+            return Err(ParseError::bad_layout(
+                table.header,
+                format!("field '{}' is declared twice", field.name),
+            ));
+        }
+        fields.push(field);
+    }
+    Ok(Schema { fields })
+}
+
+fn parse_field(table: &Table) -> Result<Field, ParseError> {
+    let name = table
+        .get_str("name")?
+        .ok_or_else(|| ParseError::missing_field(table.header, "key 'name'"))?
+        .to_owned();
+    let mut field = Field {
+        name,
+        required: false,
+        validators: Vec::new(),
+    };
+    for entry in &table.entries {
+        match entry.key {
+            "name" => (),
+            "required" => match entry.value {
+                Value::Boolean(required) => field.required = required,
+                _ => return Err(ParseError::unexpected_token(entry.raw, "true or false")),
+            },
+            "range" => {
+                let (min, max) = range(entry)?;
+                field.validators.push(Validator::Range { min, max });
+            }
+            "units" => {
+                let units = strings(entry)?
+                    .iter()
+                    .map(|unit| parse_unit(unit))
+                    .collect::<Option<Vec<Unit>>>()
+                    .ok_or_else(|| {
+                        ParseError::unexpected_token(entry.raw, "units like [\"cm:150-193\"]")
+                    })?;
+                field.validators.push(Validator::Units(units));
+            }
+            "pattern" => {
+                let pattern = match &entry.value {
+                    Value::String(pattern) => pattern,
+                    _ => return Err(ParseError::unexpected_token(entry.raw, "a string")),
+                };
+                let regex = Regex::new(pattern).map_err(|e| {
+                    ParseError::unexpected_token(entry.raw, format!("a regular expression ({})", e))
+                })?;
+                field.validators.push(Validator::Pattern(regex));
+            }
+            "one_of" => field.validators.push(Validator::OneOf(strings(entry)?)),
+            key => {
+                return Err(ParseError::unexpected_token(
+                    key,
+                    format!("one of the keys {}", KEYS.join(", ")),
+                ))
+            }
+        }
+    }
+    Ok(field)
+}
+
+fn range(entry: &Entry) -> Result<(i64, i64), ParseError> {
+    match &entry.value {
+        Value::Array(values) => match values.as_slice() {
+            [Value::Integer(min), Value::Integer(max)] if min <= max => Ok((*min, *max)),
+            _ => Err(ParseError::unexpected_token(
+                entry.raw,
+                "[min, max] with min <= max",
+            )),
+        },
+        _ => Err(ParseError::unexpected_token(entry.raw, "[min, max]")),
+    }
+}
+
+fn strings(entry: &Entry) -> Result<Vec<String>, ParseError> {
+    let values = match &entry.value {
+        Value::Array(values) if !values.is_empty() => values,
+        _ => {
+            return Err(ParseError::unexpected_token(
+                entry.raw,
+                "an array of strings",
+            ))
+        }
+    };
+    values
+        .iter()
+        .map(|value| match value {
+            Value::String(s) => Ok(s.clone()),
+            _ => Err(ParseError::unexpected_token(
+                entry.raw,
+                "an array of strings",
+            )),
+        })
+        .collect()
+}
+
+// `cm:150-193`
+fn parse_unit(unit: &str) -> Option<Unit> {
+    let (name, range) = unit.split_once(':')?;
+    let (min, max) = range.split_once('-')?;
+    let (min, max) = (min.parse().ok()?, max.parse().ok()?);
+    if name.is_empty() || min > max {
+        return None;
+    }
+    Some(Unit {
+        unit: name.to_owned(),
+        min,
+        max,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn puzzle_schema_has_all_fields() {
+        // when
+        let schema = puzzle_schema();
+
+        // then
+        let names: Vec<(&str, bool)> = schema
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.required))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("byr", true),
+                ("iyr", true),
+                ("eyr", true),
+                ("hgt", true),
+                ("hcl", true),
+                ("ecl", true),
+                ("pid", true),
+                ("cid", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn validators_check_values() {
+        // given
+        let hgt = &puzzle_schema()
+            .field("hgt")
+            .expect("Expected hgt")
+            .validators[0];
+        let pid = &puzzle_schema()
+            .field("pid")
+            .expect("Expected pid")
+            .validators[0];
+
+        // then
+        assert!(hgt.check("60in"));
+        assert!(hgt.check("190cm"));
+        assert!(!hgt.check("190in"));
+        assert!(!hgt.check("190"));
+        assert!(pid.check("000000001"));
+        assert!(!pid.check("0123456789"));
+        assert_eq!(hgt.to_string(), "150 to 193 cm or 59 to 76 in");
+    }
+
+    #[test]
+    fn validate_lists_every_failure() {
+        // given
+        let document: HashMap<&str, &str> = [
+            ("byr", "1919"),
+            ("hgt", "190in"),
+            ("ecl", "blu"),
+            ("pid", "12"),
+        ]
+        .iter()
+        .copied()
+        .collect();
+
+        // when
+        let failures = puzzle_schema().validate(&document);
+
+        // then
+        let described: Vec<String> = failures.iter().map(|f| f.to_string()).collect();
+        assert_eq!(
+            described,
+            vec![
+                "byr: '1919' is not an integer from 1920 to 2002",
+                "iyr: missing",
+                "eyr: missing",
+                "hgt: '190in' is not 150 to 193 cm or 59 to 76 in",
+                "hcl: missing",
+                "pid: '12' is not a value matching '[0-9]{9}'",
+            ]
+        );
+        assert_eq!(
+            failures[0].reason(),
+            "byr: not an integer from 1920 to 2002"
+        );
+    }
+
+    #[test]
+    fn parse_schema_reads_other_documents() {
+        // given
+        let text = "[[field]]\nname = \"iban\"\nrequired = true\npattern = '[A-Z]{2}[0-9]{2}[A-Z0-9]+'\n\n[[field]]\nname = \"age\"\nrange = [18, 120]\n";
+        let document: HashMap<&str, &str> = [("iban", "DE44500105175407324931"), ("age", "17")]
+            .iter()
+            .copied()
+            .collect();
+
+        // when
+        let schema = parse_schema(text).expect("Expected valid schema");
+
+        // then
+        assert!(schema.is_complete(&document));
+        assert!(!schema.is_valid(&document));
+    }
+
+    #[test]
+    fn parse_schema_rejects_bad_fields() {
+        // given
+        let bad = [
+            "[[field]]\nrequired = true\n",
+            "[[field]]\nname = \"a\"\nrange = [5, 1]\n",
+            "[[field]]\nname = \"a\"\nunits = [\"cm\"]\n",
+            "[[field]]\nname = \"a\"\npattern = '(a'\n",
+            "[[field]]\nname = \"a\"\nmax = 3\n",
+            "[[field]]\nname = \"a\"\n[[field]]\nname = \"a\"\n",
+            "[fields]\nname = \"a\"\n",
+        ];
+
+        // then
+        for text in bad {
+            assert!(parse_schema(text).is_err(), "{}", text);
+        }
+    }
+}
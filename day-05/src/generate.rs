@@ -0,0 +1,75 @@
+use crate::Day05;
+use common::generate::{Generated, Generator, Rng};
+
+/// `size` is the number of boarding passes (3 to 1000). The seat IDs are consecutive, except for
+/// one free seat.
+impl Generator for Day05 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.clamp(3, 1000) as u16;
+        let lowest = rng.between(1, i64::from(1023 - size)) as u16;
+        let highest = lowest + size;
+        let free = rng.between(i64::from(lowest) + 1, i64::from(highest) - 1) as u16;
+
+        let mut seat_ids: Vec<u16> = (lowest..=highest).filter(|id| *id != free).collect();
+        rng.shuffle(&mut seat_ids);
+
+        Generated {
+            input: seat_ids
+                .iter()
+                .map(|id| boarding_pass(*id) + "\n")
+                .collect(),
+            part1: Some(highest.to_string()),
+            part2: Some(free.to_string()),
+        }
+    }
+}
+
+fn boarding_pass(seat_id: u16) -> String {
+    (0..10)
+        .rev()
+        .map(|bit| {
+            let set = seat_id & (1 << bit) != 0;
+            match (bit >= 3, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day05::generate(&mut Rng::new(seed), 500);
+
+            // when
+            let mismatches = check_planted::<Day05>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn boarding_pass_encodes_seat_id() {
+        assert_eq!(boarding_pass(357), "FBFBBFFRLR");
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day05>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
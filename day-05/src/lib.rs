@@ -0,0 +1,127 @@
+//! Day 5: Binary Boarding
+
+use common::{ParseError, Solution};
+
+mod generate;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'a> = Vec<u16>;
+    type Answer1 = u16;
+    type Answer2 = u16;
+
+    fn parse(input: &str) -> Result<Vec<u16>, ParseError> {
+        input
+            .split('\n')
+            .filter(|l| !l.is_empty())
+            .map(line_to_number)
+            .collect()
+    }
+
+    fn part1(seat_ids: &Vec<u16>) -> Result<u16, String> {
+        seat_ids
+            .iter()
+            .max()
+            .copied()
+            .ok_or_else(|| "No seat IDs in input".to_owned())
+    }
+
+    fn part2(seat_ids: &Vec<u16>) -> Result<u16, String> {
+        find_free_seat(seat_ids).ok_or_else(|| "There is no free seat!".to_owned())
+    }
+}
+
+/// Finds the missing seat ID whose neighbours are both taken.
+pub fn find_free_seat(seat_ids: &[u16]) -> Option<u16> {
+    let max_seat_id = *seat_ids.iter().max()?;
+
+    let mut taken_seats: Vec<bool> = vec![false; max_seat_id as usize + 1];
+
+    for seat in seat_ids {
+        taken_seats[*seat as usize] = true;
+    }
+
+    taken_seats
+        .windows(3)
+        .enumerate()
+        .filter(|(_, w)| w[0] && !w[1] && w[2])
+        .map(|(i, _)| (i + 1) as u16)
+        .next()
+}
+
+/// Decodes a boarding pass like `FBFBBFFRLR` into its seat ID.
+pub fn line_to_number(line: &str) -> Result<u16, ParseError> {
+    if line.len() != 10 {
+        return Err(ParseError::bad_layout(line, "line is not 10 bytes long"));
+    }
+    let mut n: u16 = 0;
+    for (i, c) in line.char_indices() {
+        n <<= 1;
+        let bit = match c {
+            'F' => 0,
+            'B' => 1,
+            'R' => 1,
+            'L' => 0,
+            _ => {
+                return Err(ParseError::unexpected_token(
+                    &line[i..i + c.len_utf8()],
+                    "one of 'F', 'B', 'L', 'R'",
+                ))
+            }
+        };
+        n += bit;
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::ErrorKind;
+
+    #[test]
+    fn line_to_number_should_parse_correctly() {
+        // given
+        let line = "FBFBBFFRLR";
+
+        // when
+        let result = line_to_number(line);
+
+        // then
+        assert_eq!(result, Ok(357));
+    }
+
+    #[test]
+    fn line_to_number_should_fail_for_invalid_length() {
+        // given
+        let line = "FBFBFBFBFBF";
+
+        // when
+        let result = line_to_number(line);
+
+        // then
+        assert!(matches!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::BadLayout { .. })
+        ));
+    }
+
+    #[test]
+    fn line_to_number_should_fail_for_unknown_characters() {
+        // given
+        let line = "FBFBÖFBFR";
+
+        // when
+        let result = line_to_number(line);
+
+        // then
+        assert_eq!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::UnexpectedToken {
+                found: "Ö".to_owned(),
+                expected: "one of 'F', 'B', 'L', 'R'".to_owned(),
+            })
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::Day06;
+use common::generate::{Generated, Generator, Rng};
+
+/// `size` is the number of groups, each with one to five people.
+impl Generator for Day06 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut groups: Vec<String> = Vec::with_capacity(size);
+        let mut anyone = 0;
+        let mut everyone = 0;
+        for _ in 0..size {
+            let people: Vec<u32> = (0..rng.between(1, 5))
+                .map(|_| {
+                    // about a quarter of the questions, but at least one, are answered with yes
+                    let answers = rng.next_u64() & rng.next_u64() & ((1 << 26) - 1);
+                    answers as u32 | 1 << rng.below(26)
+                })
+                .collect();
+            anyone += people.iter().fold(0, |acc, p| acc | p).count_ones();
+            everyone += people.iter().fold(u32::MAX, |acc, p| acc & p).count_ones();
+            let lines: Vec<String> = people
+                .iter()
+                .map(|answers| {
+                    let mut letters: Vec<char> = (0..26)
+                        .filter(|bit| answers & (1 << bit) != 0)
+                        .map(|bit| (b'a' + bit as u8) as char)
+                        .collect();
+                    rng.shuffle(&mut letters);
+                    letters.into_iter().collect()
+                })
+                .collect();
+            groups.push(lines.join("\n"));
+        }
+
+        Generated {
+            input: groups.join("\n\n") + "\n",
+            part1: Some(anyone.to_string()),
+            part2: Some(everyone.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day06::generate(&mut Rng::new(seed), 100);
+
+            // when
+            let mismatches = check_planted::<Day06>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day06>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
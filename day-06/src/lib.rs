@@ -0,0 +1,137 @@
+//! Day 6: Custom Customs
+
+use common::{ParseError, Solution};
+
+mod generate;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    // the groups parsed as "anyone answered yes" and as "everyone answered yes"
+    type Input<'a> = (Vec<[bool; 26]>, Vec<[bool; 26]>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok((parse_groups_any(input), parse_groups_all(input)))
+    }
+
+    fn part1((groups_any, _): &Self::Input<'_>) -> Result<usize, String> {
+        Ok(count_yes_answers(groups_any))
+    }
+
+    fn part2((_, groups_all): &Self::Input<'_>) -> Result<usize, String> {
+        Ok(count_yes_answers(groups_all))
+    }
+}
+
+/// Sums up the questions answered with yes over all groups.
+pub fn count_yes_answers(groups: &[[bool; 26]]) -> usize {
+    groups
+        .iter()
+        .map(|group| group.iter().filter(|a| **a).count())
+        .sum()
+}
+
+/// Parses the groups, a question counts if anyone in the group answered yes.
+pub fn parse_groups_any(content: &str) -> Vec<[bool; 26]> {
+    content
+        .split("\n\n")
+        .filter(|g| !g.is_empty())
+        .map(parse_group_any)
+        .collect()
+}
+
+fn parse_group_any(input: &str) -> [bool; 26] {
+    let mut answers = [false; 26];
+    for c in input
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic() && c.is_ascii_lowercase())
+    {
+        let mut buf = [0; 1];
+        c.encode_utf8(&mut buf);
+        answers[(buf[0] - 97) as usize] = true;
+    }
+    answers
+}
+
+/// Parses the groups, a question counts if everyone in the group answered yes.
+pub fn parse_groups_all(content: &str) -> Vec<[bool; 26]> {
+    content
+        .split("\n\n")
+        .filter(|g| !g.is_empty())
+        .map(parse_group_all)
+        .collect()
+}
+
+fn parse_group_all(input: &str) -> [bool; 26] {
+    input
+        .split('\n')
+        .filter(|s| !s.is_empty())
+        .map(parse_group_any)
+        .fold([true; 26], |mut all, this| {
+            for i in 0..26 {
+                all[i] = all[i] && this[i]
+            }
+            all
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn count_any_yes_answers_works_correctly() {
+        // given
+        let input = r"abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+        // when
+        let groups = parse_groups_any(input);
+        let count = count_yes_answers(&groups);
+
+        // then
+        assert_eq!(count, 11);
+    }
+
+    #[test]
+    fn count_all_yes_answers_works_correctly() {
+        // given
+        let input = r"abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+        // when
+        let groups = parse_groups_all(input);
+        let count = count_yes_answers(&groups);
+
+        // then
+        assert_eq!(count, 6);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::Day07;
+use common::generate::{Generated, Generator, Rng};
+
+const ADJECTIVES: &[&str] = &[
+    "light", "dark", "bright", "muted", "shiny", "faded", "dotted", "vibrant", "dull", "pale",
+    "clear", "dim", "drab", "mirrored", "plaid", "posh", "striped", "wavy",
+];
+const COLORS: &[&str] = &[
+    "red", "orange", "white", "yellow", "gold", "olive", "plum", "blue", "black", "green", "tan",
+    "teal", "violet", "beige", "bronze", "coral", "crimson", "cyan", "fuchsia", "indigo",
+];
+const TARGET: &str = "shiny gold";
+
+/// `size` is the number of bag colors (at least 2). The rules form a random DAG: bags only
+/// contain bags of colors that come later in a fixed order. Shiny gold is near the end of that
+/// order, so the number of bags inside it stays small.
+impl Generator for Day07 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.max(2);
+        let target = size - 1 - rng.index(size.min(10));
+        let names = color_names(size, target);
+
+        let contents: Vec<Vec<(usize, u64)>> = (0..size)
+            .map(|color| {
+                let mut children: Vec<usize> = ((color + 1)..size).collect();
+                rng.shuffle(&mut children);
+                children.truncate(rng.index(4));
+                children
+                    .into_iter()
+                    .map(|child| (child, rng.between(1, 4) as u64))
+                    .collect()
+            })
+            .collect();
+
+        let mut lines: Vec<String> = contents
+            .iter()
+            .enumerate()
+            .map(|(color, children)| rule_line(&names, color, children))
+            .collect();
+        rng.shuffle(&mut lines);
+
+        Generated {
+            input: lines.concat(),
+            part1: Some(count_containers(&contents, target).to_string()),
+            part2: Some((count_bags(&contents, target) - 1).to_string()),
+        }
+    }
+}
+
+fn color_names(size: usize, target: usize) -> Vec<String> {
+    let mut combinations = (0..).map(|i: usize| {
+        let adjective = ADJECTIVES[i % ADJECTIVES.len()];
+        let color = COLORS[(i / ADJECTIVES.len()) % COLORS.len()];
+        match i / (ADJECTIVES.len() * COLORS.len()) {
+            0 => format!("{} {}", adjective, color),
+            // numbered variants once the combinations run out
+            round => format!("{}{} {}", adjective, round, color),
+        }
+    });
+    (0..size)
+        .map(|i| {
+            if i == target {
+                TARGET.to_owned()
+            } else {
+                combinations.find(|name| name != TARGET).unwrap_or_default()
+            }
+        })
+        .collect()
+}
+
+fn rule_line(names: &[String], color: usize, children: &[(usize, u64)]) -> String {
+    if children.is_empty() {
+        return format!("{} bags contain no other bags.\n", names[color]);
+    }
+    let contained: Vec<String> = children
+        .iter()
+        .map(|(child, n)| {
+            let bags = if *n == 1 { "bag" } else { "bags" };
+            format!("{} {} {}", n, names[*child], bags)
+        })
+        .collect();
+    format!("{} bags contain {}.\n", names[color], contained.join(", "))
+}
+
+// the number of colors that eventually contain the target
+fn count_containers(contents: &[Vec<(usize, u64)>], target: usize) -> usize {
+    let mut contains_target = vec![false; contents.len()];
+    // children come later in the order, so they are done before their parents
+    for color in (0..target).rev() {
+        contains_target[color] = contents[color]
+            .iter()
+            .any(|(child, _)| *child == target || contains_target[*child]);
+    }
+    contains_target.iter().filter(|c| **c).count()
+}
+
+// the number of bags including the outermost one
+fn count_bags(contents: &[Vec<(usize, u64)>], color: usize) -> u64 {
+    1 + contents[color]
+        .iter()
+        .map(|(child, n)| n * count_bags(contents, *child))
+        .sum::<u64>()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day07::generate(&mut Rng::new(seed), 400);
+
+            // when
+            let mismatches = check_planted::<Day07>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day07>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
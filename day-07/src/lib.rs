@@ -0,0 +1,206 @@
+//! Day 7: Handy Haversacks
+
+use common::{end_of, parse_int, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+
+mod generate;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input<'a> = Graph<'a>;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Graph<'_>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(graph: &Graph) -> Result<usize, String> {
+        let inversed_graph = inverse_graph(graph);
+        Ok(count_descendants(&inversed_graph, "shiny gold"))
+    }
+
+    fn part2(graph: &Graph) -> Result<u64, String> {
+        count_bags_in_shiny_gold_bag(graph)
+    }
+}
+
+/// Counts the nodes reachable from `start`, without `start` itself.
+pub fn count_descendants(graph: &HashMap<&str, Vec<&str>>, start: &str) -> usize {
+    let mut seen: HashSet<&str> = HashSet::with_capacity(graph.len());
+    let mut stack: Vec<&str> = Vec::with_capacity(graph.len());
+    stack.push(start);
+
+    while let Some(v) = stack.pop() {
+        seen.insert(v);
+        if let Some(children) = graph.get(v) {
+            for child in children {
+                if !seen.contains(child) {
+                    stack.push(child);
+                }
+            }
+        }
+    }
+
+    seen.len() - 1
+}
+
+/// Counts the bags inside a shiny gold bag. An error if a bag contains itself, there would be no
+/// end to them, or if the count does not fit into a u64.
+pub fn count_bags_in_shiny_gold_bag(graph: &Graph) -> Result<u64, String> {
+    let mut lookup = HashMap::with_capacity(graph.len());
+    count_contained_bags(graph, "shiny gold", &mut lookup)
+}
+
+// `lookup` holds `None` for the bags whose count is still being computed
+fn count_contained_bags<'a>(
+    graph: &Graph<'a>,
+    color: &'a str,
+    lookup: &mut HashMap<&'a str, Option<u64>>,
+) -> Result<u64, String> {
+    match lookup.get(color) {
+        Some(Some(n)) => return Ok(*n),
+        Some(None) => return Err(format!("A {} bag contains itself", color)),
+        None => {}
+    }
+    lookup.insert(color, None);
+    let mut number_of_children: u64 = 0;
+    for (child_color, n) in graph.get(color).into_iter().flatten() {
+        let inside = count_contained_bags(graph, child_color, lookup)?;
+        number_of_children = inside
+            .checked_add(1)
+            .and_then(|bags| bags.checked_mul(*n))
+            .and_then(|bags| bags.checked_add(number_of_children))
+            .ok_or_else(|| format!("Overflow counting the bags in a {} bag", color))?;
+    }
+    lookup.insert(color, Some(number_of_children));
+    Ok(number_of_children)
+}
+
+/// Maps each bag color to the colors of the bags that directly contain it.
+pub fn inverse_graph<'a>(graph: &'a Graph<'a>) -> HashMap<&'a str, Vec<&'a str>> {
+    let mut inverted: HashMap<&'a str, Vec<&'a str>> = HashMap::with_capacity(graph.len());
+
+    for (lhs, rhs) in graph.iter() {
+        for (key, _) in rhs {
+            inverted
+                .entry(key)
+                .or_insert_with(|| Vec::with_capacity(graph.len()))
+                .push(lhs);
+        }
+    }
+
+    inverted
+}
+
+/// Maps each bag color to the colors (and amounts) of bags it directly contains
+pub type Graph<'a> = HashMap<&'a str, Vec<(&'a str, u64)>>;
+
+pub fn parse_lines(content: &str) -> Result<Graph<'_>, ParseError> {
+    content
+        .split('\n')
+        .filter(|s| !s.is_empty())
+        .map(parse_line)
+        .collect()
+}
+
+type GraphEntry<'a> = (&'a str, Vec<(&'a str, u64)>);
+
+fn parse_line(line: &str) -> Result<GraphEntry<'_>, ParseError> {
+    let (lhs, rhs_raw) = line
+        .split_once(" bags contain ")
+        .ok_or_else(|| ParseError::missing_field(end_of(line), "' bags contain '"))?;
+    if lhs.is_empty() {
+        return Err(ParseError::missing_field(lhs, "bag color"));
+    }
+    if rhs_raw.is_empty() {
+        return Err(ParseError::missing_field(rhs_raw, "contained bags"));
+    }
+
+    if rhs_raw == "no other bags." {
+        return Ok((lhs, vec![]));
+    }
+
+    let rhs: Result<Vec<(&str, u64)>, ParseError> =
+        rhs_raw.split(", ").map(parse_num_and_color).collect();
+
+    Ok((lhs, rhs?))
+}
+
+fn parse_num_and_color(s: &str) -> Result<(&str, u64), ParseError> {
+    let trimmed = s
+        .trim()
+        .trim_end_matches('.')
+        .trim_end_matches("bags")
+        .trim_end_matches("bag");
+    let (num, color) = trimmed
+        .split_once(' ')
+        .ok_or_else(|| ParseError::missing_field(end_of(trimmed), "bag color"))?;
+    let num = parse_int::<u64>(num)?;
+    let color = color.trim().trim_end_matches('.');
+    Ok((color, num))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_line_parses_valid_line() {
+        // given
+        let line = "light orange bags contain 1 dark maroon bag, 3 dim maroon bags, 5 striped green bags, 2 pale aqua bags.";
+
+        // when
+        let result = parse_line(line);
+
+        // then
+        let (lhs, rhs) = result.expect("Expected valid result");
+        assert_eq!(lhs, "light orange");
+        assert_eq!(
+            &rhs,
+            &[
+                ("dark maroon", 1),
+                ("dim maroon", 3),
+                ("striped green", 5),
+                ("pale aqua", 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn count_bags_in_shiny_gold_bag_counts_correctly() {
+        // given
+        let rules = r"light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+        let graph = parse_lines(rules).expect("Expected valid graph");
+
+        // when
+        let count = count_bags_in_shiny_gold_bag(&graph);
+
+        // then
+        assert_eq!(count, Ok(32));
+    }
+
+    #[test]
+    fn count_bags_in_shiny_gold_bag_rejects_cycles() {
+        // given
+        let rules = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 1 shiny gold bag.";
+        let graph = parse_lines(rules).expect("Expected valid graph");
+
+        // when
+        let count = count_bags_in_shiny_gold_bag(&graph);
+
+        // then
+        assert_eq!(count, Err("A shiny gold bag contains itself".to_owned()));
+    }
+}
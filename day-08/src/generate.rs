@@ -0,0 +1,88 @@
+use crate::{Day08, Op};
+use common::generate::{Generated, Generator, Rng};
+
+/// `size` is the number of instructions (at least 2). Exactly one `jmp` is corrupt: it jumps
+/// backwards, where it should have been a `nop`. All other jumps go forward and never skip the
+/// corrupt instruction, so the program loops and changing any earlier instruction does not help.
+impl Generator for Day08 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.max(2) as i32;
+        let corrupt = rng.between(1, i64::from(size) - 1) as i32;
+
+        let program: Vec<Op> = (0..size)
+            .map(|ip| {
+                if ip == corrupt {
+                    return Op::Jmp(rng.between(-i64::from(ip), -1) as i32);
+                }
+                // jumps before the corrupt instruction must not skip it
+                let target_limit = if ip < corrupt { corrupt } else { size };
+                match rng.below(3) {
+                    0 => Op::Acc(rng.between(-50, 50) as i32),
+                    1 => Op::Jmp(rng.between(1, i64::from(target_limit - ip)) as i32),
+                    _ => Op::Nop(rng.between(-i64::from(ip), i64::from(target_limit - ip)) as i32),
+                }
+            })
+            .collect();
+
+        let mut repaired = program.clone();
+        if let Op::Jmp(arg) = program[corrupt as usize] {
+            repaired[corrupt as usize] = Op::Nop(arg);
+        }
+
+        Generated {
+            input: program.iter().map(|op| format!("{}\n", op)).collect(),
+            part1: Some(run(&program).to_string()),
+            part2: Some(run(&repaired).to_string()),
+        }
+    }
+}
+
+// the accumulator when the program terminates or is about to run an instruction twice
+fn run(program: &[Op]) -> i32 {
+    let mut visited = vec![false; program.len()];
+    let mut ip = 0;
+    let mut accumulator = 0;
+    while ip < program.len() && !visited[ip] {
+        visited[ip] = true;
+        match program[ip] {
+            Op::Acc(arg) => accumulator += arg,
+            Op::Jmp(arg) => {
+                ip = (ip as i32 + arg) as usize;
+                continue;
+            }
+            Op::Nop(_) => (),
+        }
+        ip += 1;
+    }
+    accumulator
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day08::generate(&mut Rng::new(seed), 200);
+
+            // when
+            let mismatches = check_planted::<Day08>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day08>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,388 @@
+//! Day 8: Handheld Halting
+
+use common::parallel;
+use common::{end_of, parse_int, ParseError, Solution};
+use std::fmt;
+
+mod generate;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'a> = Vec<Op>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Op>, ParseError> {
+        parse_ops(input)
+    }
+
+    fn part1(program: &Vec<Op>) -> Result<i32, String> {
+        match detect_loop(program)? {
+            (true, accumulator_after_loop) => Ok(accumulator_after_loop),
+            (false, _) => Err("Unable to detect loop, program terminated correctly".to_owned()),
+        }
+    }
+
+    fn part2(program: &Vec<Op>) -> Result<i32, String> {
+        repair_program(program)
+    }
+}
+
+/// Changes a single `jmp` to `nop` or vice versa so the program terminates, and returns the
+/// accumulator after termination.
+pub fn repair_program(program: &[Op]) -> Result<i32, String> {
+    let candidates: Vec<usize> = (0..program.len())
+        .filter(|i| !matches!(program[*i], Op::Acc(_)))
+        .collect();
+    parallel::par_find_first(&candidates, |i| {
+        let mut mod_program = program.to_vec();
+        mod_program[*i] = match program[*i] {
+            Op::Jmp(arg) => Op::Nop(arg),
+            Op::Nop(arg) => Op::Jmp(arg),
+            Op::Acc(arg) => Op::Acc(arg),
+        };
+        match detect_loop(&mod_program) {
+            Ok((false, acc)) => Some(acc),
+            _ => None,
+        }
+    })
+    .ok_or_else(|| "Unable to repair program".to_owned())
+}
+
+/// Runs the program until an instruction would run a second time or the program terminates.
+/// Returns whether there is a loop and the accumulator at that point.
+pub fn detect_loop(program: &[Op]) -> Result<(bool, i32), String> {
+    if program.is_empty() {
+        return Err("Program is empty".to_owned());
+    }
+    let mut state = State::default();
+    let mut visited = vec![false; program.len()];
+
+    while !visited[state.ip as usize] {
+        visited[state.ip as usize] = true;
+        state = run_instruction(program, &state)?;
+        if state.ip == program.len() as i32 {
+            return Ok((false, state.accumulator));
+        }
+        if state.ip < 0 || state.ip > program.len() as i32 {
+            return Err(format!(
+                "Instruction pointer out of bounds in loop detection: {}",
+                state.ip
+            ));
+        }
+    }
+
+    Ok((true, state.accumulator))
+}
+
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash, Default)]
+/// The state of the handheld: instruction pointer and accumulator
+pub struct State {
+    pub ip: i32,
+    pub accumulator: i32,
+}
+
+/// Runs the instruction at the instruction pointer. Fails if the instruction pointer is out of
+/// bounds.
+pub fn run_instruction(program: &[Op], state: &State) -> Result<State, String> {
+    let ip = state.ip;
+    let accumulator = state.accumulator;
+    if ip < 0 || ip >= program.len() as i32 {
+        Err(format!("Instruction pointer out of bounds: {}", ip))
+    } else {
+        Ok(match program[ip as usize] {
+            Op::Acc(arg) => State {
+                ip: ip + 1,
+                accumulator: accumulator + arg,
+            },
+            Op::Jmp(arg) => State {
+                ip: ip + arg,
+                accumulator,
+            },
+            Op::Nop(_) => State {
+                ip: ip + 1,
+                accumulator,
+            },
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// An instruction with its argument
+pub enum Op {
+    Acc(i32),
+    Jmp(i32),
+    Nop(i32),
+}
+
+/// Writes the instruction as in the puzzle input, e.g. `jmp -4`
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Op::Acc(arg) => write!(f, "acc {:+}", arg),
+            Op::Jmp(arg) => write!(f, "jmp {:+}", arg),
+            Op::Nop(arg) => write!(f, "nop {:+}", arg),
+        }
+    }
+}
+
+/// Parses a program, one instruction like `jmp -4` per line.
+pub fn parse_ops(code: &str) -> Result<Vec<Op>, ParseError> {
+    code.split_terminator('\n').map(parse_op).collect()
+}
+
+fn parse_op(line: &str) -> Result<Op, ParseError> {
+    let (opcode_s, argument) = line
+        .split_once(' ')
+        .ok_or_else(|| ParseError::missing_field(end_of(line), "argument"))?;
+    let argument = parse_int::<i32>(argument)?;
+
+    match opcode_s {
+        "acc" => Ok(Op::Acc(argument)),
+        "jmp" => Ok(Op::Jmp(argument)),
+        "nop" => Ok(Op::Nop(argument)),
+        _ => Err(ParseError::unexpected_token(
+            opcode_s,
+            "an opcode (acc, jmp or nop)",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::generate::{Generator, Rng};
+    use common::ErrorKind;
+
+    #[test]
+    fn detect_loop_works_for_example() {
+        // given
+        let input = r"nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+        let program = parse_ops(input).expect("Expected program to parse");
+
+        // when
+        let result = detect_loop(&program);
+
+        // then
+        let acc = result.expect("Expected loop detection to be successful");
+        assert_eq!(acc, (true, 5));
+    }
+
+    #[test]
+    fn repair_program_works_on_example() {
+        // given
+        let input = r"nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+        let program = parse_ops(input).expect("Expected program to parse");
+
+        // when
+        let result = repair_program(&program);
+
+        // then
+        let acc = result.expect("Expected successful repair");
+        assert_eq!(acc, 8);
+    }
+
+    #[test]
+    fn repair_program_finds_same_repair_in_parallel() {
+        for seed in 0..5 {
+            // given
+            let input = Day08::generate(&mut Rng::new(seed), 300).input;
+            let program = parse_ops(&input).expect("Expected valid generated input");
+            let sequential = repair_program(&program);
+
+            // when
+            let parallel = common::parallel::with_jobs(4, || repair_program(&program));
+
+            // then
+            assert_eq!(parallel, sequential);
+        }
+    }
+
+    #[test]
+    fn parse_ops_parses_valid_ops() {
+        // given
+        let input = "acc +4\nnop -3000\njmp -2\n";
+
+        // when
+        let result = parse_ops(input);
+
+        // then
+        let ops = result.expect("Expected valid parsing");
+        assert_eq!(&ops, &[Op::Acc(4), Op::Nop(-3000), Op::Jmp(-2)]);
+    }
+
+    #[test]
+    fn parse_ops_fails_for_missing_argument() {
+        // given
+        let input = "acc\n";
+
+        // when
+        let result = parse_ops(input);
+
+        // then
+        assert_eq!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::MissingField {
+                field: "argument".to_owned()
+            })
+        )
+    }
+
+    #[test]
+    fn parse_ops_fails_for_non_int_argument() {
+        // given
+        let input = "acc bananas\n";
+
+        // when
+        let result = parse_ops(input);
+
+        // then
+        assert_eq!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::UnexpectedToken {
+                found: "bananas".to_owned(),
+                expected: "a number".to_owned()
+            })
+        )
+    }
+
+    #[test]
+    fn parse_ops_fails_for_invalid_opcode() {
+        // given
+        let input = "foo +42\n";
+
+        // when
+        let result = parse_ops(input);
+
+        // then
+        let error = result
+            .map_err(|e| e.locate(input))
+            .expect_err("Expected unknown opcode to fail");
+        assert!(matches!(error.kind(), ErrorKind::UnexpectedToken { found, .. } if found == "foo"));
+        let location = error.location().expect("Expected error to be located");
+        assert_eq!((location.line, location.column, location.length), (1, 1, 3));
+    }
+
+    #[test]
+    fn run_instruction_runs_acc_correctly() {
+        // given
+        let program = &[Op::Nop(11), Op::Acc(42)];
+        let state = State {
+            ip: 1,
+            accumulator: 11,
+        };
+
+        // when
+        let result = run_instruction(program, &state);
+
+        // then
+        let new_state = result.expect("Expected no error");
+        assert_eq!(
+            new_state,
+            State {
+                ip: 2,
+                accumulator: 53
+            }
+        );
+    }
+
+    #[test]
+    fn run_instruction_runs_jmp_correctly() {
+        // given
+        let program = &[Op::Nop(11), Op::Jmp(42)];
+        let state = State {
+            ip: 1,
+            accumulator: 11,
+        };
+
+        // when
+        let result = run_instruction(program, &state);
+
+        // then
+        let new_state = result.expect("Expected no error");
+        assert_eq!(
+            new_state,
+            State {
+                ip: 43,
+                accumulator: 11
+            }
+        );
+    }
+
+    #[test]
+    fn run_instruction_runs_nop_correctly() {
+        // given
+        let program = &[Op::Nop(11), Op::Nop(42)];
+        let state = State {
+            ip: 1,
+            accumulator: 11,
+        };
+
+        // when
+        let result = run_instruction(program, &state);
+
+        // then
+        let new_state = result.expect("Expected no error");
+        assert_eq!(
+            new_state,
+            State {
+                ip: 2,
+                accumulator: 11
+            }
+        );
+    }
+
+    #[test]
+    fn run_instruction_fails_for_invalid_ip() {
+        // given
+        let program = &[Op::Nop(42)];
+        let state = State {
+            ip: -1,
+            accumulator: 0,
+        };
+
+        // when
+        let result = run_instruction(program, &state);
+
+        // then
+        assert_eq!(
+            result,
+            Err("Instruction pointer out of bounds: -1".to_owned())
+        );
+    }
+
+    #[test]
+    fn parse_inverts_display_for_generated_ops() {
+        for seed in 0..10 {
+            // given
+            let input = Day08::generate(&mut Rng::new(seed), 50).input;
+            let ops = parse_ops(&input).expect("Expected valid generated input");
+
+            // when
+            let written: String = ops.iter().map(|op| format!("{}\n", op)).collect();
+            let reparsed = parse_ops(&written);
+
+            // then
+            assert_eq!(reparsed, Ok(ops));
+        }
+    }
+}
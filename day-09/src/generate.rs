@@ -0,0 +1,116 @@
+use crate::{Day09, PREAMBLE_LENGTH};
+use common::generate::{Generated, Generator, Rng};
+
+/// `size` is the number of data points (30 to 1000, the numbers grow quickly). Each number after
+/// the preamble is the sum of two of the numbers before it, except for the planted invalid number
+/// near the end. It is the sum of a contiguous range of earlier numbers, which is the weakness.
+impl Generator for Day09 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.clamp(30, 1000);
+        loop {
+            if let Some(generated) = try_generate(rng, size) {
+                return generated;
+            }
+        }
+    }
+}
+
+// fails if the planted range does not work out, e.g. because there is a second range with the
+// same sum
+fn try_generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+    let mut data: Vec<u64> = (1..=50).collect();
+    rng.shuffle(&mut data);
+    data.truncate(PREAMBLE_LENGTH);
+
+    let invalid_index = size - 1 - rng.index((size - PREAMBLE_LENGTH) / 4 + 1);
+    while data.len() < invalid_index {
+        let window = &data[data.len() - PREAMBLE_LENGTH..];
+        let a = *rng.choose(window);
+        let b = *rng.choose(window);
+        if a != b {
+            data.push(a + b);
+        }
+    }
+
+    let length = rng.between(2, 17) as usize;
+    let start = rng.index(invalid_index - length);
+    let weakness = &data[start..start + length];
+    let invalid = weakness.iter().sum::<u64>();
+    let min = *weakness.iter().min()?;
+    let max = *weakness.iter().max()?;
+    let window = &data[invalid_index - PREAMBLE_LENGTH..];
+    if is_sum_of_two(window, invalid) {
+        return None;
+    }
+    data.push(invalid);
+
+    while data.len() < size {
+        let window = &data[data.len() - PREAMBLE_LENGTH..];
+        let a = *rng.choose(window);
+        let b = *rng.choose(window);
+        if a != b {
+            data.push(a + b);
+        }
+    }
+    if count_ranges_with_sum(&data, invalid) != 1 {
+        return None;
+    }
+
+    Some(Generated {
+        input: data.iter().map(|n| format!("{}\n", n)).collect(),
+        part1: Some(invalid.to_string()),
+        part2: Some((min + max).to_string()),
+    })
+}
+
+fn is_sum_of_two(window: &[u64], number: u64) -> bool {
+    (0..window.len()).any(|a| ((a + 1)..window.len()).any(|b| window[a] + window[b] == number))
+}
+
+// contiguous ranges of at least two numbers
+fn count_ranges_with_sum(data: &[u64], sum: u64) -> usize {
+    let mut count = 0;
+    for start in 0..data.len() {
+        let mut range_sum = data[start];
+        for n in &data[start + 1..] {
+            range_sum += n;
+            if range_sum == sum {
+                count += 1;
+            }
+            if range_sum >= sum {
+                break;
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day09::generate(&mut Rng::new(seed), 200);
+
+            // when
+            let mismatches = check_planted::<Day09>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day09>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
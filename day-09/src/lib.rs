@@ -0,0 +1,221 @@
+//! Day 9: Encoding Error
+
+use common::{parse_int, Params, ParseError, Solution};
+
+mod generate;
+
+pub struct Day09;
+
+/// How many numbers before a number can be summed up to it, unless `--preamble` says otherwise
+pub const PREAMBLE_LENGTH: usize = 25;
+
+/// The data and the length of its preamble
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Xmas {
+    pub data: Vec<u64>,
+    pub preamble_length: usize,
+}
+
+impl Solution for Day09 {
+    type Input<'a> = Xmas;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    /// `preamble` is the length of the preamble (default: 25, the examples use 5)
+    const PARAMS: &'static [&'static str] = &["preamble"];
+
+    fn parse(input: &str) -> Result<Xmas, ParseError> {
+        Self::parse_with_params(input, &Params::new())
+    }
+
+    fn parse_with_params(input: &str, params: &Params) -> Result<Xmas, ParseError> {
+        let preamble_length = params.get_int("preamble", PREAMBLE_LENGTH)?;
+        if preamble_length < 2 {
+            let raw = params.get_str("preamble").unwrap_or_default();
+            return Err(ParseError::unexpected_token(
+                raw,
+                "a preamble of at least 2 numbers",
+            ));
+        }
+        Ok(Xmas {
+            data: parse_input(input)?,
+            preamble_length,
+        })
+    }
+
+    fn part1(xmas: &Xmas) -> Result<u64, String> {
+        find_first_invalid_number(&xmas.data, xmas.preamble_length)
+            .ok_or_else(|| "There is no invalid number.".to_owned())
+    }
+
+    fn part2(xmas: &Xmas) -> Result<u64, String> {
+        let invalid_number = Self::part1(xmas)?;
+        find_weakness(&xmas.data, invalid_number).ok_or_else(|| "Found no weakness".to_owned())
+    }
+}
+
+/// Finds the first number that is not the sum of two of the `preamble_length` numbers before it.
+pub fn find_first_invalid_number(data: &[u64], preamble_length: usize) -> Option<u64> {
+    for window in data.windows(preamble_length + 1) {
+        if !number_valid(&window[0..preamble_length], window[preamble_length]) {
+            return Some(window[preamble_length]);
+        }
+    }
+    None
+}
+
+// for this, brute forcing should be a viable option
+fn number_valid(preamble: &[u64], number: u64) -> bool {
+    for a in 0..(preamble.len() - 1) {
+        for b in (a + 1)..preamble.len() {
+            if preamble[a].checked_add(preamble[b]) == Some(number) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn cum_sum(data: &[u64]) -> Vec<u64> {
+    data.iter()
+        .fold((Vec::with_capacity(data.len()), 0), |(mut v, sum), num| {
+            v.push(sum + num);
+            (v, sum + num)
+        })
+        .0
+}
+
+/// Finds a contiguous range of at least two numbers that sum up to `invalid_number` and returns
+/// the sum of its smallest and largest number.
+pub fn find_weakness(data: &[u64], invalid_number: u64) -> Option<u64> {
+    if data.len() < 3 {
+        return None;
+    }
+    let data_sum = cum_sum(data);
+    // You know what, let's brute force the rest again
+    for window_len in 2..data_sum.len() {
+        if data_sum[window_len - 1] == invalid_number {
+            let min = data[0..(window_len)].iter().min()?;
+            let max = data[0..(window_len)].iter().max()?;
+            return Some(min + max);
+        }
+        // the range from lower + 1 to upper, both inclusive
+        for lower in 0..(data_sum.len() - window_len) {
+            let upper = lower + window_len;
+            if data_sum[upper] - data_sum[lower] == invalid_number {
+                let min = data[(lower + 1)..(upper + 1)].iter().min()?;
+                let max = data[(lower + 1)..(upper + 1)].iter().max()?;
+                return Some(min + max);
+            }
+        }
+    }
+    None
+}
+
+/// Parses the data, one number per line.
+pub fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
+    input.split_terminator('\n').map(parse_int).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cum_sum_calculates_cumulative_sum() {
+        // given
+        let data = [1, 2, 3, 4];
+
+        // when
+        let sum = cum_sum(&data);
+
+        // then
+        assert_eq!(&sum, &[1, 3, 6, 10])
+    }
+
+    #[test]
+    fn find_first_invalid_number_works_for_example() {
+        // given
+        let data = [
+            35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309,
+            576,
+        ];
+        let preamble_length = 5;
+
+        // when
+        let result = find_first_invalid_number(&data, preamble_length);
+
+        // then
+        assert_eq!(result, Some(127));
+    }
+
+    #[test]
+    fn preamble_is_taken_from_params() {
+        // given
+        let input = "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n";
+        let mut params = Params::new();
+        params.insert("preamble", "5");
+
+        // when
+        let xmas = Day09::parse_with_params(input, &params).expect("Expected valid input");
+
+        // then
+        assert_eq!(xmas.preamble_length, 5);
+        assert_eq!(Day09::part1(&xmas), Ok(127));
+        assert_eq!(Day09::part2(&xmas), Ok(62));
+    }
+
+    #[test]
+    fn parse_with_params_rejects_short_preamble() {
+        // given
+        let mut params = Params::new();
+        params.insert("preamble", "1");
+
+        // when
+        let result = Day09::parse_with_params("1\n2\n3\n", &params);
+
+        // then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn find_weakness_works_for_example() {
+        // given
+        let data = [
+            35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309,
+            576,
+        ];
+        let invalid_number = 127;
+
+        // when
+        let result = find_weakness(&data, invalid_number);
+
+        // then
+        assert_eq!(result, Some(62))
+    }
+
+    #[test]
+    fn find_weakness_only_looks_at_numbers_in_range() {
+        // given
+        let data = [10, 1, 2, 3, 100];
+
+        // when
+        let result = find_weakness(&data, 5);
+
+        // then
+        assert_eq!(result, Some(5))
+    }
+
+    #[test]
+    fn find_weakness_works_for_end_to_end_example() {
+        // given
+        let data = [1, 2, 3];
+        let invalid_number = 3;
+
+        // when
+        let result = find_weakness(&data, invalid_number);
+
+        // then
+        assert_eq!(result, Some(3));
+    }
+}
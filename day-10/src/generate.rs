@@ -0,0 +1,71 @@
+use crate::Day10;
+use common::generate::{Generated, Generator, Rng};
+
+// the number of ways to arrange a run of n adapters with a difference of 1 jolt each
+const RUN_ARRANGEMENTS: [u64; 5] = [1, 1, 2, 4, 7];
+
+/// `size` is the number of adapters. Like in the real puzzle, the differences are runs of up to
+/// four 1-jolt steps separated by 3-jolt steps, so the number of arrangements is the product of
+/// the arrangements of each run. Once that product gets close to overflowing, the runs get shorter.
+impl Generator for Day10 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut adapters: Vec<u64> = Vec::with_capacity(size);
+        let mut joltage = 0;
+        let mut one_steps = 0;
+        // the device is always 3 jolts above the last adapter
+        let mut three_steps = 1;
+        let mut arrangements: u64 = 1;
+        while adapters.len() < size {
+            if !adapters.is_empty() {
+                joltage += 3;
+                three_steps += 1;
+                adapters.push(joltage);
+            }
+            let max_run = if arrangements > u64::MAX / 8 { 1 } else { 4 };
+            let run = rng.index(max_run + 1).min(size - adapters.len());
+            for _ in 0..run {
+                joltage += 1;
+                adapters.push(joltage);
+            }
+            one_steps += run;
+            arrangements *= RUN_ARRANGEMENTS[run];
+        }
+        rng.shuffle(&mut adapters);
+
+        Generated {
+            input: adapters.iter().map(|a| format!("{}\n", a)).collect(),
+            part1: Some((one_steps * three_steps).to_string()),
+            part2: Some(arrangements.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day10::generate(&mut Rng::new(seed), 100);
+
+            // when
+            let mismatches = check_planted::<Day10>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day10>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,179 @@
+//! Day 10: Adapter Array
+
+use common::num::{self, Arithmetic, BigInt, Int, Number};
+use common::{parse_int, Params, ParseError, Solution};
+use std::cmp::min;
+
+mod generate;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    // sorted adapters, including the outlet and the device, and how to count their combinations
+    type Input<'a> = (Vec<u64>, Arithmetic);
+    type Answer1 = usize;
+    type Answer2 = Number<u64>;
+
+    const PARAMS: &'static [&'static str] = &[num::ARITHMETIC];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Self::parse_with_params(input, &Params::new())
+    }
+
+    fn parse_with_params<'a>(
+        input: &'a str,
+        params: &Params,
+    ) -> Result<Self::Input<'a>, ParseError> {
+        Ok((parse_input(input)?, Arithmetic::from_params(params)?))
+    }
+
+    fn part1((adapters, _): &Self::Input<'_>) -> Result<usize, String> {
+        solve_adapter_chain(adapters).ok_or_else(|| "no valid adapter chain".to_owned())
+    }
+
+    fn part2((adapters, arithmetic): &Self::Input<'_>) -> Result<Number<u64>, String> {
+        match arithmetic {
+            Arithmetic::Checked => count_adapter_combinations(adapters).map(Number::Fixed),
+            Arithmetic::Big => count_adapter_combinations::<BigInt>(adapters).map(Number::Big),
+        }
+    }
+}
+
+/// Counts the ways to arrange the adapters so that they connect the outlet to the device. Fails if
+/// the count does not fit into `N`.
+pub fn count_adapter_combinations<N: Int>(sorted_adapters: &[u64]) -> Result<N, String> {
+    if sorted_adapters.is_empty() {
+        return num::int(0, "zero");
+    }
+    let mut combinations: Vec<N> = Vec::with_capacity(sorted_adapters.len());
+    combinations.push(num::int(1, "one")?);
+    for i in 1..sorted_adapters.len() {
+        let mut count = num::int(0, "zero")?;
+        for j in (i - min(i, 3))..i {
+            if sorted_adapters[i] - sorted_adapters[j] < 4 {
+                count = num::add(&count, &combinations[j], "count of adapter combinations")?;
+            }
+        }
+        combinations.push(count);
+    }
+    Ok(combinations[combinations.len() - 1].clone())
+}
+
+/// Multiplies the number of 1-jolt differences with the number of 3-jolt differences of the
+/// chain using all adapters. `None` if there is a gap of more than 3 jolts.
+pub fn solve_adapter_chain(sorted_adapters: &[u64]) -> Option<usize> {
+    let differences: Vec<u64> = sorted_adapters.windows(2).map(|w| w[1] - w[0]).collect();
+
+    if differences.iter().any(|d| *d > 3) {
+        return None;
+    }
+    let one_differences = differences.iter().filter(|d| **d == 1).count();
+    let three_differences = differences.iter().filter(|d| **d == 3).count();
+
+    Some(one_differences * three_differences)
+}
+
+/// Parses the joltage ratings and adds the outlet (0) and the device (max + 3). The result is
+/// sorted.
+pub fn parse_input(content: &str) -> Result<Vec<u64>, ParseError> {
+    let mut adapters = content
+        .split_terminator('\n')
+        .map(|line| {
+            let rating: u64 = parse_int(line)?;
+            if rating > u64::MAX - 3 {
+                // the device is rated 3 jolts higher than the highest adapter
+                return Err(ParseError::unexpected_token(
+                    line,
+                    "a joltage rating that leaves room for the device",
+                ));
+            }
+            Ok(rating)
+        })
+        .collect::<Result<Vec<u64>, ParseError>>()?;
+
+    let max_adapter = *adapters
+        .iter()
+        .max()
+        .ok_or_else(|| ParseError::missing_field(content, "joltage ratings"))?;
+    adapters.push(0);
+    adapters.push(max_adapter + 3);
+    adapters.sort_unstable();
+
+    Ok(adapters)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn solve_adapter_chain_works_for_example_1() {
+        // given
+        let adapters =
+            parse_input("16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4\n").expect("Expected valid input");
+
+        // when
+        let result = solve_adapter_chain(&adapters);
+
+        // then
+        assert_eq!(result, Some(35));
+    }
+
+    #[test]
+    fn solve_adapter_chain_works_for_example_2() {
+        // given
+        let adapters = parse_input(
+            "28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38\n39\n11\n1\n32\n25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3\n").expect("Expected valid input");
+
+        // when
+        let result = solve_adapter_chain(&adapters);
+
+        // then
+        assert_eq!(result, Some(220));
+    }
+
+    #[test]
+    fn count_adapter_combinations_works_for_example_1() {
+        // given
+        let adapters =
+            parse_input("16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4\n").expect("Expected valid input");
+
+        // when
+        let result = count_adapter_combinations::<u64>(&adapters);
+
+        // then
+        assert_eq!(result, Ok(8));
+    }
+
+    #[test]
+    fn count_adapter_combinations_works_for_example_2() {
+        // given
+        let adapters = parse_input(
+            "28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38\n39\n11\n1\n32\n25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3\n").expect("Expected valid input");
+
+        // when
+        let result = count_adapter_combinations::<u64>(&adapters);
+
+        // then
+        assert_eq!(result, Ok(19208));
+    }
+
+    #[test]
+    fn count_adapter_combinations_needs_big_integers_for_long_chains() {
+        // given
+        let input: String = (1..=100).map(|n| format!("{}\n", n)).collect();
+        let adapters = parse_input(&input).expect("Expected valid input");
+
+        // when
+        let checked = count_adapter_combinations::<u64>(&adapters);
+        let big = count_adapter_combinations::<BigInt>(&adapters);
+
+        // then
+        let error = checked.expect_err("Expected the count to overflow");
+        assert!(error.starts_with("Overflow in count of adapter combinations"));
+        assert_eq!(
+            big.map(|count| count.to_string()),
+            Ok("180396380815100901214157639".to_owned())
+        );
+    }
+}
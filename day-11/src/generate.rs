@@ -0,0 +1,54 @@
+use crate::Day11;
+use common::generate::{Generated, Generator, Rng};
+
+/// `size` is the width and height of the seat layout (at least 1). About a quarter of the cells
+/// are floor, all seats start empty. The answers are not planted, the generated layouts are only
+/// good for checking that the solution terminates and for benchmarks.
+impl Generator for Day11 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.max(1);
+        let mut input = String::with_capacity(size * (size + 1));
+        for _ in 0..size {
+            for _ in 0..size {
+                input.push(if rng.chance(0.25) { '.' } else { 'L' });
+            }
+            input.push('\n');
+        }
+
+        Generated {
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_can_be_solved() {
+        for seed in 0..10 {
+            // given
+            let generated = Day11::generate(&mut Rng::new(seed), 20);
+
+            // when
+            let mismatches = check_planted::<Day11>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day11>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
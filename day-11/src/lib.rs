@@ -0,0 +1,323 @@
+//! Day 11: Seating System
+
+use common::automaton::{Automaton, LineOfSight, Rule, Square, Topology};
+use common::grid::{CharCell, Grid, NEIGHBOURS8};
+use common::{ParseError, Solution};
+
+mod generate;
+mod trace;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Ferry;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Ferry, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(ferry: &Ferry) -> Result<usize, String> {
+        seats_at_equilibrium(ferry)
+    }
+
+    fn part2(ferry: &Ferry) -> Result<usize, String> {
+        seats_at_equilibrium_line_of_sight(ferry)
+    }
+}
+
+/// How many rounds to wait for the seats to settle before giving up
+pub const MAX_ROUNDS: usize = 10_000;
+
+/// Applies the adjacent seats rules until nothing changes and counts the occupied seats.
+pub fn seats_at_equilibrium(ferry: &Ferry) -> Result<usize, String> {
+    settle(seating(ferry, adjacent_seats(ferry), 4))
+}
+
+/// Applies the line of sight rules until nothing changes and counts the occupied seats.
+pub fn seats_at_equilibrium_line_of_sight(ferry: &Ferry) -> Result<usize, String> {
+    settle(seating(ferry, seats_in_sight(ferry), 5))
+}
+
+fn settle<T: Topology<Pos = (usize, usize)>>(
+    mut seats: Automaton<T, Cell, Seating>,
+) -> Result<usize, String> {
+    seats
+        .run_to_fixed_point(MAX_ROUNDS)
+        .ok_or_else(|| format!("The seats did not settle within {} rounds", MAX_ROUNDS))?;
+    Ok(seats.count(&Cell::Occupied))
+}
+
+/// One round of seating with the adjacent seats rules
+pub fn next_gen(ferry: &Ferry) -> Ferry {
+    next_gen_with(ferry, seating(ferry, adjacent_seats(ferry), 4))
+}
+
+/// One round of seating with the line of sight rules
+pub fn next_gen_line_of_sight(ferry: &Ferry) -> Ferry {
+    next_gen_with(ferry, seating(ferry, seats_in_sight(ferry), 5))
+}
+
+fn next_gen_with<T: Topology<Pos = (usize, usize)>>(
+    ferry: &Ferry,
+    mut seats: Automaton<T, Cell, Seating>,
+) -> Ferry {
+    seats.step();
+    to_ferry(ferry, &seats)
+}
+
+/// The current state of the seats, on a ferry the size of the given one
+pub fn to_ferry<T: Topology<Pos = (usize, usize)>>(
+    ferry: &Ferry,
+    seats: &Automaton<T, Cell, Seating>,
+) -> Ferry {
+    Grid::from_fn(ferry.width(), ferry.height(), |x, y| *seats.get((x, y)))
+}
+
+/// The seats of the ferry as a cellular automaton. The floor never changes, so it is the
+/// background.
+pub fn seating<T: Topology<Pos = (usize, usize)>>(
+    ferry: &Ferry,
+    topology: T,
+    tolerance: usize,
+) -> Automaton<T, Cell, Seating> {
+    Automaton::new(
+        topology,
+        Seating { tolerance },
+        Cell::Floor,
+        ferry.positions().map(|pos| (pos, ferry[pos])),
+    )
+}
+
+/// The neighbours of a seat are the eight cells around it
+pub fn adjacent_seats(ferry: &Ferry) -> Square {
+    Square {
+        width: ferry.width(),
+        height: ferry.height(),
+    }
+}
+
+/// The neighbours of a seat are the first seats in each of the eight directions
+pub fn seats_in_sight(ferry: &Ferry) -> LineOfSight {
+    LineOfSight::new(ferry, |cell| *cell == Cell::Floor)
+}
+
+/// An empty seat is taken if none of its neighbours is occupied, an occupied seat is left if at
+/// least `tolerance` of its neighbours are occupied.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Seating {
+    pub tolerance: usize,
+}
+
+impl Rule<Cell> for Seating {
+    fn next(&self, cell: &Cell, neighbours: &[Cell]) -> Cell {
+        let occupied = neighbours.iter().filter(|n| **n == Cell::Occupied).count();
+        match cell {
+            Cell::Occupied if occupied >= self.tolerance => Cell::Seat,
+            Cell::Seat if occupied == 0 => Cell::Occupied,
+            _ => *cell,
+        }
+    }
+
+    fn changes_background(&self) -> bool {
+        false
+    }
+}
+
+/// Counts the occupied seats that can be seen from (x, y).
+pub fn count_occupied_seats_in_sight(ferry: &Ferry, x: usize, y: usize) -> usize {
+    NEIGHBOURS8
+        .iter()
+        .filter(|(dx, dy)| {
+            ferry.ray(x, y, *dx, *dy).find(|cell| **cell != Cell::Floor) == Some(&Cell::Occupied)
+        })
+        .count()
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub enum Cell {
+    Floor,
+    Seat,
+    Occupied,
+}
+
+impl CharCell for Cell {
+    const EXPECTED: &'static str = "a seat ('L', '#') or floor ('.')";
+
+    fn from_char(c: char) -> Option<Cell> {
+        match c {
+            '.' => Some(Cell::Floor),
+            'L' => Some(Cell::Seat),
+            '#' => Some(Cell::Occupied),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Cell::Floor => '.',
+            Cell::Seat => 'L',
+            Cell::Occupied => '#',
+        }
+    }
+}
+
+/// The seat layout
+pub type Ferry = Grid<Cell>;
+
+/// Parses the seat layout, `L` is an empty seat, `#` an occupied seat and `.` floor.
+pub fn parse_input(input: &str) -> Result<Ferry, ParseError> {
+    Grid::parse(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn next_gen_works_for_example() {
+        // given
+        let initial = parse_input(
+            r"#.LL.L#.##
+#LLLLLL.L#
+L.L.L..L..
+#LLL.LL.L#
+#.LL.LL.LL
+#.LLLL#.##
+..L.L.....
+#LLLLLLLL#
+#.LLLLLL.L
+#.#LLLL.##",
+        )
+        .expect("Expected initial state to be parseable");
+        let expected = parse_input(
+            r"#.##.L#.##
+#L###LL.L#
+L.#.#..#..
+#L##.##.L#
+#.##.LL.LL
+#.###L#.##
+..#.#.....
+#L######L#
+#.LL###L.L
+#.#L###.##",
+        )
+        .expect("Expected next generation state to be parseable");
+
+        // when
+        let result = next_gen(&initial);
+
+        // then
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn seats_at_equilibrium_works_for_example() {
+        // given
+        let initial = parse_input(
+            r"L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL",
+        )
+        .expect("Expected initial state to be parseable");
+
+        // when
+        let result = seats_at_equilibrium(&initial);
+
+        // then
+        assert_eq!(result, Ok(37));
+    }
+
+    #[test]
+    fn count_occupied_seats_in_sight_works_for_example_1() {
+        // given
+        let ferry = parse_input(
+            r".......#.
+...#.....
+.#.......
+.........
+..#L....#
+....#....
+.........
+#........
+...#.....",
+        )
+        .expect("Expected initial state to be parseable");
+
+        // when
+        let count = count_occupied_seats_in_sight(&ferry, 3, 4);
+
+        // then
+        assert_eq!(count, 8);
+    }
+
+    #[test]
+    fn count_occupied_seats_in_sight_works_for_example_2() {
+        // given
+        let ferry = parse_input(
+            r".............
+.L.L.#.#.#.#.
+.............",
+        )
+        .expect("Expected initial state to be parseable");
+
+        // when
+        let count = count_occupied_seats_in_sight(&ferry, 1, 1);
+
+        // then
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn count_occupied_seats_in_sight_works_for_example_3() {
+        // given
+        let ferry = parse_input(
+            r".##.##.
+#.#.#.#
+##...##
+...L...
+##...##
+#.#.#.#
+.##.##.",
+        )
+        .expect("Expected initial state to be parseable");
+
+        // when
+        let count = count_occupied_seats_in_sight(&ferry, 3, 3);
+
+        // then
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn seats_at_equilibrium_line_of_sight_works_for_example() {
+        // given
+        let initial = parse_input(
+            r"L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL",
+        )
+        .expect("Expected initial state to be parseable");
+
+        // when
+        let result = seats_at_equilibrium_line_of_sight(&initial);
+
+        // then
+        assert_eq!(result, Ok(26));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    adjacent_seats, seating, seats_in_sight, to_ferry, Cell, Day11, Ferry, Seating, MAX_ROUNDS,
+};
+use common::automaton::{Automaton, Topology};
+use common::trace::{Frame, Picture, Trace};
+
+/// One frame per round until the seats settle
+impl Trace for Day11 {
+    fn trace(ferry: &Ferry, part: usize) -> Result<Vec<Frame>, String> {
+        if part == 1 {
+            trace_seats(ferry, seating(ferry, adjacent_seats(ferry), 4))
+        } else {
+            trace_seats(ferry, seating(ferry, seats_in_sight(ferry), 5))
+        }
+    }
+}
+
+fn trace_seats<T: Topology<Pos = (usize, usize)>>(
+    ferry: &Ferry,
+    mut seats: Automaton<T, Cell, Seating>,
+) -> Result<Vec<Frame>, String> {
+    let mut frames: Vec<Frame> = Vec::new();
+    loop {
+        frames.push(Frame::new(
+            format!("round {}", seats.generation()),
+            Picture::map(&to_ferry(ferry, &seats)),
+        ));
+        if !seats.step() {
+            return Ok(frames);
+        }
+        if seats.generation() > MAX_ROUNDS {
+            return Err(format!(
+                "The seats did not settle within {} rounds",
+                MAX_ROUNDS
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn trace_ends_with_settled_seats() {
+        // given
+        let ferry = parse_input("L.LL\nLLLL\nL.LL\n").expect("Expected valid input");
+
+        // when
+        let frames = Day11::trace(&ferry, 1).expect("Expected seats to settle");
+
+        // then
+        let settled = parse_input("#.L#\n#LLL\n#.L#\n").expect("Expected valid settled state");
+        assert_eq!(frames[0].picture, Picture::map(&ferry));
+        assert_eq!(frames[0].label, "round 0");
+        assert_eq!(
+            frames.last().map(|frame| &frame.picture),
+            Some(&Picture::map(&settled))
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use crate::Day12;
+use common::generate::{Generated, Generator, Rng};
+
+/// `size` is the number of navigation instructions. The planted answers come from following the
+/// instructions with direction vectors instead of the quarter turn counting of the solution.
+impl Generator for Day12 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut input = String::with_capacity(size * 4);
+        let mut ship = (0, 0);
+        let mut heading = (1, 0);
+        let mut ship2 = (0, 0);
+        let mut waypoint = (10, 1);
+        for _ in 0..size {
+            let action = *rng.choose(&['N', 'S', 'E', 'W', 'L', 'R', 'F', 'F']);
+            let value = match action {
+                'L' | 'R' => 90 * rng.between(1, 3),
+                _ => rng.between(1, 100),
+            };
+            input.push_str(&format!("{}{}\n", action, value));
+
+            let (dx, dy) = match action {
+                'N' => (0, value),
+                'S' => (0, -value),
+                'E' => (value, 0),
+                'W' => (-value, 0),
+                _ => (0, 0),
+            };
+            match action {
+                'L' | 'R' => {
+                    for _ in 0..value / 90 {
+                        heading = turn(heading, action);
+                        waypoint = turn(waypoint, action);
+                    }
+                }
+                'F' => {
+                    ship = (ship.0 + value * heading.0, ship.1 + value * heading.1);
+                    ship2 = (ship2.0 + value * waypoint.0, ship2.1 + value * waypoint.1);
+                }
+                _ => {
+                    ship = (ship.0 + dx, ship.1 + dy);
+                    waypoint = (waypoint.0 + dx, waypoint.1 + dy);
+                }
+            }
+        }
+
+        Generated {
+            input,
+            part1: Some((ship.0.abs() + ship.1.abs()).to_string()),
+            part2: Some((ship2.0.abs() + ship2.1.abs()).to_string()),
+        }
+    }
+}
+
+// turns an (east, north) vector by 90 degrees to the left or right
+fn turn((east, north): (i64, i64), direction: char) -> (i64, i64) {
+    if direction == 'L' {
+        (-north, east)
+    } else {
+        (north, -east)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day12::generate(&mut Rng::new(seed), 100);
+
+            // when
+            let mismatches = check_planted::<Day12>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day12>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,233 @@
+//! Day 12: Rain Risk
+
+use common::{parse_int, ParseError, Solution};
+use std::fmt;
+
+mod generate;
+mod trace;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = Vec<Action>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Action>, ParseError> {
+        parse_actions(input)
+    }
+
+    fn part1(actions: &Vec<Action>) -> Result<i64, String> {
+        let (north, east) = run_instructions(actions);
+        Ok(north.abs() + east.abs())
+    }
+
+    fn part2(actions: &Vec<Action>) -> Result<i64, String> {
+        let (north, east) = run_instructions_waypoint(actions);
+        Ok(north.abs() + east.abs())
+    }
+}
+
+/// Navigates by moving the waypoint and returns the final (north, east) position of the ship.
+pub fn run_instructions_waypoint(actions: &[Action]) -> (i64, i64) {
+    run_instructions_waypoint_with(actions, |_, _| ())
+}
+
+/// Like `run_instructions_waypoint`, calls `on_action` with each action and the (north, east)
+/// position of the ship after it.
+pub fn run_instructions_waypoint_with(
+    actions: &[Action],
+    mut on_action: impl FnMut(&Action, (i64, i64)),
+) -> (i64, i64) {
+    let mut ship_north = 0;
+    let mut ship_east = 0;
+    let mut wp_north = 1;
+    let mut wp_east = 10;
+
+    for action in actions {
+        match action {
+            Action::Ver(v) => {
+                wp_north += v;
+            }
+            Action::Hor(v) => {
+                wp_east += v;
+            }
+            Action::Rot(v) => {
+                for _ in 0..v.rem_euclid(4) {
+                    let old_east = wp_east;
+                    wp_east = -wp_north;
+                    wp_north = old_east;
+                }
+            }
+            Action::Forward(v) => {
+                ship_north += v * wp_north;
+                ship_east += v * wp_east;
+            }
+        };
+        on_action(action, (ship_north, ship_east));
+    }
+
+    (ship_north, ship_east)
+}
+
+/// Navigates by moving the ship and returns its final (north, east) position.
+pub fn run_instructions(actions: &[Action]) -> (i64, i64) {
+    run_instructions_with(actions, |_, _| ())
+}
+
+/// Like `run_instructions`, calls `on_action` with each action and the (north, east) position of
+/// the ship after it.
+pub fn run_instructions_with(
+    actions: &[Action],
+    mut on_action: impl FnMut(&Action, (i64, i64)),
+) -> (i64, i64) {
+    let mut north = 0;
+    let mut east = 0;
+    let mut dir = 0;
+
+    for action in actions {
+        match action {
+            Action::Ver(v) => {
+                north += v;
+            }
+            Action::Hor(v) => {
+                east += v;
+            }
+            Action::Rot(v) => {
+                dir = (dir + v).rem_euclid(4);
+            }
+            Action::Forward(v) => match dir.rem_euclid(4) {
+                0 => {
+                    east += v;
+                }
+                1 => {
+                    north += v;
+                }
+                2 => {
+                    east -= v;
+                }
+                3 => {
+                    north -= v;
+                }
+                _ => panic!("Unknown direction"),
+            },
+        }
+        on_action(action, (north, east));
+    }
+
+    (north, east)
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A navigation instruction. Directions are normalized: `Hor` is east (negative for west),
+/// `Ver` is north (negative for south) and `Rot` is the counterclockwise rotation in quarter turns.
+pub enum Action {
+    Hor(i64),
+    Ver(i64),
+    Forward(i64),
+    Rot(i64),
+}
+
+/// Writes the action as in the puzzle input. Negative values become the opposite direction, e.g.
+/// `Hor(-3)` is `W3` and `Rot(-1)` is `R90`.
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Action::Hor(v) if v < 0 => write!(f, "W{}", -v),
+            Action::Hor(v) => write!(f, "E{}", v),
+            Action::Ver(v) if v < 0 => write!(f, "S{}", -v),
+            Action::Ver(v) => write!(f, "N{}", v),
+            Action::Forward(v) => write!(f, "F{}", v),
+            Action::Rot(v) if v < 0 => write!(f, "R{}", -v * 90),
+            Action::Rot(v) => write!(f, "L{}", v * 90),
+        }
+    }
+}
+
+/// Parses the navigation instructions, one like `F10` per line.
+pub fn parse_actions(content: &str) -> Result<Vec<Action>, ParseError> {
+    content.split_terminator('\n').map(parse_action).collect()
+}
+
+fn parse_action(input: &str) -> Result<Action, ParseError> {
+    let action_short = input
+        .chars()
+        .next()
+        .ok_or_else(|| ParseError::missing_field(input, "action"))?;
+    let (action_s, value_s) = input.split_at(action_short.len_utf8());
+    if !action_short.is_ascii() {
+        return Err(ParseError::unexpected_token(action_s, "an ascii action"));
+    }
+    let value: i64 = parse_int(value_s)?;
+
+    Ok(match action_short {
+        'N' => Action::Ver(value),
+        'S' => Action::Ver(-value),
+        'E' => Action::Hor(value),
+        'W' => Action::Hor(-value),
+        'L' => {
+            if value % 90 != 0 {
+                return Err(ParseError::unexpected_token(
+                    value_s,
+                    "a rotation by a multiple of 90",
+                ));
+            }
+            Action::Rot(value / 90)
+        }
+        'R' => {
+            if value % 90 != 0 {
+                return Err(ParseError::unexpected_token(
+                    value_s,
+                    "a rotation by a multiple of 90",
+                ));
+            }
+            Action::Rot(-(value / 90))
+        }
+        'F' => Action::Forward(value),
+        _ => {
+            return Err(ParseError::unexpected_token(
+                action_s,
+                "an action (N, S, E, W, L, R or F)",
+            ));
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::generate::{Generator, Rng};
+
+    #[test]
+    fn run_instructions_waypoint_works_for_example() {
+        // given
+        let instructions =
+            parse_actions("F10\nN3\nF7\nR90\nF11").expect("Expected valid example data");
+
+        // when
+        let (north, east) = run_instructions_waypoint(&instructions);
+
+        // then
+        assert_eq!(east, 214);
+        assert_eq!(north, -72);
+    }
+
+    #[test]
+    fn parse_inverts_display_for_generated_actions() {
+        for seed in 0..10 {
+            // given
+            let input = Day12::generate(&mut Rng::new(seed), 50).input;
+            let actions = parse_actions(&input).expect("Expected valid generated input");
+
+            // when
+            let written: String = actions
+                .iter()
+                .map(|action| format!("{}\n", action))
+                .collect();
+            let reparsed = parse_actions(&written);
+
+            // then
+            assert_eq!(reparsed, Ok(actions));
+        }
+    }
+}
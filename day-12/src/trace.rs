@@ -0,0 +1,46 @@
+use crate::{run_instructions_waypoint_with, run_instructions_with, Action, Day12};
+use common::trace::{Frame, Picture, Trace};
+
+/// One frame per action, showing the way of the ship so far with east to the right and north up
+impl Trace for Day12 {
+    fn trace(actions: &Vec<Action>, part: usize) -> Result<Vec<Frame>, String> {
+        let mut path: Vec<(i64, i64)> = vec![(0, 0)];
+        let mut frames = vec![Frame::new("start", Picture::Path(path.clone()))];
+        let on_action = |action: &Action, (north, east): (i64, i64)| {
+            path.push((east, north));
+            frames.push(Frame::new(
+                format!("action {}: {}", frames.len(), action),
+                Picture::Path(path.clone()),
+            ));
+        };
+        if part == 1 {
+            run_instructions_with(actions, on_action);
+        } else {
+            run_instructions_waypoint_with(actions, on_action);
+        }
+        Ok(frames)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_actions;
+
+    #[test]
+    fn trace_follows_the_ship() {
+        // given
+        let actions = parse_actions("F10\nN3\nF7\nR90\nF11\n").expect("Expected valid example");
+
+        // when
+        let frames = Day12::trace(&actions, 1).expect("Expected a trace");
+
+        // then
+        assert_eq!(frames.len(), 6);
+        assert_eq!(frames[1].label, "action 1: F10");
+        assert_eq!(
+            frames[5].picture,
+            Picture::Path(vec![(0, 0), (10, 0), (10, 3), (17, 3), (17, 3), (17, -8)])
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use crate::Day13;
+use common::generate::{Generated, Generator, Rng};
+
+// keeps the departure and the period of all busses of part 2 well within an u64
+const MAX_PERIOD: u64 = 100_000_000_000;
+
+/// `size` is the number of busses (at least 1). The bus IDs are distinct primes, so they are
+/// coprime as the solution expects. The generator picks the departure time of part 2 first and
+/// then places each bus at an offset that makes it depart on time, so part 2 is planted rather
+/// than solved.
+impl Generator for Day13 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let primes: Vec<u64> = (11..500).filter(|n| is_prime(*n)).collect();
+        let mut busses: Vec<u64> = Vec::with_capacity(size);
+        let mut period = 1;
+        while busses.len() < size.max(1) {
+            let candidates: Vec<u64> = primes
+                .iter()
+                .copied()
+                .filter(|p| !busses.contains(p) && period * p <= MAX_PERIOD)
+                .collect();
+            if candidates.is_empty() {
+                break;
+            }
+            let bus = *rng.choose(&candidates);
+            period *= bus;
+            busses.push(bus);
+        }
+
+        let departure = rng.below(period);
+        let mut schedule: Vec<Option<u64>> = Vec::new();
+        for bus in &busses {
+            // the bus at offset i departs at departure + i
+            let mut offset = ((bus - departure % bus) % bus) as usize;
+            while schedule.get(offset).is_some_and(|slot| slot.is_some()) {
+                offset += *bus as usize;
+            }
+            if schedule.len() <= offset {
+                schedule.resize(offset + 1, None);
+            }
+            schedule[offset] = Some(*bus);
+        }
+
+        let earliest = rng.below(1_000_000) + 1;
+        let (next_bus, wait) = schedule
+            .iter()
+            .filter_map(|bus| *bus)
+            .map(|bus| (bus, bus - earliest % bus))
+            .min_by_key(|(_, wait)| *wait)
+            .unwrap_or((0, 0));
+        let ids: Vec<String> = schedule
+            .iter()
+            .map(|bus| bus.map_or_else(|| "x".to_owned(), |id| id.to_string()))
+            .collect();
+
+        Generated {
+            input: format!("{}\n{}\n", earliest, ids.join(",")),
+            part1: Some((next_bus * wait).to_string()),
+            part2: Some(departure.to_string()),
+        }
+    }
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|k| k * k <= n)
+            .all(|k| !n.is_multiple_of(k))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day13::generate(&mut Rng::new(seed), 8);
+
+            // when
+            let mismatches = check_planted::<Day13>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day13>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
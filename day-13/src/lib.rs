@@ -0,0 +1,259 @@
+//! Day 13: Shuttle Search
+
+use common::num::{self, Arithmetic, BigInt, Int, Number};
+use common::{end_of, parse_int, Params, ParseError, Solution};
+
+mod generate;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    // earliest departure timestamp, the bus IDs ("x" is `None`) and how to compute the departure
+    // of part 2
+    type Input<'a> = (u64, Vec<Option<u64>>, Arithmetic);
+    type Answer1 = u64;
+    type Answer2 = Number<u64>;
+
+    const PARAMS: &'static [&'static str] = &[num::ARITHMETIC];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Self::parse_with_params(input, &Params::new())
+    }
+
+    fn parse_with_params<'a>(
+        input: &'a str,
+        params: &Params,
+    ) -> Result<Self::Input<'a>, ParseError> {
+        let (earliest_departure, bus_ids) = parse_without(input)?;
+        Ok((
+            earliest_departure,
+            bus_ids,
+            Arithmetic::from_params(params)?,
+        ))
+    }
+
+    fn part1((earliest_departure, bus_ids, _): &Self::Input<'_>) -> Result<u64, String> {
+        let (next_bus_id, next_bus_timestamp) =
+            find_earliest_bus_after_timestamp(*earliest_departure, bus_ids)?
+                .ok_or_else(|| "No busses arrive at all!".to_owned())?;
+        num::mul(
+            &next_bus_id,
+            &(next_bus_timestamp - earliest_departure),
+            "bus ID times waiting time",
+        )
+    }
+
+    fn part2((_, bus_ids, arithmetic): &Self::Input<'_>) -> Result<Number<u64>, String> {
+        match arithmetic {
+            Arithmetic::Checked => find_earliest_departure_sequence(bus_ids).map(Number::Fixed),
+            Arithmetic::Big => find_earliest_departure_sequence::<BigInt>(bus_ids).map(Number::Big),
+        }
+    }
+}
+
+/// Finds the first bus departing after `timestamp`. Returns its ID and departure time, `None` if
+/// there are no busses. Fails if a departure time does not fit into an `u64`.
+pub fn find_earliest_bus_after_timestamp(
+    timestamp: u64,
+    bus_ids: &[Option<u64>],
+) -> Result<Option<(u64, u64)>, String> {
+    let departures = bus_ids
+        .iter()
+        .filter_map(|id| *id)
+        .map(|id| Ok((id, num::mul(&(timestamp / id + 1), &id, "departure time")?)))
+        .collect::<Result<Vec<(u64, u64)>, String>>()?;
+    Ok(departures.into_iter().min_by_key(|(_, ts)| *ts))
+}
+
+/// Finds the earliest timestamp at which each bus departs at its offset in the list. Only works if
+/// the bus IDs are coprime, so no ID may occur twice. Fails if the timestamp or the period of all
+/// busses does not fit into `N`.
+pub fn find_earliest_departure_sequence<N: Int>(bus_ids: &[Option<u64>]) -> Result<N, String> {
+    let bus_ids_with_offset: Vec<(u64, u64)> = bus_ids
+        .iter()
+        .enumerate()
+        .filter_map(|(i, opt_id)| opt_id.map(|id| (id, i as u64)))
+        .collect();
+    if bus_ids_with_offset.is_empty() {
+        return Err("Unable to find departure sequence".to_owned());
+    }
+    for (i, (a, _)) in bus_ids_with_offset.iter().enumerate() {
+        for (b, _) in &bus_ids_with_offset[i + 1..] {
+            if gcd(*a as i64, *b as i64).0 != 1 {
+                return Err("bus IDs are not coprime".to_owned());
+            }
+        }
+    }
+
+    // chinese remainder theorem by sieving: after each bus, `departure` is the earliest timestamp
+    // that works for the busses so far and `period` the product of their ids. Adding the period
+    // keeps the previous busses on time, and as all ids are coprime (see above) one of the next `id`
+    // candidates works for the next bus.
+    let mut departure: N = num::int(0, "zero")?;
+    let mut period: N = num::int(1, "one")?;
+    for (i, (id, offset)) in bus_ids_with_offset.iter().enumerate() {
+        while !(departure.rem_u64(*id) as u128 + (offset % id) as u128).is_multiple_of(*id as u128)
+        {
+            departure = num::add(&departure, &period, "departure time")?;
+        }
+        if i + 1 < bus_ids_with_offset.len() {
+            period = num::mul(&period, &num::int(*id, "bus ID")?, "period of the busses")?;
+        }
+    }
+    Ok(departure)
+}
+
+/// The extended euclidean algorithm: returns `(d, s, t)` with `d = gcd(a, b) = s * a + t * b`
+pub fn gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+    let (d, s, t) = gcd(b, a % b);
+    (d, t, s - (a / b) * t)
+}
+
+/// Parses the earliest departure timestamp and the bus IDs, `x` is `None`.
+pub fn parse_without(input: &str) -> Result<(u64, Vec<Option<u64>>), ParseError> {
+    let mut lines = input.split_terminator('\n');
+    let earliest_departure_string = lines
+        .next()
+        .ok_or_else(|| ParseError::missing_field(input, "earliest departure timestamp"))?;
+    let earliest_departure: u64 = parse_int(earliest_departure_string)?;
+    let bus_ids_string = lines
+        .next()
+        .ok_or_else(|| ParseError::missing_field(end_of(input), "bus IDs"))?;
+    let bus_ids = parse_bus_ids(bus_ids_string)?;
+
+    Ok((earliest_departure, bus_ids))
+}
+
+fn parse_bus_ids(line: &str) -> Result<Vec<Option<u64>>, ParseError> {
+    line.split(',')
+        .map(|s| {
+            if s == "x" {
+                Ok(None)
+            } else {
+                match parse_int::<u64>(s)? {
+                    0 => Err(ParseError::unexpected_token(s, "a bus ID greater than 0")),
+                    id => Ok(Some(id)),
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn find_earliest_bus_after_timestamp_works_for_example() {
+        // given
+        let (earliest_departure, bus_ids) =
+            parse_without("939\n7,13,x,x,59,x,31,19\n").expect("Expected valid example input");
+
+        // when
+        let result = find_earliest_bus_after_timestamp(earliest_departure, &bus_ids);
+
+        // then
+        assert_eq!(result, Ok(Some((59, 944))));
+    }
+
+    #[test]
+    fn find_earliest_departure_sequence_works_for_example() {
+        // given
+        let (_, bus_ids) =
+            parse_without("939\n7,13,x,x,59,x,31,19\n").expect("Expected valid example input");
+
+        // when
+        let result = find_earliest_departure_sequence::<u64>(&bus_ids);
+
+        // then
+        assert_eq!(result, Ok(1068781));
+    }
+
+    const MANY_IDS: [u64; 14] = [
+        101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167,
+    ];
+
+    #[test]
+    fn find_earliest_departure_sequence_reports_overflow_for_many_busses() {
+        // given
+        let bus_ids: Vec<Option<u64>> = MANY_IDS.iter().map(|id| Some(*id)).collect();
+
+        // when
+        let result = find_earliest_departure_sequence::<u64>(&bus_ids);
+
+        // then
+        assert!(result
+            .expect_err("Expected the departure to overflow")
+            .starts_with("Overflow in "));
+    }
+
+    #[test]
+    fn find_earliest_departure_sequence_solves_many_busses_with_big_integers() {
+        // given
+        let bus_ids: Vec<Option<u64>> = MANY_IDS.iter().map(|id| Some(*id)).collect();
+
+        // when
+        let result = find_earliest_departure_sequence::<BigInt>(&bus_ids);
+
+        // then
+        let departure = result.expect("Expected a departure with big integers");
+        for (offset, id) in MANY_IDS.iter().enumerate() {
+            assert_eq!((departure.rem_u64(*id) + offset as u64) % id, 0);
+        }
+    }
+
+    #[test]
+    fn find_earliest_departure_sequence_solves_coprime_ids() {
+        // given
+        let (_, bus_ids) = parse_without("939\n4,9\n").expect("Expected valid input");
+
+        // when
+        let result = find_earliest_departure_sequence::<u64>(&bus_ids);
+
+        // then
+        assert_eq!(result, Ok(8));
+    }
+
+    #[test]
+    fn find_earliest_departure_sequence_rejects_ids_that_are_not_coprime() {
+        // given
+        let (_, bus_ids) = parse_without("939\n4,x,6\n").expect("Expected valid input");
+
+        // when
+        let result = find_earliest_departure_sequence::<u64>(&bus_ids);
+
+        // then
+        assert_eq!(result, Err("bus IDs are not coprime".to_owned()));
+    }
+
+    #[test]
+    fn find_earliest_departure_sequence_rejects_repeated_ids() {
+        // given
+        let (_, bus_ids) = parse_without("939\n7,7\n").expect("Expected valid input");
+
+        // when
+        let result = find_earliest_departure_sequence::<u64>(&bus_ids);
+
+        // then
+        assert_eq!(result, Err("bus IDs are not coprime".to_owned()));
+    }
+
+    #[test]
+    fn parse_without_rejects_bus_id_0() {
+        // when
+        let result = parse_without("939\n7,0\n");
+
+        // then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn gcd_returns_correct_values() {
+        assert_eq!(gcd(8, 12), (4, -1, 1));
+        assert_eq!(gcd(4, 12), (4, 1, 0));
+        assert_eq!(gcd(3, 13), (1, -4, 1));
+    }
+}
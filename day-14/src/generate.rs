@@ -0,0 +1,99 @@
+use crate::Day14;
+use common::generate::{Generated, Generator, Rng};
+use std::collections::HashMap;
+
+// more floating bits would make part 2 write too many addresses
+const MAX_FLOATING_BITS: usize = 9;
+
+/// `size` is the number of masks, each followed by one to five memory writes. The planted answers
+/// come from applying the masks character by character and writing every floating address one by
+/// one.
+impl Generator for Day14 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut input = String::new();
+        let mut memory: HashMap<u64, u64> = HashMap::new();
+        let mut memory_v2: HashMap<u64, u64> = HashMap::new();
+        for _ in 0..size {
+            let mut mask = [b'0'; 36];
+            for bit in mask.iter_mut() {
+                *bit = *rng.choose(b"01");
+            }
+            for _ in 0..rng.between(0, MAX_FLOATING_BITS as i64) {
+                mask[rng.index(36)] = b'X';
+            }
+            let mask = String::from_utf8_lossy(&mask).into_owned();
+            input.push_str(&format!("mask = {}\n", mask));
+
+            for _ in 0..rng.between(1, 5) {
+                let address = rng.below(1 << 16);
+                let value = rng.below(1 << 36);
+                input.push_str(&format!("mem[{}] = {}\n", address, value));
+
+                memory.insert(address, apply_to_value(&mask, value));
+                for floating in floating_addresses(&mask, address) {
+                    memory_v2.insert(floating, value);
+                }
+            }
+        }
+
+        Generated {
+            input,
+            part1: Some(memory.values().sum::<u64>().to_string()),
+            part2: Some(memory_v2.values().sum::<u64>().to_string()),
+        }
+    }
+}
+
+// the mask starts with the most significant bit
+fn bits(mask: &str) -> impl Iterator<Item = (u64, char)> + '_ {
+    mask.chars().enumerate().map(|(i, c)| (1 << (35 - i), c))
+}
+
+fn apply_to_value(mask: &str, value: u64) -> u64 {
+    bits(mask).fold(value, |value, (bit, c)| match c {
+        '0' => value & !bit,
+        '1' => value | bit,
+        _ => value,
+    })
+}
+
+fn floating_addresses(mask: &str, address: u64) -> Vec<u64> {
+    bits(mask).fold(vec![address], |addresses, (bit, c)| match c {
+        '1' => addresses.iter().map(|a| a | bit).collect(),
+        'X' => addresses
+            .iter()
+            .flat_map(|a| vec![a & !bit, a | bit])
+            .collect(),
+        _ => addresses,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day14::generate(&mut Rng::new(seed), 20);
+
+            // when
+            let mismatches = check_planted::<Day14>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day14>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
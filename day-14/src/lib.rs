@@ -0,0 +1,430 @@
+//! Day 14: Docking Data
+
+use common::num::{self, Arithmetic, BigInt, Int, Number};
+use common::{end_of, parse_int, Params, ParseError, Solution};
+use std::collections::HashMap;
+use std::fmt;
+
+mod generate;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    // the program and how to sum up the memory
+    type Input<'a> = (Vec<Instruction>, Arithmetic);
+    type Answer1 = Number<u64>;
+    type Answer2 = Number<u64>;
+
+    const PARAMS: &'static [&'static str] = &[num::ARITHMETIC];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Self::parse_with_params(input, &Params::new())
+    }
+
+    fn parse_with_params<'a>(
+        input: &'a str,
+        params: &Params,
+    ) -> Result<Self::Input<'a>, ParseError> {
+        Ok((parse_instructions(input)?, Arithmetic::from_params(params)?))
+    }
+
+    fn part1((instructions, arithmetic): &Self::Input<'_>) -> Result<Number<u64>, String> {
+        let memory = run_instructions(instructions);
+        match arithmetic {
+            Arithmetic::Checked => sum_memory(&memory).map(Number::Fixed),
+            Arithmetic::Big => sum_memory(&memory).map(Number::Big),
+        }
+    }
+
+    fn part2((instructions, arithmetic): &Self::Input<'_>) -> Result<Number<u64>, String> {
+        match arithmetic {
+            Arithmetic::Checked => run_instructions_v2(instructions).map(Number::Fixed),
+            Arithmetic::Big => run_instructions_v2::<BigInt>(instructions).map(Number::Big),
+        }
+    }
+}
+
+/// The sum of all values in memory
+pub fn sum_memory<N: Int>(memory: &HashMap<u64, u64>) -> Result<N, String> {
+    memory
+        .values()
+        .try_fold(num::int(0, "zero")?, |sum, value| {
+            num::add(&sum, &num::int(*value, "value")?, "sum of the memory")
+        })
+}
+
+/// Runs the program with version 1 of the decoder chip, the mask applies to values. Returns the
+/// memory after the program.
+pub fn run_instructions(instructions: &[Instruction]) -> HashMap<u64, u64> {
+    let mut result: HashMap<u64, u64> = HashMap::with_capacity(instructions.len());
+    let mut current_mask = Mask {
+        pos: u64::MAX,
+        bits: 0,
+    };
+
+    for instruction in instructions {
+        match instruction {
+            Instruction::Mask(mask) => current_mask = *mask,
+            Instruction::Assign(assign) => {
+                result.insert(assign.address, current_mask.apply(assign.value));
+            }
+        }
+    }
+
+    result
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A line of the initialization program
+pub enum Instruction {
+    Mask(Mask),
+    Assign(Assign),
+}
+
+/// Writes the instruction as in the puzzle input
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Mask(mask) => write!(f, "mask = {}", mask),
+            Instruction::Assign(assign) => {
+                write!(f, "mem[{}] = {}", assign.address, assign.value)
+            }
+        }
+    }
+}
+
+/// Parses the initialization program, one `mask = ...` or `mem[...] = ...` per line.
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .split_terminator('\n')
+        .map(parse_instruction)
+        .collect()
+}
+
+fn parse_instruction(line: &str) -> Result<Instruction, ParseError> {
+    if line.starts_with("mem[") {
+        parse_assign(line).map(Instruction::Assign)
+    } else if line.starts_with("mask =") {
+        parse_mask(line).map(Instruction::Mask)
+    } else {
+        Err(ParseError::unexpected_token(
+            line,
+            "an instruction ('mem[...] = ...' or 'mask = ...')",
+        ))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A bitmask: `pos` has the `X` bits set, `bits` the `1` bits
+pub struct Mask {
+    pub pos: u64,
+    pub bits: u64,
+}
+
+fn parse_mask(line: &str) -> Result<Mask, ParseError> {
+    let mut pos = 0;
+    let mut bits = 0;
+
+    let mask = line.trim_start_matches("mask = ");
+    for (i, c) in mask.char_indices() {
+        pos <<= 1;
+        bits <<= 1;
+        match c {
+            '1' => {
+                bits += 1;
+            }
+            '0' => (),
+            'X' => {
+                pos += 1;
+            }
+            _ => {
+                return Err(ParseError::unexpected_token(
+                    &mask[i..i + c.len_utf8()],
+                    "a mask bit ('0', '1' or 'X')",
+                ))
+            }
+        }
+    }
+
+    Ok(Mask { pos, bits })
+}
+
+/// Writes the 36 bits of the mask, most significant bit first
+impl fmt::Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for bit in (0..36).rev() {
+            let c = if self.pos >> bit & 1 == 1 {
+                'X'
+            } else if self.bits >> bit & 1 == 1 {
+                '1'
+            } else {
+                '0'
+            };
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl Mask {
+    /// Overwrites the bits of the value that are not `X` in the mask.
+    pub fn apply(&self, value: u64) -> u64 {
+        (value & self.pos) | self.bits
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// Writes `value` to the memory at `address`
+pub struct Assign {
+    pub address: u64,
+    pub value: u64,
+}
+
+fn parse_assign(line: &str) -> Result<Assign, ParseError> {
+    let (left, right) = line
+        .split_once(" = ")
+        .ok_or_else(|| ParseError::missing_field(end_of(line), "right hand side"))?;
+    let left = left.trim_start_matches("mem[").trim_end_matches(']');
+
+    let address: u64 = parse_int(left)?;
+    let value: u64 = parse_int(right)?;
+
+    Ok(Assign { address, value })
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A set of memory addresses: the `floating_bits` can be either 0 or 1, all other bits are as in
+/// `bits`. Floating bits are 0 in `bits`.
+pub struct FloatingAddress {
+    pub floating_bits: u64,
+    pub bits: u64,
+}
+
+impl FloatingAddress {
+    pub fn new(floating_bits: u64, bits: u64) -> Self {
+        FloatingAddress {
+            floating_bits,
+            bits,
+        }
+    }
+
+    /// The addresses that are in both sets, `None` if there are none
+    pub fn cut(&self, addr2: &FloatingAddress) -> Option<FloatingAddress> {
+        if self.bits & !addr2.floating_bits != addr2.bits & !self.floating_bits {
+            return None;
+        }
+        let floating_bits = self.floating_bits & addr2.floating_bits;
+        let bits = (self.bits | addr2.bits) & !floating_bits;
+
+        Some(FloatingAddress::new(floating_bits, bits))
+    }
+
+    /// The number of addresses in the set
+    pub fn cardinality(&self) -> u64 {
+        1 << self.floating_bits.count_ones()
+    }
+}
+
+/// Applies the mask to an address as version 2 of the decoder chip does.
+pub fn decode_address(raw: u64, mask: &Mask) -> FloatingAddress {
+    FloatingAddress {
+        floating_bits: mask.pos,
+        bits: (raw & !mask.pos) | mask.bits,
+    }
+}
+
+fn decode_instructions_v2(instructions: &[Instruction]) -> Vec<(FloatingAddress, u64)> {
+    let mut result = Vec::with_capacity(instructions.len());
+    let mut current_mask = Mask { pos: 0, bits: 0 };
+    for instruction in instructions {
+        match instruction {
+            Instruction::Mask(mask) => current_mask = *mask,
+            Instruction::Assign(assign) => {
+                result.push((decode_address(assign.address, &current_mask), assign.value));
+            }
+        }
+    }
+    result
+}
+
+/// Runs the program with version 2 of the decoder chip, the mask applies to addresses. Returns
+/// the sum of all values in memory after the program. Fails if the sum does not fit into `N`.
+pub fn run_instructions_v2<N: Int>(instructions: &[Instruction]) -> Result<N, String> {
+    let mut decoded_instructions = decode_instructions_v2(instructions);
+    decoded_instructions.reverse();
+
+    // each value counts for the addresses that no later write overwrites, so the partial sums
+    // only grow
+    let mut sum = num::int(0, "zero")?;
+    for (i, (addr, value)) in decoded_instructions.iter().enumerate() {
+        let later: Vec<FloatingAddress> = decoded_instructions[..i]
+            .iter()
+            .map(|(addr, _)| *addr)
+            .collect();
+        let count = num::int(uncovered(addr, &later), "address count")?;
+        let term = num::mul(&count, &num::int(*value, "value")?, "sum of the memory")?;
+        sum = num::add(&sum, &term, "sum of the memory")?;
+    }
+    Ok(sum)
+}
+
+// The number of addresses in `addr` that are in none of the `others`: all of them, except for
+// those in each of the others that are not in the others before it.
+fn uncovered(addr: &FloatingAddress, others: &[FloatingAddress]) -> u64 {
+    others
+        .iter()
+        .enumerate()
+        .filter_map(|(i, other)| addr.cut(other).map(|cut| uncovered(&cut, &others[..i])))
+        .fold(addr.cardinality(), |count, covered| count - covered)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::generate::{Generator, Rng};
+
+    #[test]
+    fn mask_application_works_correctly() {
+        // given
+        let mask = parse_mask("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X")
+            .expect("Expected valid example mask");
+
+        // when
+        let result = mask.apply(11);
+
+        // then
+        assert_eq!(result, 73);
+    }
+
+    #[test]
+    fn run_instructions_v2_works_for_example() {
+        // given
+        let instructions = parse_instructions(
+            r"mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1",
+        )
+        .expect("Expected example program to be valid");
+
+        // when
+        let result = run_instructions_v2::<u64>(&instructions);
+
+        // then
+        assert_eq!(result, Ok(208));
+    }
+    #[test]
+    fn run_instructions_v2_works_for_extended_example() {
+        // given
+        let instructions = parse_instructions(
+            r"mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1
+mem[26] = 1",
+        )
+        .expect("Expected example program to be valid");
+
+        // when
+        let result = run_instructions_v2::<u64>(&instructions);
+
+        // then
+        assert_eq!(result, Ok(208));
+    }
+
+    #[test]
+    fn run_instructions_v2_needs_big_integers_for_large_values() {
+        // given
+        // each write covers 2^36 addresses
+        let instructions = parse_instructions(&format!(
+            "mask = {}\nmem[0] = {}\n",
+            "X".repeat(36),
+            1u64 << 30
+        ))
+        .expect("Expected program to be valid");
+
+        // when
+        let checked = run_instructions_v2::<u64>(&instructions);
+        let big = run_instructions_v2::<BigInt>(&instructions);
+
+        // then
+        assert!(checked
+            .expect_err("Expected the sum to overflow")
+            .contains("sum of the memory"));
+        assert_eq!(
+            big.map(|sum| sum.to_string()),
+            Ok((1u128 << 66).to_string())
+        );
+    }
+
+    #[test]
+    fn run_instructions_v2_sums_up_to_u64() {
+        // given
+        // the first write covers 2^36 addresses, of which the second one overwrites 2^35
+        let instructions = parse_instructions(&format!(
+            "mask = {}\nmem[0] = {}\nmask = 0{}\nmem[0] = {}\n",
+            "X".repeat(36),
+            (1u64 << 28) - 1,
+            "X".repeat(35),
+            1u64 << 28
+        ))
+        .expect("Expected program to be valid");
+
+        // when
+        let result = run_instructions_v2::<u64>(&instructions);
+
+        // then
+        assert_eq!(result, Ok((1u64 << 63) + (1 << 35) * ((1 << 28) - 1)));
+    }
+
+    #[test]
+    fn floating_address_cut_creates_cut_set_of_addresses() {
+        assert_eq!(
+            FloatingAddress::new(0, 1).cut(&FloatingAddress::new(0, 0)),
+            None
+        );
+        assert_eq!(
+            FloatingAddress::new(1, 0).cut(&FloatingAddress::new(0, 1)),
+            Some(FloatingAddress::new(0, 1))
+        );
+        assert_eq!(
+            FloatingAddress::new(0b10, 0b01).cut(&FloatingAddress::new(0b01, 0b10)),
+            Some(FloatingAddress::new(0, 0b11))
+        );
+        assert_eq!(
+            FloatingAddress::new(0b100, 0b010).cut(&FloatingAddress::new(0b010, 0b100)),
+            Some(FloatingAddress::new(0, 0b110))
+        );
+        assert_eq!(
+            FloatingAddress::new(0b11, 0b00).cut(&FloatingAddress::new(0b11, 0b00)),
+            Some(FloatingAddress::new(0b11, 0b00))
+        );
+        assert_eq!(
+            FloatingAddress::new(0b110, 0b001).cut(&FloatingAddress::new(0b110, 0b000)),
+            None
+        );
+        assert_eq!(
+            FloatingAddress::new(0b1100, 0b0011).cut(&FloatingAddress::new(0b1010, 0b0101)),
+            Some(FloatingAddress::new(0b1000, 0b0111))
+        );
+    }
+
+    #[test]
+    fn parse_inverts_display_for_generated_instructions() {
+        for seed in 0..10 {
+            // given
+            let input = Day14::generate(&mut Rng::new(seed), 50).input;
+            let instructions = parse_instructions(&input).expect("Expected valid generated input");
+
+            // when
+            let written: String = instructions
+                .iter()
+                .map(|instruction| format!("{}\n", instruction))
+                .collect();
+            let reparsed = parse_instructions(&written);
+
+            // then
+            assert_eq!(reparsed, Ok(instructions));
+        }
+    }
+}
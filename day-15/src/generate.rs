@@ -0,0 +1,74 @@
+use crate::Day15;
+use common::generate::{Generated, Generator, Rng};
+use common::Params;
+
+/// `size` is the number of distinct starting numbers (at least 1). Part 1 is planted by playing
+/// the game the slow way, looking back through all spoken numbers each turn. Part 2 is not
+/// planted, that would take far too long.
+impl Generator for Day15 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut numbers: Vec<usize> = Vec::with_capacity(size);
+        while numbers.len() < size.max(1) {
+            let n = rng.index(20 + 2 * size);
+            if !numbers.contains(&n) {
+                numbers.push(n);
+            }
+        }
+
+        let mut spoken = numbers.clone();
+        while spoken.len() < 2020 {
+            let (last, before) = spoken.split_last().unwrap_or((&0, &[]));
+            let age = before
+                .iter()
+                .rev()
+                .position(|n| n == last)
+                .map_or(0, |i| i + 1);
+            spoken.push(age);
+        }
+        let input: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+
+        Generated {
+            input: format!("{}\n", input.join(",")),
+            part1: Some(spoken[2019].to_string()),
+            part2: None,
+        }
+    }
+
+    // 30 million turns for each mutated input would take ages
+    fn fuzz_params() -> Params {
+        let mut params = Params::new();
+        params.insert("turns2", "3000");
+        params
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::Solution;
+
+    #[test]
+    fn generated_input_has_planted_answer_for_part1() {
+        for seed in 0..10 {
+            // given
+            let generated = Day15::generate(&mut Rng::new(seed), 6);
+            let game = Day15::parse(&generated.input).expect("Expected valid generated input");
+
+            // when
+            let answer = Day15::part1(&game).map(|n| n.to_string());
+
+            // then
+            assert_eq!(answer.ok(), generated.part1);
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day15>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
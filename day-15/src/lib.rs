@@ -0,0 +1,158 @@
+//! Day 15: Rambunctious Recitation
+
+use common::{end_of, parse_int, warn, Params, ParseError, Solution};
+use std::collections::HashMap;
+
+mod generate;
+
+pub struct Day15;
+
+/// The starting numbers of the memory game and how many turns to play for each part
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Game {
+    pub numbers: Vec<usize>,
+    pub turns_part1: usize,
+    pub turns_part2: usize,
+}
+
+impl Solution for Day15 {
+    type Input<'a> = Game;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    /// `start` replaces the starting numbers from the input, `turns1` and `turns2` are the turns
+    /// to play for part 1 (default: 2020) and part 2 (default: 30 000 000)
+    const PARAMS: &'static [&'static str] = &["start", "turns1", "turns2"];
+
+    fn parse(input: &str) -> Result<Game, ParseError> {
+        Self::parse_with_params(input, &Params::new())
+    }
+
+    fn parse_with_params(input: &str, params: &Params) -> Result<Game, ParseError> {
+        Ok(Game {
+            numbers: parse_numbers(params.get_str("start").unwrap_or(input))?,
+            turns_part1: params.get_int("turns1", 2020)?,
+            turns_part2: params.get_int("turns2", 30_000_000)?,
+        })
+    }
+
+    fn part1(game: &Game) -> Result<usize, String> {
+        get_number_at_turn(game.turns_part1, &game.numbers)
+    }
+
+    fn part2(game: &Game) -> Result<usize, String> {
+        // After yesterday, I need a day off. So I'm bruteforcing this
+        // Takes 3.5s and some dozen MiB of memory, so who cares?
+        // 2.8s if I reserve some more memory up front
+        // Hey, If they did not want me to bute force it, they would have picked a higher number.
+        get_number_at_turn(game.turns_part2, &game.numbers)
+    }
+}
+
+/// Parses comma separated starting numbers, e.g. "0,3,6"
+fn parse_numbers(input: &str) -> Result<Vec<usize>, ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseError::missing_field(end_of(input), "starting numbers"));
+    }
+    input.split(',').map(|n| parse_int(n.trim())).collect()
+}
+
+/// Plays the memory game with the given starting numbers and returns the number spoken at
+/// `final_turn`. Turns start at 1.
+pub fn get_number_at_turn(final_turn: usize, input: &[usize]) -> Result<usize, String> {
+    if input.is_empty() {
+        return Err("No initial numbers".to_owned());
+    }
+    if final_turn == 0 {
+        return Err("There is no turn 0, turns start at 1".to_owned());
+    }
+    if final_turn <= input.len() {
+        return Ok(input[final_turn - 1]);
+    }
+    let mut seen: HashMap<usize, usize> = input[0..input.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (*n, i))
+        .collect();
+    seen.reserve(input.len() / 4);
+    let mut prev = input[input.len() - 1];
+
+    for turn in input.len()..final_turn {
+        let current: usize = seen.get(&prev).map(|t| turn - t - 1).unwrap_or(0);
+        seen.insert(prev, turn - 1);
+        prev = current;
+    }
+    warn(format!(
+        "seen {} numbers, {} MiB",
+        seen.len(),
+        seen.len() * 8 / 1024 / 1024
+    ));
+    Ok(prev)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::ErrorKind;
+
+    #[test]
+    fn get_number_2020_works_for_example() {
+        // given
+        let input = &[0, 3, 6];
+
+        // when
+        let result = get_number_at_turn(2020, input).expect("Expected no failure");
+
+        // then
+        assert_eq!(result, 436);
+    }
+
+    #[test]
+    fn get_number_at_turn_works_within_starting_numbers() {
+        // given
+        let input = &[0, 3, 6];
+
+        // when
+        let result = get_number_at_turn(2, input);
+
+        // then
+        assert_eq!(result, Ok(3));
+    }
+
+    #[test]
+    fn parse_with_params_prefers_start_parameter() {
+        // given
+        let mut params = Params::new();
+        params.insert("start", "3,1,2");
+        params.insert("turns2", "10");
+
+        // when
+        let result = Day15::parse_with_params("0,3,6\n", &params);
+
+        // then
+        assert_eq!(
+            result,
+            Ok(Game {
+                numbers: vec![3, 1, 2],
+                turns_part1: 2020,
+                turns_part2: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_fails_for_missing_numbers() {
+        // given
+        let input = "\n";
+
+        // when
+        let result = Day15::parse(input);
+
+        // then
+        assert!(matches!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::MissingField { .. })
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::Day16;
+use common::generate::{Generated, Generator, Rng};
+
+const FIELD_NAMES: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+// the width of the band of values that only field g and the fields after it accept
+const BAND: u64 = 20;
+
+/// `size` is the number of ticket fields (2 to 20). Field g accepts the values from 1 to
+/// `BAND * (g + 1)`, plus a small range above all ticket values. Every field has at least one
+/// ticket value in its own band, so the fields can be found one after the other, starting with
+/// the last. Some tickets have one value that no field accepts.
+impl Generator for Day16 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let fields = size.clamp(2, FIELD_NAMES.len());
+        let mut names = FIELD_NAMES.to_vec();
+        rng.shuffle(&mut names);
+
+        let mut input = String::new();
+        let mut rule_order: Vec<usize> = (0..fields).collect();
+        rng.shuffle(&mut rule_order);
+        for g in rule_order {
+            let high = 900 + 10 * g as u64;
+            input.push_str(&format!(
+                "{}: 1-{} or {}-{}\n",
+                names[g],
+                BAND * (g as u64 + 1),
+                high,
+                high + 5
+            ));
+        }
+
+        // column i of the tickets is field columns[i]
+        let mut columns: Vec<usize> = (0..fields).collect();
+        rng.shuffle(&mut columns);
+        let in_band = |rng: &mut Rng, g: usize| BAND * g as u64 + rng.below(BAND) + 1;
+
+        let own_ticket: Vec<u64> = columns.iter().map(|g| in_band(rng, *g)).collect();
+        let departure_product: u64 = own_ticket
+            .iter()
+            .zip(&columns)
+            .filter(|(_, g)| names[**g].starts_with("departure"))
+            .map(|(value, _)| value)
+            .product();
+
+        let mut tickets: Vec<Vec<u64>> = vec![columns.iter().map(|g| in_band(rng, *g)).collect()];
+        let mut error_sum = 0;
+        for _ in 0..(3 * fields) {
+            let mut ticket: Vec<u64> = columns
+                .iter()
+                .map(|g| rng.below(BAND * (*g as u64 + 1)) + 1)
+                .collect();
+            if rng.chance(0.25) {
+                let invalid = rng.below(499) + 401;
+                ticket[rng.index(fields)] = invalid;
+                error_sum += invalid;
+            }
+            tickets.push(ticket);
+        }
+        rng.shuffle(&mut tickets);
+
+        input.push_str("\nyour ticket:\n");
+        input.push_str(&join(&own_ticket));
+        input.push_str("\n\nnearby tickets:\n");
+        for ticket in tickets {
+            input.push_str(&join(&ticket));
+            input.push('\n');
+        }
+
+        Generated {
+            input,
+            part1: Some(error_sum.to_string()),
+            part2: Some(departure_product.to_string()),
+        }
+    }
+}
+
+fn join(ticket: &[u64]) -> String {
+    let values: Vec<String> = ticket.iter().map(|v| v.to_string()).collect();
+    values.join(",")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day16::generate(&mut Rng::new(seed), 20);
+
+            // when
+            let mismatches = check_planted::<Day16>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day16>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
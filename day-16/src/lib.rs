@@ -0,0 +1,273 @@
+//! Day 16: Ticket Translation
+
+use common::{end_of, parse_int, ParseError, Solution};
+use std::collections::HashSet;
+
+mod generate;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Notes<'a>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Notes<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((rules, _, nearby_tickets): &Notes) -> Result<u64, String> {
+        Ok(ticket_scanning_error_sum(rules, nearby_tickets))
+    }
+
+    fn part2((rules, own_ticket, nearby_tickets): &Notes) -> Result<u64, String> {
+        let field_order = get_field_order(rules, nearby_tickets)?;
+
+        Ok(own_ticket
+            .iter()
+            .zip(&field_order)
+            .filter(|(_, rule)| rule.field_name.starts_with("departure"))
+            .map(|(value, _)| value)
+            .product())
+    }
+}
+
+/// Sums up all values of the tickets that do not match any rule.
+pub fn ticket_scanning_error_sum(rules: &[Rule], tickets: &[Vec<u64>]) -> u64 {
+    tickets
+        .iter()
+        .flatten()
+        .filter(|value| !rules.iter().any(|rule| value_matches_rule(rule, **value)))
+        .sum()
+}
+
+/// Finds the rule for each field of the tickets, in the order of the fields. Tickets with values
+/// that do not match any rule are ignored.
+pub fn get_field_order<'a>(
+    rules: &'a [Rule],
+    unfiltered_tickets: &'a [Vec<u64>],
+) -> Result<Vec<&'a Rule<'a>>, String> {
+    let tickets: Vec<&[u64]> = unfiltered_tickets
+        .iter()
+        .filter(|ticket| {
+            ticket
+                .iter()
+                .all(|value| rules.iter().any(|rule| value_matches_rule(rule, *value)))
+        })
+        .map(|ticket| ticket as &[u64])
+        .collect();
+
+    if tickets.iter().any(|ticket| ticket.len() != rules.len()) {
+        return Err(
+            "Number of rule does not match number of ticket fields for all tickets".to_owned(),
+        );
+    }
+
+    let matching_rules: Vec<Vec<HashSet<&Rule>>> = tickets
+        .iter()
+        .map(|ticket| matching_rules_for_values(rules, ticket))
+        .collect();
+
+    let mut rule_options: Vec<HashSet<&Rule>> =
+        matching_rules
+            .iter()
+            .skip(1)
+            .fold(matching_rules[0].clone(), |mut acc, ticket_rules| {
+                for i in 0..rules.len() {
+                    acc[i] = acc[i].intersection(&ticket_rules[i]).copied().collect()
+                }
+                acc
+            });
+
+    let mut unique_rules: HashSet<Rule> = HashSet::with_capacity(rules.len());
+    let mut found = true;
+    while found {
+        found = false;
+        rule_options = if let Some(unique_rule) = rule_options
+            .iter()
+            .filter_map(|options| {
+                if options.len() == 1 {
+                    options.iter().next()
+                } else {
+                    None
+                }
+            })
+            .find(|rule| !unique_rules.contains(*rule))
+        {
+            found = true;
+            // ugh don't wanna care about borrowing right now ust clone the stuff
+            unique_rules.insert((*unique_rule).clone());
+            rule_options
+                .iter()
+                .map(|rules| {
+                    if rules.len() == 1 {
+                        rules.clone()
+                    } else {
+                        rules
+                            .iter()
+                            .copied()
+                            .filter(|rule| rule != unique_rule)
+                            .collect()
+                    }
+                })
+                .collect()
+        } else {
+            rule_options
+        }
+    }
+
+    // not sure if this approach works in every case, but it works for my input
+    rule_options
+        .iter()
+        .enumerate()
+        .map(|(i, options)| {
+            if options.len() > 1 {
+                Err(format!("Ambigious field found at index {}", i))
+            } else {
+                options
+                    .iter()
+                    .next()
+                    .copied()
+                    .ok_or_else(|| format!("Found field without any matching rules at index {}", i))
+            }
+        })
+        .collect()
+}
+
+fn matching_rules_for_values<'a>(
+    rules: &'a [Rule<'a>],
+    ticket: &'a [u64],
+) -> Vec<HashSet<&'a Rule<'a>>> {
+    ticket
+        .iter()
+        .map(|value| {
+            rules
+                .iter()
+                .filter(|rule| value_matches_rule(rule, *value))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn value_matches_rule(rule: &Rule, value: u64) -> bool {
+    rule.ranges
+        .iter()
+        .any(|(from, to)| value >= *from && value <= *to)
+}
+
+/// The rules, your own ticket and the nearby tickets
+pub type Notes<'a> = (Vec<Rule<'a>>, Vec<u64>, Vec<Vec<u64>>);
+
+pub fn parse_input(content: &str) -> Result<Notes<'_>, ParseError> {
+    let mut split = content.splitn(3, "\n\n");
+    // splitn always yields at least one element
+    let rules = parse_rules(split.next().unwrap_or(content))?;
+    let own_ticket = split
+        .next()
+        .ok_or_else(|| ParseError::missing_field(end_of(content), "own ticket"))
+        .and_then(|s| parse_ticket(s.trim_start_matches("your ticket:\n")))?;
+    let nearby_tickets = split
+        .next()
+        .ok_or_else(|| ParseError::missing_field(end_of(content), "nearby tickets"))
+        .and_then(|s| parse_tickets(s.trim_start_matches("nearby tickets:\n")))?;
+
+    Ok((rules, own_ticket, nearby_tickets))
+}
+
+fn parse_tickets(lines: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    lines.split_terminator('\n').map(parse_ticket).collect()
+}
+
+fn parse_ticket(line: &str) -> Result<Vec<u64>, ParseError> {
+    line.split(',').map(parse_int).collect()
+}
+
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A ticket field and the two (inclusive) ranges of valid values
+pub struct Rule<'a> {
+    pub field_name: &'a str,
+    pub ranges: [(u64, u64); 2],
+}
+
+fn parse_rules(lines: &str) -> Result<Vec<Rule<'_>>, ParseError> {
+    lines.split_terminator('\n').map(parse_rule).collect()
+}
+
+fn parse_rule(line: &str) -> Result<Rule<'_>, ParseError> {
+    let (field_name, ranges) = line
+        .split_once(": ")
+        .ok_or_else(|| ParseError::missing_field(end_of(line), "ranges"))?;
+    let ranges = parse_ranges(ranges)?;
+    Ok(Rule { field_name, ranges })
+}
+
+fn parse_ranges(input: &str) -> Result<[(u64, u64); 2], ParseError> {
+    let (first, second) = input
+        .split_once(" or ")
+        .ok_or_else(|| ParseError::missing_field(end_of(input), "second range"))?;
+
+    Ok([parse_range(first)?, parse_range(second)?])
+}
+
+fn parse_range(input: &str) -> Result<(u64, u64), ParseError> {
+    let (from, to) = input
+        .split_once('-')
+        .ok_or_else(|| ParseError::missing_field(end_of(input), "end of range"))?;
+    Ok((parse_int(from)?, parse_int(to)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ticket_scanning_error_sum_works_for_example() {
+        // given
+        let input = r"class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+        let (rules, _, nearby_tickets) = parse_input(input).expect("Expected valid example input");
+
+        // when
+        let sum = ticket_scanning_error_sum(&rules, &nearby_tickets);
+
+        // then
+        assert_eq!(sum, 71);
+    }
+
+    #[test]
+    fn get_field_order_works_for_example() {
+        // given
+        let input = r"class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+        let (rules, _, nearby_tickets) = parse_input(input).expect("Expected valid example input");
+
+        // when
+        let result = get_field_order(&rules, &nearby_tickets);
+
+        // then
+        let ordered = result.expect("Expected a solution");
+        assert_eq!(ordered.len(), 3, "Unexpected length");
+        assert_eq!(ordered[0].field_name, "row");
+        assert_eq!(ordered[1].field_name, "class");
+        assert_eq!(ordered[2].field_name, "seat");
+    }
+}
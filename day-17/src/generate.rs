@@ -0,0 +1,54 @@
+use crate::Day17;
+use common::generate::{Generated, Generator, Rng};
+
+/// `size` is the width and height of the initial plane (at least 1), about a third of the cubes
+/// start active. The answers are not planted, the generated planes are only good for checking
+/// that the solution runs and for benchmarks.
+impl Generator for Day17 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.max(1);
+        let mut input = String::with_capacity(size * (size + 1));
+        for _ in 0..size {
+            for _ in 0..size {
+                input.push(if rng.chance(0.35) { '#' } else { '.' });
+            }
+            input.push('\n');
+        }
+
+        Generated {
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_can_be_solved() {
+        for seed in 0..3 {
+            // given
+            let generated = Day17::generate(&mut Rng::new(seed), 4);
+
+            // when
+            let mismatches = check_planted::<Day17>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when, with fewer inputs than for other days: four dimensions take a while in a debug build
+        let failures = fuzz_solve::<Day17>(0, 20);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,120 @@
+//! Day 17: Conway Cubes
+
+use common::automaton::{Automaton, LifeLike, Moore};
+use common::grid::Grid;
+use common::{ParseError, Solution};
+
+mod generate;
+mod trace;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    // the initial plane, `true` is an active cube
+    type Input<'a> = Grid<bool>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input_plane: &Grid<bool>) -> Result<usize, String> {
+        let mut pocket_dimension = pocket_dimension::<3>(input_plane);
+        pocket_dimension.run(6);
+        Ok(pocket_dimension.population())
+    }
+
+    fn part2(input_plane: &Grid<bool>) -> Result<usize, String> {
+        let mut pocket_dimension = pocket_dimension::<4>(input_plane);
+        pocket_dimension.run(6);
+        Ok(pocket_dimension.population())
+    }
+}
+
+/// An active cube stays active with two or three active neighbours, an inactive cube becomes
+/// active with exactly three active neighbours.
+pub fn conway_cubes() -> LifeLike {
+    LifeLike::new(&[3], &[2, 3])
+}
+
+/// An infinite pocket dimension with `N` (at least two) dimensions. The initial plane is the one
+/// where all coordinates but x and y are 0.
+pub fn pocket_dimension<const N: usize>(plane: &Grid<bool>) -> Automaton<Moore<N>, bool, LifeLike> {
+    assert!(N >= 2, "the initial plane needs two dimensions");
+    let cubes = plane.positions().filter(|pos| plane[*pos]).map(|(x, y)| {
+        let mut pos = [0; N];
+        pos[0] = x as i64;
+        pos[1] = y as i64;
+        (pos, true)
+    });
+    Automaton::new(Moore::new(), conway_cubes(), false, cubes)
+}
+
+/// Parses the initial plane, `#` is an active cube and `.` an inactive one.
+pub fn parse_input(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::parse(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::ErrorKind;
+
+    const EXAMPLE_INPUT: &str = r".#.
+..#
+###";
+
+    #[test]
+    fn six_cycles_in_three_dimensions_work_for_example() {
+        // given
+        let init_plane = parse_input(EXAMPLE_INPUT).expect("Expected valid example input");
+        let mut pocket_dimension = pocket_dimension::<3>(&init_plane);
+
+        // when
+        pocket_dimension.run(6);
+        let alive_count = pocket_dimension.population();
+
+        // then
+        assert_eq!(alive_count, 112);
+    }
+
+    #[test]
+    fn six_cycles_in_four_dimensions_work_for_example() {
+        // given
+        let init_plane = parse_input(EXAMPLE_INPUT).expect("Expected valid example input");
+        let mut pocket_dimension = pocket_dimension::<4>(&init_plane);
+
+        // when
+        pocket_dimension.run(6);
+        let alive_count = pocket_dimension.population();
+
+        // then
+        assert_eq!(alive_count, 848);
+    }
+
+    #[test]
+    fn parse_input_fails_for_rows_of_different_length() {
+        // given
+        let input = ".#.\n..\n###";
+
+        // when
+        let result = parse_input(input);
+
+        // then
+        let error = result
+            .map_err(|e| e.locate(input))
+            .expect_err("Expected parsing to fail");
+        assert!(matches!(error.kind(), ErrorKind::BadLayout { .. }));
+        assert_eq!(error.location().map(|l| l.line), Some(2));
+    }
+
+    #[test]
+    fn parse_input_fails_for_empty_input() {
+        // when
+        let result = parse_input("");
+
+        // then
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{pocket_dimension, Day17};
+use common::automaton::{Automaton, LifeLike, Moore};
+use common::grid::Grid;
+use common::trace::{Frame, Picture, Trace};
+
+/// One frame per cycle. The layers are drawn side by side from the lowest to the highest z, in
+/// four dimensions there is one row of layers for each w.
+impl Trace for Day17 {
+    fn trace(plane: &Grid<bool>, part: usize) -> Result<Vec<Frame>, String> {
+        if part == 1 {
+            Ok(trace_cycles(pocket_dimension::<3>(plane)))
+        } else {
+            Ok(trace_cycles(pocket_dimension::<4>(plane)))
+        }
+    }
+}
+
+fn trace_cycles<const N: usize>(
+    mut pocket_dimension: Automaton<Moore<N>, bool, LifeLike>,
+) -> Vec<Frame> {
+    let mut frames: Vec<Frame> = Vec::with_capacity(7);
+    loop {
+        let (min, max) = bounds(&pocket_dimension);
+        let mut label = format!("cycle {}", pocket_dimension.generation());
+        for (name, dimension) in [("z", 2), ("w", 3)].iter().take(N - 2) {
+            label.push_str(&format!(
+                ", {} from {} to {}",
+                name, min[*dimension], max[*dimension]
+            ));
+        }
+        frames.push(Frame::new(
+            label,
+            Picture::Map(draw_layers(&pocket_dimension, min, max)),
+        ));
+        if pocket_dimension.generation() == 6 {
+            return frames;
+        }
+        pocket_dimension.step();
+    }
+}
+
+// the smallest and largest coordinates of active cubes in each dimension
+fn bounds<const N: usize>(
+    pocket_dimension: &Automaton<Moore<N>, bool, LifeLike>,
+) -> ([i64; N], [i64; N]) {
+    pocket_dimension.cells().fold(
+        ([i64::MAX; N], [i64::MIN; N]),
+        |(mut min, mut max), (pos, _)| {
+            for i in 0..N {
+                min[i] = min[i].min(pos[i]);
+                max[i] = max[i].max(pos[i]);
+            }
+            (min, max)
+        },
+    )
+}
+
+fn draw_layers<const N: usize>(
+    pocket_dimension: &Automaton<Moore<N>, bool, LifeLike>,
+    min: [i64; N],
+    max: [i64; N],
+) -> Vec<String> {
+    if pocket_dimension.population() == 0 {
+        return Vec::new();
+    }
+    // dimensions beyond the ones drawn only have a single layer
+    let range = |i: usize| if i < N { min[i]..=max[i] } else { 0..=0 };
+    let mut rows: Vec<String> = Vec::new();
+    for w in range(3) {
+        if !rows.is_empty() {
+            rows.push(String::new());
+        }
+        for y in range(1) {
+            let row: Vec<String> = range(2)
+                .map(|z| {
+                    range(0)
+                        .map(|x| {
+                            let mut pos = [0; N];
+                            for (i, coordinate) in [x, y, z, w].iter().enumerate().take(N) {
+                                pos[i] = *coordinate;
+                            }
+                            if *pocket_dimension.get(pos) {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+            rows.push(row.join(" "));
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn trace_shows_layers_of_first_cycle() {
+        // given
+        let plane = parse_input(".#.\n..#\n###\n").expect("Expected valid example input");
+
+        // when
+        let frames = Day17::trace(&plane, 1).expect("Expected a trace");
+
+        // then
+        assert_eq!(frames.len(), 7);
+        assert_eq!(frames[1].label, "cycle 1, z from -1 to 1");
+        assert_eq!(
+            frames[1].picture,
+            Picture::Map(vec![
+                "#.. #.# #..".to_owned(),
+                "..# .## ..#".to_owned(),
+                ".#. .#. .#.".to_owned(),
+            ])
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::Day18;
+use common::generate::{Generated, Generator, Rng};
+
+/// `size` is the number of expressions. Each expression has two to five operands, single digits or
+/// nested expressions in parentheses, nested at most twice. The planted answers come from
+/// evaluating the generated expression trees, so they never go through the tokens.
+impl Generator for Day18 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut input = String::new();
+        let mut sum = 0u64;
+        let mut sum_advanced = 0u64;
+        let mut lines = 0;
+        while lines < size {
+            let expression = Expr::generate(rng, 2);
+            // the results have to fit into an u64, just try again if they don't
+            let results = expression
+                .evaluate(false)
+                .zip(expression.evaluate(true))
+                .and_then(|(a, b)| Some((sum.checked_add(a)?, sum_advanced.checked_add(b)?)));
+            if let Some((new_sum, new_sum_advanced)) = results {
+                sum = new_sum;
+                sum_advanced = new_sum_advanced;
+                input.push_str(&expression.to_string());
+                input.push('\n');
+                lines += 1;
+            }
+        }
+
+        Generated {
+            input,
+            part1: Some(sum.to_string()),
+            part2: Some(sum_advanced.to_string()),
+        }
+    }
+}
+
+enum Expr {
+    Num(u64),
+    // the first operand, then each operator (true for addition) with its right operand
+    Seq(Box<Expr>, Vec<(bool, Expr)>),
+}
+
+impl Expr {
+    fn generate(rng: &mut Rng, depth: usize) -> Expr {
+        let operand = |rng: &mut Rng| {
+            if depth > 0 && rng.chance(0.25) {
+                Expr::generate(rng, depth - 1)
+            } else {
+                Expr::Num(rng.below(9) + 1)
+            }
+        };
+        let first = operand(rng);
+        let rest = (1..rng.between(2, 5))
+            .map(|_| (rng.chance(0.5), operand(rng)))
+            .collect();
+        Expr::Seq(Box::new(first), rest)
+    }
+
+    // with `advanced`, addition is evaluated before multiplication, otherwise left to right
+    fn evaluate(&self, advanced: bool) -> Option<u64> {
+        match self {
+            Expr::Num(n) => Some(*n),
+            Expr::Seq(first, rest) => {
+                let mut product = 1u64;
+                let mut acc = first.evaluate(advanced)?;
+                for (add, operand) in rest {
+                    let value = operand.evaluate(advanced)?;
+                    if *add {
+                        acc = acc.checked_add(value)?;
+                    } else if advanced {
+                        product = product.checked_mul(acc)?;
+                        acc = value;
+                    } else {
+                        acc = acc.checked_mul(value)?;
+                    }
+                }
+                product.checked_mul(acc)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Num(n) => write!(f, "{}", n),
+            Expr::Seq(first, rest) => {
+                write!(f, "{}", Paren(first))?;
+                for (add, operand) in rest {
+                    write!(f, " {} {}", if *add { '+' } else { '*' }, Paren(operand))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// an operand, with parentheses around nested expressions
+struct Paren<'a>(&'a Expr);
+
+impl std::fmt::Display for Paren<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Expr::Num(_) => write!(f, "{}", self.0),
+            Expr::Seq(_, _) => write!(f, "({})", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day18::generate(&mut Rng::new(seed), 100);
+
+            // when
+            let mismatches = check_planted::<Day18>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day18>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,340 @@
+//! Day 18: Operation Order
+
+use common::num::{self, Arithmetic, BigInt, Int, Number};
+use common::{Params, ParseError, Solution};
+
+mod generate;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    // the tokens of each expression and how to evaluate them
+    type Input<'a> = (Vec<Vec<Token>>, Arithmetic);
+    type Answer1 = Number<u64>;
+    type Answer2 = Number<u64>;
+
+    const PARAMS: &'static [&'static str] = &[num::ARITHMETIC];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Self::parse_with_params(input, &Params::new())
+    }
+
+    fn parse_with_params<'a>(
+        input: &'a str,
+        params: &Params,
+    ) -> Result<Self::Input<'a>, ParseError> {
+        Ok((tokenize_input(input)?, Arithmetic::from_params(params)?))
+    }
+
+    fn part1((tokenized, arithmetic): &Self::Input<'_>) -> Result<Number<u64>, String> {
+        match arithmetic {
+            Arithmetic::Checked => sum_expressions(tokenized, run_expression).map(Number::Fixed),
+            Arithmetic::Big => {
+                sum_expressions(tokenized, run_expression::<BigInt>).map(Number::Big)
+            }
+        }
+    }
+
+    fn part2((tokenized, arithmetic): &Self::Input<'_>) -> Result<Number<u64>, String> {
+        match arithmetic {
+            Arithmetic::Checked => {
+                sum_expressions(tokenized, run_expression_advanced).map(Number::Fixed)
+            }
+            Arithmetic::Big => {
+                sum_expressions(tokenized, run_expression_advanced::<BigInt>).map(Number::Big)
+            }
+        }
+    }
+}
+
+/// The sum of the values of all expressions
+pub fn sum_expressions<N: Int>(
+    tokenized: &[Vec<Token>],
+    run: impl Fn(&[Token]) -> Result<N, String>,
+) -> Result<N, String> {
+    tokenized
+        .iter()
+        .try_fold(num::int(0, "zero")?, |sum, tokens| {
+            num::add(&sum, &run(tokens)?, "sum of the expressions")
+        })
+}
+
+/// Evaluates an expression where `+` and `*` have the same precedence.
+pub fn run_expression<N: Int>(tokens: &[Token]) -> Result<N, String> {
+    check_operands(tokens)?;
+    let mut output_queue: Vec<Token> = Vec::with_capacity(128);
+    let mut op_stack: Vec<Token> = Vec::with_capacity(128);
+    for token in tokens {
+        match token {
+            Token::Num(_) => output_queue.push(*token),
+            Token::Mul | Token::Add => {
+                while let Some(op) = op_stack.last().copied() {
+                    if op == Token::Mul || op == Token::Add {
+                        op_stack.pop();
+                        output_queue.push(op);
+                    } else {
+                        break;
+                    }
+                }
+                op_stack.push(*token);
+            }
+            Token::ParO => op_stack.push(*token),
+            Token::ParC => {
+                while let Some(op) = op_stack.last() {
+                    if *op == Token::ParO {
+                        break;
+                    }
+                    output_queue.push(*op);
+                    op_stack.pop();
+                }
+                if op_stack.pop() != Some(Token::ParO) {
+                    return Err("Mismatching paranthesis".to_owned());
+                }
+            }
+        }
+    }
+    while let Some(op) = op_stack.pop() {
+        output_queue.push(op);
+    }
+
+    evaluate(&output_queue)
+}
+
+/// Evaluates an expression where `+` has a higher precedence than `*`.
+pub fn run_expression_advanced<N: Int>(tokens: &[Token]) -> Result<N, String> {
+    check_operands(tokens)?;
+    let mut output_queue: Vec<Token> = Vec::with_capacity(128);
+    let mut op_stack: Vec<Token> = Vec::with_capacity(128);
+    for token in tokens {
+        match token {
+            Token::Num(_) => output_queue.push(*token),
+            Token::Mul => {
+                while let Some(op) = op_stack.last().copied() {
+                    if op == Token::Mul || op == Token::Add {
+                        op_stack.pop();
+                        output_queue.push(op);
+                    } else {
+                        break;
+                    }
+                }
+                op_stack.push(*token);
+            }
+            Token::Add => {
+                while let Some(op) = op_stack.last().copied() {
+                    if op == Token::Add {
+                        op_stack.pop();
+                        output_queue.push(op);
+                    } else {
+                        break;
+                    }
+                }
+                op_stack.push(*token);
+            }
+            Token::ParO => op_stack.push(*token),
+            Token::ParC => {
+                while let Some(op) = op_stack.last() {
+                    if *op == Token::ParO {
+                        break;
+                    }
+                    output_queue.push(*op);
+                    op_stack.pop();
+                }
+                if op_stack.pop() != Some(Token::ParO) {
+                    return Err("Mismatching paranthesis".to_owned());
+                }
+            }
+        }
+    }
+    while let Some(op) = op_stack.pop() {
+        output_queue.push(op);
+    }
+
+    evaluate(&output_queue)
+}
+
+// numbers and operators have to take turns, the shunting-yard algorithm would accept `1 (+ 2)`
+fn check_operands(tokens: &[Token]) -> Result<(), String> {
+    let mut expect_operand = true;
+    for token in tokens {
+        expect_operand = match (expect_operand, token) {
+            (true, Token::Num(_)) | (false, Token::ParC) => false,
+            (true, Token::ParO) | (false, Token::Mul | Token::Add) => true,
+            (true, _) => return Err(format!("Missing operand before {:?}", token)),
+            (false, _) => return Err(format!("Missing operator before {:?}", token)),
+        };
+    }
+    if expect_operand {
+        return Err("Missing operand at the end of the expression".to_owned());
+    }
+    Ok(())
+}
+
+// evaluates the expression in reverse polish notation
+fn evaluate<N: Int>(output_queue: &[Token]) -> Result<N, String> {
+    let mut num_stack: Vec<N> = Vec::with_capacity(output_queue.len());
+    for token in output_queue {
+        match token {
+            Token::Num(n) => num_stack.push(num::int(*n, "number")?),
+            Token::Mul => {
+                let (n1, n2) = operands(&mut num_stack)?;
+                num_stack.push(num::mul(&n1, &n2, "multiplication")?);
+            }
+            Token::Add => {
+                let (n1, n2) = operands(&mut num_stack)?;
+                num_stack.push(num::add(&n1, &n2, "addition")?);
+            }
+            _ => return Err(format!("Unknown operator on stack: {:?}", token)),
+        }
+    }
+    match num_stack.as_slice() {
+        [result] => Ok(result.clone()),
+        [] => Err("No result after executing expression".to_owned()),
+        _ => Err("Missing operator between numbers".to_owned()),
+    }
+}
+
+fn operands<N>(num_stack: &mut Vec<N>) -> Result<(N, N), String> {
+    match (num_stack.pop(), num_stack.pop()) {
+        (Some(n1), Some(n2)) => Ok((n1, n2)),
+        _ => Err("Missing operand of an operator".to_owned()),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A token of an expression. `ParO` and `ParC` are the opening and closing parentheses.
+pub enum Token {
+    Num(u64),
+    Mul,
+    Add,
+    ParO,
+    ParC,
+}
+
+/// Tokenizes the expressions, one per line.
+pub fn tokenize_input(input: &str) -> Result<Vec<Vec<Token>>, ParseError> {
+    input.split_terminator('\n').map(tokenize_line).collect()
+}
+
+pub fn tokenize_line(line: &str) -> Result<Vec<Token>, ParseError> {
+    // the input looks like that all numbers are single-digit numbers, so I won't bother parsing
+    // longer numbers
+    line.char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .map(|n| Token::Num(n as u64))
+                .or(match c {
+                    '*' => Some(Token::Mul),
+                    '+' => Some(Token::Add),
+                    '(' => Some(Token::ParO),
+                    ')' => Some(Token::ParC),
+                    _ => None,
+                })
+                .ok_or_else(|| {
+                    ParseError::unexpected_token(
+                        &line[i..i + c.len_utf8()],
+                        "a digit, an operator or a parenthesis",
+                    )
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn run_expression_works_for_examples() {
+        // given
+        let examples = r"2 * 3 + (4 * 5)
+        5 + (8 * 3 + 9 + 3 * 4 * 3)
+        5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
+        ((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+        let tokenized = tokenize_input(examples).expect("Expected successful tokenization");
+
+        // when
+        let results = tokenized
+            .iter()
+            .map(|tokens| run_expression(tokens))
+            .collect::<Result<Vec<u64>, String>>()
+            .expect("Expected successful parsing");
+
+        // then
+        assert_eq!(results, &[26, 437, 12240, 13632]);
+    }
+
+    #[test]
+    fn run_expression_advanced_works_for_examples() {
+        // given
+        let examples = r"1 + (2 * 3) + (4 * (5 + 6))
+        2 * 3 + (4 * 5)
+        5 + (8 * 3 + 9 + 3 * 4 * 3)
+        5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
+        ((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+        let tokenized = tokenize_input(examples).expect("Expected successful tokenization");
+
+        // when
+        let results = tokenized
+            .iter()
+            .map(|tokens| run_expression_advanced(tokens))
+            .collect::<Result<Vec<u64>, String>>()
+            .expect("Expected successful parsing");
+
+        // then
+        assert_eq!(results, &[51, 46, 1445, 669060, 23340]);
+    }
+
+    #[test]
+    fn run_expression_reports_overflow() {
+        // given
+        let expression =
+            "9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9";
+        let tokens = tokenize_line(expression).expect("Expected successful tokenization");
+
+        // when
+        let checked = run_expression::<u64>(&tokens);
+        let big = run_expression::<BigInt>(&tokens);
+
+        // then
+        assert!(checked
+            .expect_err("Expected 9^21 to overflow")
+            .starts_with("Overflow in multiplication"));
+        assert_eq!(
+            big.map(|n| n.to_string()),
+            Ok("109418989131512359209".to_owned())
+        );
+    }
+
+    #[test]
+    fn run_expression_rejects_missing_operands() {
+        for expression in ["1 +", "+ 1", "1 (+ 2)", "()", "2 * ()"] {
+            // given
+            let tokens = tokenize_line(expression).expect("Expected successful tokenization");
+
+            // then
+            assert!(run_expression::<u64>(&tokens).is_err(), "{}", expression);
+            assert!(
+                run_expression_advanced::<u64>(&tokens).is_err(),
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn run_expression_rejects_missing_operators() {
+        for expression in ["1 2", "(1) 2", "1 (2)", "(1)(2)"] {
+            // given
+            let tokens = tokenize_line(expression).expect("Expected successful tokenization");
+
+            // then
+            assert!(run_expression::<u64>(&tokens).is_err(), "{}", expression);
+            assert!(
+                run_expression_advanced::<u64>(&tokens).is_err(),
+                "{}",
+                expression
+            );
+        }
+    }
+}
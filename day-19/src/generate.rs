@@ -0,0 +1,112 @@
+use crate::Day19;
+use common::generate::{Generated, Generator, Rng};
+
+// the length of the words matched by rules 42 and 31
+const CHUNK_LENGTH: usize = 4;
+
+/// `size` is the number of messages. Rule 42 matches all words of four letters that start with
+/// `a`, rule 31 those that start with `b`, and rule 0 is `8 11` as in the real puzzle. So the
+/// generator can check each message chunk by chunk, without a parser. The other rules up to 42 are
+/// unused fillers.
+impl Generator for Day19 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut rules: Vec<String> = (0..=42)
+            .map(|id| match id {
+                0 => "0: 8 11".to_owned(),
+                1 => "1: \"a\"".to_owned(),
+                2 => "2: \"b\"".to_owned(),
+                // any letter, any two letters, any three letters
+                3 => "3: 1 | 2".to_owned(),
+                4 => "4: 3 3".to_owned(),
+                5 => "5: 3 4".to_owned(),
+                8 => "8: 42".to_owned(),
+                11 => "11: 42 31".to_owned(),
+                31 => "31: 2 5".to_owned(),
+                42 => "42: 1 5".to_owned(),
+                _ => format!("{}: {} {}", id, rng.between(1, 2), rng.between(1, 2)),
+            })
+            .collect();
+        rng.shuffle(&mut rules);
+
+        let mut messages = String::new();
+        let mut valid = 0;
+        let mut valid_with_loops = 0;
+        for _ in 0..size {
+            let length = if rng.chance(0.1) {
+                // a length that is not a multiple of the chunk length can never be valid
+                rng.index(4 * CHUNK_LENGTH - 1) + 1
+            } else {
+                (rng.index(6) + 1) * CHUNK_LENGTH
+            };
+            // start with a few rule 42 chunks, so that more messages are valid
+            let starts: Vec<bool> = (0..length / CHUNK_LENGTH)
+                .scan(true, |is_42, _| {
+                    *is_42 = *is_42 && rng.chance(0.7);
+                    Some(if rng.chance(0.1) { !*is_42 } else { *is_42 })
+                })
+                .collect();
+            let message: String = (0..length)
+                .map(|i| match starts.get(i / CHUNK_LENGTH) {
+                    Some(is_42) if i % CHUNK_LENGTH == 0 => {
+                        if *is_42 {
+                            'a'
+                        } else {
+                            'b'
+                        }
+                    }
+                    _ => *rng.choose(&['a', 'b']),
+                })
+                .collect();
+
+            if length % CHUNK_LENGTH == 0 {
+                let count_42 = starts.iter().take_while(|is_42| **is_42).count();
+                let count_31 = starts.len() - count_42;
+                let only_31_after_42 = starts[count_42..].iter().all(|is_42| !is_42);
+                if starts == [true, true, false] {
+                    valid += 1;
+                }
+                if only_31_after_42 && count_31 >= 1 && count_42 > count_31 {
+                    valid_with_loops += 1;
+                }
+            }
+            messages.push_str(&message);
+            messages.push('\n');
+        }
+
+        Generated {
+            input: format!("{}\n\n{}", rules.join("\n"), messages),
+            part1: Some(valid.to_string()),
+            part2: Some(valid_with_loops.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day19::generate(&mut Rng::new(seed), 50);
+
+            // when
+            let mismatches = check_planted::<Day19>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day19>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,377 @@
+//! Day 19: Monster Messages
+
+use common::parallel;
+use common::{end_of, parse_int, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+
+mod generate;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    // the rules (indexed by ID) and the received messages
+    type Input<'a> = (Vec<Rule>, Vec<&'a str>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((rules, messages): &Self::Input<'_>) -> Result<usize, String> {
+        count_valid_messages(rules, messages)
+    }
+
+    fn part2((rules, messages): &Self::Input<'_>) -> Result<usize, String> {
+        let modified_rules = modify_rules(rules.clone())?;
+        count_valid_messages(&modified_rules, messages)
+    }
+}
+
+/// Counts the messages that completely match rule 0.
+pub fn count_valid_messages(rules: &[Rule], messages: &[&str]) -> Result<usize, String> {
+    let rules_cnf = rules_to_cnf(rules)?;
+    let inverted_rules = inverse_rules(&rules_cnf);
+
+    Ok(
+        parallel::par_map(messages, |message| cyk(&inverted_rules, message))
+            .into_iter()
+            .filter(|valid| *valid)
+            .count(),
+    )
+}
+
+/// Replaces rules 8 and 11 with the looping rules of part 2.
+pub fn modify_rules(mut rules: Vec<Rule>) -> Result<Vec<Rule>, String> {
+    // sanity check first
+    if rules.len() < 12 {
+        return Err(format!(
+            "Unable to modify rule #11, there are only {} rules",
+            rules.len()
+        ));
+    }
+    if rules[8] != Rule::Sub(vec![RuleSubst::Mono(42)])
+        || rules[11] != Rule::Sub(vec![RuleSubst::Cat(42, 31)])
+    {
+        return Err("Rules to modify do not match expectations".to_owned());
+    }
+
+    rules[8] = Rule::Sub(vec![RuleSubst::Mono(42), RuleSubst::Cat(42, 8)]);
+    // our rules_to_cnf function does not handle splitting longer rules, but for this one case
+    // we can handle this here manually
+    rules[11] = Rule::Sub(vec![
+        RuleSubst::Cat(42, 31),
+        RuleSubst::Cat(42, rules.len()),
+    ]);
+    rules.push(Rule::Sub(vec![RuleSubst::Cat(11, 31)]));
+
+    Ok(rules)
+}
+
+/// Checks with the CYK algorithm whether rule 0 matches the word. The rules have to be in
+/// chomsky normal form and inverted, see `rules_to_cnf` and `inverse_rules`.
+// Yes, I know that the problem is regular
+// but the rules are already _almost_ in chomsky normal form and far from being a regular grammar
+// and even with a regular grammar I had to build a deterministic nfm…
+// TL;DR I am to lazy to solve this efficiently
+pub fn cyk(rules: &HashMap<RuleCnfLhs, Vec<usize>>, word: &str) -> bool {
+    let word_length = word.chars().count();
+    // a rule in chomsky normal form never produces the empty word
+    if word_length == 0 {
+        return false;
+    }
+    let mut v: Vec<HashSet<usize>> = (0..(word_length * word_length))
+        .map(|_| HashSet::new())
+        .collect();
+    for (i, c) in word.chars().enumerate() {
+        if let Some(rule_ids) = rules.get(&RuleCnfLhs::Lit(c)) {
+            for rule_id in rule_ids {
+                v[i].insert(*rule_id);
+            }
+        }
+    }
+    // this is the second-slowest implementation of the cyk algorithm I've ever seen
+    // and screw wikipedia for its 1-based fucking indexing!
+    for j in 1..word_length {
+        for i in 0..(word_length - j) {
+            for k in 0..j {
+                for rule_id in get_rules_matching(
+                    rules,
+                    &v[i + k * word_length],
+                    &v[i + k + 1 + (j - k - 1) * word_length],
+                ) {
+                    v[j * word_length + i].insert(rule_id);
+                }
+            }
+        }
+    }
+
+    v[word_length * (word_length - 1)].contains(&0)
+}
+
+fn get_rules_matching(
+    rules: &HashMap<RuleCnfLhs, Vec<usize>>,
+    vleft: &HashSet<usize>,
+    vright: &HashSet<usize>,
+) -> Vec<usize> {
+    let mut result: Vec<usize> = Vec::with_capacity(rules.len());
+    for b in vleft {
+        for c in vright {
+            if let Some(rule_ids) = rules.get(&RuleCnfLhs::Sub(*b, *c)) {
+                for rule_id in rule_ids {
+                    result.push(*rule_id);
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Converts the rules to chomsky normal form by resolving rules that only refer to a single
+/// other rule. Rules with more than two sub rules are not supported. An error if a single rule
+/// refers to a rule that does not exist, or leads back to itself.
+pub fn rules_to_cnf(in_rules: &[Rule]) -> Result<Vec<Vec<RuleCnfLhs>>, String> {
+    let mut chain: Vec<usize> = Vec::with_capacity(in_rules.len());
+    (0..in_rules.len())
+        .map(|rule_id| rule_to_cnf(rule_id, in_rules, &mut chain))
+        .collect()
+}
+
+// `chain` holds the rules being resolved, to detect loops of single rules
+fn rule_to_cnf(
+    rule_id: usize,
+    in_rules: &[Rule],
+    chain: &mut Vec<usize>,
+) -> Result<Vec<RuleCnfLhs>, String> {
+    if chain.contains(&rule_id) {
+        return Err(format!("Rule {} refers back to itself", rule_id));
+    }
+    match in_rules.get(rule_id) {
+        None => Err(format!("There is no rule {}", rule_id)),
+        Some(Rule::Lit(c)) => Ok(vec![RuleCnfLhs::Lit(*c)]),
+        Some(Rule::Sub(substitutions)) => {
+            chain.push(rule_id);
+            let mut cnf = Vec::with_capacity(substitutions.len());
+            for subst in substitutions {
+                match subst {
+                    RuleSubst::Cat(a, b) => cnf.push(RuleCnfLhs::Sub(*a, *b)),
+                    RuleSubst::Mono(a) => cnf.extend(rule_to_cnf(*a, in_rules, chain)?),
+                }
+            }
+            chain.pop();
+            Ok(cnf)
+        }
+    }
+}
+
+/// Maps each right hand side to the IDs of the rules that produce it.
+pub fn inverse_rules(rules: &[Vec<RuleCnfLhs>]) -> HashMap<RuleCnfLhs, Vec<usize>> {
+    rules.iter().enumerate().fold(
+        HashMap::with_capacity(rules.len() * 2),
+        |mut inverted, (rule_id, rule)| {
+            for lhs in rule {
+                inverted
+                    .entry(*lhs)
+                    .or_insert_with(|| Vec::with_capacity(rules.len()))
+                    .push(rule_id);
+            }
+            inverted
+        },
+    )
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A production of a rule in chomsky normal form: a literal or the concatenation of two rules
+pub enum RuleCnfLhs {
+    Lit(char),
+    Sub(usize, usize),
+}
+
+/// Parses the rules and the messages, which are separated by a blank line. The rules are indexed
+/// by their ID, the IDs must be an unbroken sequence starting at 0.
+pub fn parse_input(content: &str) -> Result<(Vec<Rule>, Vec<&str>), ParseError> {
+    let (rules, messages) = content
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::missing_field(end_of(content), "messages after rules"))?;
+    let rules_with_ids = rules
+        .split('\n')
+        .map(|line| parse_rule(line).map(|(id, rule)| (id, rule, line)))
+        .collect::<Result<Vec<(usize, Rule, &str)>, ParseError>>()?;
+    let rules = rules_indexed_by_id(rules_with_ids)?;
+    let messages = messages.split_terminator('\n').collect::<Vec<&str>>();
+
+    Ok((rules, messages))
+}
+
+// the lines are only kept for error messages
+fn rules_indexed_by_id(
+    mut rules_with_ids: Vec<(usize, Rule, &str)>,
+) -> Result<Vec<Rule>, ParseError> {
+    rules_with_ids.sort_unstable_by_key(|(id, _, _)| *id);
+
+    rules_with_ids
+        .into_iter()
+        .enumerate()
+        .map(|(expected_id, (actual_id, rule, line))| {
+            if expected_id != actual_id {
+                Err(ParseError::bad_layout(
+                    line,
+                    format!(
+                        "rule IDs are not an unbroken sequence, expected {} but got {}",
+                        expected_id, actual_id
+                    ),
+                ))
+            } else {
+                Ok(rule)
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A rule: either a literal character or alternatives of sub rules
+pub enum Rule {
+    Lit(char),
+    Sub(Vec<RuleSubst>),
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// One alternative of a rule: a single rule ID or the concatenation of two
+pub enum RuleSubst {
+    Mono(usize),
+    Cat(usize, usize),
+}
+
+fn parse_rule(line: &str) -> Result<(usize, Rule), ParseError> {
+    let (id, lhs) = line
+        .split_once(": ")
+        .ok_or_else(|| ParseError::missing_field(end_of(line), "left hand side of rule"))?;
+    let id = parse_int::<usize>(id)?;
+    let lhs = lhs.trim();
+
+    if let Some(literal) = lhs.strip_prefix('"') {
+        let c = literal
+            .chars()
+            .next()
+            .ok_or_else(|| ParseError::missing_field(literal, "literal"))?;
+        Ok((id, Rule::Lit(c)))
+    } else if let Ok(sub_id) = lhs.parse::<usize>() {
+        Ok((id, Rule::Sub(vec![RuleSubst::Mono(sub_id)])))
+    } else {
+        let options: Vec<RuleSubst> = lhs
+            .split(" | ")
+            .map(|ids_str| {
+                let mut ids = ids_str.splitn(2, ' ').map(parse_int::<usize>);
+                let first = ids
+                    .next()
+                    .unwrap_or_else(|| Err(ParseError::missing_field(ids_str, "rule ID")))?;
+                Ok(match ids.next().transpose()? {
+                    Some(second) => RuleSubst::Cat(first, second),
+                    None => RuleSubst::Mono(first),
+                })
+            })
+            .collect::<Result<Vec<RuleSubst>, ParseError>>()?;
+        Ok((id, Rule::Sub(options)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::generate::{Generator, Rng};
+    use common::ErrorKind;
+
+    #[test]
+    fn cyk_works_for_examples() {
+        // given
+        let (rules, words) = parse_input(
+            r#"0: 4 6
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: "a"
+5: "b"
+6: 1 5
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb"#,
+        )
+        .expect("Expected example input to parse");
+        let rules_cnf = rules_to_cnf(&rules).expect("Expected rules without loops");
+        let inv_rules = inverse_rules(&rules_cnf);
+
+        // when
+        let results: Vec<bool> = words.iter().map(|word| cyk(&inv_rules, word)).collect();
+
+        // then
+        assert_eq!(&results, &[true, false, true, false, false]);
+    }
+
+    #[test]
+    fn cyk_rejects_empty_word() {
+        // given
+        let rules = vec![Rule::Lit('a')];
+        let inv_rules = inverse_rules(&rules_to_cnf(&rules).expect("Expected a literal rule"));
+
+        // then
+        assert!(!cyk(&inv_rules, ""));
+    }
+
+    #[test]
+    fn rules_to_cnf_rejects_loops_of_single_rules() {
+        // given
+        let rules = vec![
+            Rule::Sub(vec![RuleSubst::Mono(1)]),
+            Rule::Sub(vec![RuleSubst::Mono(0)]),
+        ];
+
+        // then
+        assert_eq!(
+            rules_to_cnf(&rules),
+            Err("Rule 0 refers back to itself".to_owned())
+        );
+    }
+
+    #[test]
+    fn rules_to_cnf_rejects_unknown_rules() {
+        // given
+        let rules = vec![Rule::Sub(vec![RuleSubst::Mono(7)])];
+
+        // then
+        assert_eq!(rules_to_cnf(&rules), Err("There is no rule 7".to_owned()));
+    }
+
+    #[test]
+    fn count_valid_messages_counts_the_same_in_parallel() {
+        // given
+        let input = Day19::generate(&mut Rng::new(3), 200).input;
+        let (rules, messages) = parse_input(&input).expect("Expected valid generated input");
+        let rules = modify_rules(rules).expect("Expected rules 8 and 11 to be replaceable");
+        let sequential = count_valid_messages(&rules, &messages);
+
+        // when
+        let parallel = common::parallel::with_jobs(4, || count_valid_messages(&rules, &messages));
+
+        // then
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn parse_input_fails_for_gap_in_rule_ids() {
+        // given
+        let input = "0: 2 1\n2: \"a\"\n3: \"b\"\n\naab\n";
+
+        // when
+        let result = parse_input(input);
+
+        // then
+        let error = result
+            .map_err(|e| e.locate(input))
+            .expect_err("Expected parsing to fail");
+        assert!(matches!(error.kind(), ErrorKind::BadLayout { .. }));
+        assert_eq!(error.location().map(|l| l.line), Some(2));
+    }
+}
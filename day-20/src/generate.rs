@@ -0,0 +1,234 @@
+use crate::{Day20, TILE_SIZE, TILE_SIZE_IMG};
+use common::generate::{Generated, Generator, Rng};
+use std::collections::HashSet;
+
+const SEA_SERPENT: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+/// `size` is the number of tiles per row and column (3 to 12). The generator draws the whole
+/// picture first: the borders, the water and some sea serpents. Then it cuts the picture into
+/// tiles, rotates and flips each of them and shuffles them. All borders are distinct, even when
+/// flipped, so there is only one way to put the tiles back together.
+impl Generator for Day20 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let tiles_per_row = size.clamp(3, 12);
+        let img_width = tiles_per_row * TILE_SIZE_IMG;
+        // neighbouring tiles share their border row or column in the lattice
+        let lattice_width = tiles_per_row * (TILE_SIZE - 1) + 1;
+        let mut lattice = vec![false; lattice_width * lattice_width];
+        draw_borders(rng, &mut lattice, tiles_per_row);
+
+        let serpents = img_width * img_width / 150;
+        let (img, planted) = loop {
+            let (img, planted) = draw_image(rng, img_width, serpents);
+            if count_sea_serpents(&img, img_width) == planted {
+                break (img, planted);
+            }
+        };
+        for (i, px) in img.iter().enumerate() {
+            let (y, x) = (i / img_width, i % img_width);
+            lattice[(y + y / TILE_SIZE_IMG + 1) * lattice_width + x + x / TILE_SIZE_IMG + 1] = *px;
+        }
+        let roughness = img.iter().filter(|px| **px).count() - planted * 15;
+
+        let mut ids: Vec<u64> = Vec::with_capacity(tiles_per_row * tiles_per_row);
+        while ids.len() < tiles_per_row * tiles_per_row {
+            let id = rng.below(9000) + 1000;
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        let last = tiles_per_row - 1;
+        let corners = [0, last, last * tiles_per_row, last * tiles_per_row + last];
+        let corner_product: u64 = corners.iter().map(|i| ids[*i]).product();
+
+        let mut tiles: Vec<String> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let top = (i / tiles_per_row) * (TILE_SIZE - 1);
+                let left = (i % tiles_per_row) * (TILE_SIZE - 1);
+                let pixels: Vec<bool> = (0..TILE_SIZE * TILE_SIZE)
+                    .map(|j| lattice[(top + j / TILE_SIZE) * lattice_width + left + j % TILE_SIZE])
+                    .collect();
+                let pixels = transform(&pixels, TILE_SIZE, rng.index(8));
+                let rows: Vec<String> = pixels
+                    .chunks(TILE_SIZE)
+                    .map(|row| row.iter().map(|px| if *px { '#' } else { '.' }).collect())
+                    .collect();
+                format!("Tile {}:\n{}\n", id, rows.join("\n"))
+            })
+            .collect();
+        rng.shuffle(&mut tiles);
+
+        Generated {
+            input: tiles.join("\n"),
+            part1: Some(corner_product.to_string()),
+            part2: Some(roughness.to_string()),
+        }
+    }
+}
+
+// Sets the pixels on the borders of the tiles. The corner pixels are random, then each border
+// gets random pixels in between until it is neither the same as another border (in any direction)
+// nor a palindrome, which would match itself when flipped.
+fn draw_borders(rng: &mut Rng, lattice: &mut [bool], tiles_per_row: usize) {
+    let step = TILE_SIZE - 1;
+    let lattice_width = tiles_per_row * step + 1;
+    for y in (0..lattice_width).step_by(step) {
+        for x in (0..lattice_width).step_by(step) {
+            lattice[y * lattice_width + x] = rng.chance(0.5);
+        }
+    }
+
+    let mut used: HashSet<u16> = HashSet::new();
+    for horizontal in [true, false] {
+        for line in 0..=tiles_per_row {
+            for segment in 0..tiles_per_row {
+                let index = |i: usize| {
+                    let (y, x) = if horizontal {
+                        (line * step, segment * step + i)
+                    } else {
+                        (segment * step + i, line * step)
+                    };
+                    y * lattice_width + x
+                };
+                loop {
+                    for i in 1..step {
+                        lattice[index(i)] = rng.chance(0.5);
+                    }
+                    let border =
+                        (0..TILE_SIZE).fold(0u16, |b, i| (b << 1) | lattice[index(i)] as u16);
+                    let flipped = border.reverse_bits() >> (16 - TILE_SIZE);
+                    if border != flipped && !used.contains(&border) && !used.contains(&flipped) {
+                        used.insert(border);
+                        used.insert(flipped);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Draws water with some sea serpents, all facing the same way and not overlapping. Returns the
+// image and the number of serpents that fit.
+fn draw_image(rng: &mut Rng, width: usize, serpents: usize) -> (Vec<bool>, usize) {
+    let mut img: Vec<bool> = (0..width * width).map(|_| rng.chance(0.2)).collect();
+    let mut taken = vec![false; width * width];
+    let mut planted = 0;
+    for _ in 0..serpents * 10 {
+        if planted == serpents {
+            break;
+        }
+        let top = rng.index(width - SEA_SERPENT.len() + 1);
+        let left = rng.index(width - SEA_SERPENT[0].len() + 1);
+        let area: Vec<usize> = (0..SEA_SERPENT.len())
+            .flat_map(|y| (0..SEA_SERPENT[0].len()).map(move |x| (top + y) * width + left + x))
+            .collect();
+        if area.iter().any(|i| taken[*i]) {
+            continue;
+        }
+        for (i, c) in area.iter().zip(SEA_SERPENT.concat().chars()) {
+            taken[*i] = true;
+            if c == '#' {
+                img[*i] = true;
+            }
+        }
+        planted += 1;
+    }
+    (img, planted)
+}
+
+// the number of sea serpents in the orientation with the most matches, counting overlapping ones
+fn count_sea_serpents(img: &[bool], width: usize) -> usize {
+    let offsets: Vec<(i64, i64)> = SEA_SERPENT
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.char_indices()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| (y as i64, x as i64))
+        })
+        .collect();
+    (0..8)
+        .map(|orientation| {
+            let oriented: Vec<(i64, i64)> = offsets
+                .iter()
+                .map(|(y, x)| {
+                    let (y, x) = if orientation & 4 != 0 {
+                        (*x, *y)
+                    } else {
+                        (*y, *x)
+                    };
+                    let y = if orientation & 1 != 0 { -y } else { y };
+                    let x = if orientation & 2 != 0 { -x } else { x };
+                    (y, x)
+                })
+                .collect();
+            let w = width as i64;
+            (-w..w)
+                .flat_map(|top| (-w..w).map(move |left| (top, left)))
+                .filter(|(top, left)| {
+                    oriented.iter().all(|(y, x)| {
+                        let (y, x) = (top + y, left + x);
+                        (0..w).contains(&y) && (0..w).contains(&x) && img[(y * w + x) as usize]
+                    })
+                })
+                .count()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+// one of the eight rotations and flips of a square image
+fn transform(pixels: &[bool], width: usize, orientation: usize) -> Vec<bool> {
+    (0..width * width)
+        .map(|i| {
+            let (mut y, mut x) = (i / width, i % width);
+            if orientation & 1 != 0 {
+                y = width - 1 - y;
+            }
+            if orientation & 2 != 0 {
+                x = width - 1 - x;
+            }
+            if orientation & 4 != 0 {
+                std::mem::swap(&mut x, &mut y);
+            }
+            pixels[y * width + x]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..5 {
+            // given
+            let generated = Day20::generate(&mut Rng::new(seed), 4);
+
+            // when
+            let mismatches = check_planted::<Day20>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day20>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
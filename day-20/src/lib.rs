@@ -0,0 +1,661 @@
+//! Day 20: Jurassic Jigsaw
+
+use common::grid::Grid;
+use common::{parse_int, ParseError, Solution};
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+mod generate;
+
+/// Width and height of a tile, including its borders
+pub const TILE_SIZE: usize = 10;
+/// Width and height of the image data of a tile, without its borders
+pub const TILE_SIZE_IMG: usize = TILE_SIZE - 2;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    // all rotated and flipped variants of the tiles
+    type Input<'a> = Vec<Tile>;
+    type Answer1 = u64;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Tile>, ParseError> {
+        parse_tiles(input).map(tiles_rot_flipped)
+    }
+
+    fn part1(tile_variants: &Vec<Tile>) -> Result<u64, String> {
+        let tiled_img = solve_tile_puzzle(tile_variants)?;
+        Ok(prod_corner_ids(&tiled_img))
+    }
+
+    fn part2(tile_variants: &Vec<Tile>) -> Result<usize, String> {
+        let tiled_img = solve_tile_puzzle(tile_variants)?;
+        let img = img_from_tiled_img(&tiled_img);
+        Ok(count_water_roughness(&img))
+    }
+}
+
+/// A monochrome image. `true` is a `#` pixel.
+pub type Img = Grid<bool>;
+
+/// Counts the `#` pixels that are not part of a sea serpent.
+// important assumption: sea serpents do not overlap
+pub fn count_water_roughness(photo: &Img) -> usize {
+    let patterns = create_all_sea_serpent_patterns();
+    let n_sea_serpents = count_sea_serpents(photo, &patterns);
+    let total_white_pixels = photo.cells().iter().filter(|p| **p).count();
+    let sea_searpent_white_pixels = patterns[0].cells().iter().filter(|p| **p).count();
+
+    total_white_pixels - n_sea_serpents * sea_searpent_white_pixels
+}
+
+// Important: counts overlapping sea serpents as well
+fn count_sea_serpents(photo: &Img, patterns: &[Img]) -> usize {
+    patterns
+        .iter()
+        .map(|pattern| {
+            let mut count: usize = 0;
+            for top in 0..=(photo.height() - pattern.height()) {
+                for left in 0..=(photo.width() - pattern.width()) {
+                    if pattern_matches_at_pos(photo, pattern, top, left) {
+                        count += 1;
+                    }
+                }
+            }
+            count
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn pattern_matches_at_pos(img: &Img, pattern: &Img, top: usize, left: usize) -> bool {
+    if img.height() < top + pattern.height() || img.width() < left + pattern.width() {
+        return false;
+    }
+    pattern
+        .positions()
+        .all(|(x, y)| !pattern[(x, y)] || img[(left + x, top + y)])
+}
+
+fn create_all_sea_serpent_patterns() -> [Img; 8] {
+    create_sea_serpent_pattern().orientations()
+}
+
+fn create_sea_serpent_pattern() -> Img {
+    let raw = "                  # #    ##    ##    ### #  #  #  #  #  #   ";
+    Grid::from_vec(20, raw.chars().map(|c| c == '#').collect())
+}
+
+/// Puts the image data of the tiles together, without the borders.
+pub fn img_from_tiled_img(tiled_img: &TiledImg) -> Img {
+    let width = tiled_img.width * TILE_SIZE_IMG;
+    Grid::from_fn(width, width, |x, y| {
+        let tile = tiled_img.tiles[x / TILE_SIZE_IMG + (y / TILE_SIZE_IMG) * tiled_img.width];
+        tile.img_data[(x % TILE_SIZE_IMG, y % TILE_SIZE_IMG)]
+    })
+}
+
+/// The product of the IDs of the four corner tiles
+pub fn prod_corner_ids(img: &TiledImg) -> u64 {
+    if img.width == 0 {
+        return 1;
+    }
+    [
+        0,
+        img.width - 1,
+        img.width * (img.width - 1),
+        img.width * img.width - 1,
+    ]
+    .iter()
+    .map(|i| img.tiles[*i].id)
+    .product()
+}
+
+/// Returns all rotated and flipped variants of the tiles.
+// so I missed the part where the tiles can be rotated and flipped
+// given the code I already wrote, it's easier to just add the rotated/flipped version of the tiles
+// to the options (with the same tile ID, so only one of the variants is used)
+pub fn tiles_rot_flipped(tiles: Vec<Tile>) -> Vec<Tile> {
+    tiles
+        .into_iter()
+        // I can't get around this to_vec here for some reason
+        .flat_map(|tile| tile_rot_flipped(tile).to_vec())
+        .collect()
+}
+
+fn tile_rot_flipped(tile: Tile) -> [Tile; 8] {
+    let rot90 = rotate_tile_counterclock(&tile);
+    let rot180 = rotate_tile_counterclock(&rot90);
+    let rot270 = rotate_tile_counterclock(&rot180);
+
+    let flipped = flip_tile_hor(&tile);
+    let flipped_rot90 = rotate_tile_counterclock(&flipped);
+    let flipped_rot180 = rotate_tile_counterclock(&flipped_rot90);
+    let flipped_rot270 = rotate_tile_counterclock(&flipped_rot180);
+
+    [
+        tile,
+        rot90,
+        rot180,
+        rot270,
+        flipped,
+        flipped_rot90,
+        flipped_rot180,
+        flipped_rot270,
+    ]
+}
+
+pub fn rotate_tile_counterclock(tile: &Tile) -> Tile {
+    Tile {
+        id: tile.id,
+        border_top: tile.border_right,
+        border_left: flip_border(tile.border_top),
+        border_bottom: tile.border_left,
+        border_right: flip_border(tile.border_bottom),
+        img_data: tile.img_data.rotate_counterclockwise(),
+    }
+}
+
+pub fn flip_tile_hor(tile: &Tile) -> Tile {
+    Tile {
+        id: tile.id,
+        border_top: flip_border(tile.border_top),
+        border_left: tile.border_right,
+        border_bottom: flip_border(tile.border_bottom),
+        border_right: tile.border_left,
+        img_data: tile.img_data.flip_horizontal(),
+    }
+}
+
+fn flip_border(mut border: u16) -> u16 {
+    let mut result = 0;
+    for _ in 0..TILE_SIZE {
+        result <<= 1;
+        result |= border & 1;
+        border >>= 1;
+    }
+    result
+}
+
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// The tiles arranged into a square, row by row
+pub struct TiledImg<'a> {
+    pub tiles: Vec<&'a Tile>,
+    /// the number of tiles per row
+    pub width: usize,
+}
+
+/// Arranges the tiles so that the borders of all neighbouring tiles match. Expects all rotated
+/// and flipped variants of the tiles, see `tiles_rot_flipped`. Only one variant of each tile is
+/// used.
+pub fn solve_tile_puzzle(tiles: &[Tile]) -> Result<TiledImg<'_>, String> {
+    let img_width = stupid_int_sqrt(tiles.len() / 8)
+        .ok_or_else(|| format!("Cannot order {} tiles into a square", tiles.len() / 8))?;
+    let tiles_by_top: HashMap<u16, Vec<&Tile>> =
+        tiles
+            .iter()
+            .fold(HashMap::with_capacity(tiles.len()), |mut map, tile| {
+                map.entry(tile.border_top)
+                    .or_insert_with(|| Vec::with_capacity(tiles.len()))
+                    .push(tile);
+                map
+            });
+    let tiles_by_left: HashMap<u16, Vec<&Tile>> =
+        tiles
+            .iter()
+            .fold(HashMap::with_capacity(tiles.len()), |mut map, tile| {
+                map.entry(tile.border_left)
+                    .or_insert_with(|| Vec::with_capacity(tiles.len()))
+                    .push(tile);
+                map
+            });
+    let tiles_by_top_left: HashMap<(u16, u16), Vec<&Tile>> =
+        tiles
+            .iter()
+            .fold(HashMap::with_capacity(tiles.len()), |mut map, tile| {
+                map.entry((tile.border_top, tile.border_left))
+                    .or_insert_with(|| Vec::with_capacity(tiles.len()))
+                    .push(tile);
+                map
+            });
+    let mut seen_tile_ids: HashSet<u64> = HashSet::with_capacity(tiles.len() / 8);
+    let mut ordered_tiles: Vec<&Tile> = Vec::with_capacity(tiles.len() / 8);
+
+    for tile in tiles {
+        seen_tile_ids.insert(tile.id);
+        ordered_tiles.push(tile);
+        if append_matching_tiles(
+            &mut ordered_tiles,
+            &mut seen_tile_ids,
+            img_width,
+            &tiles_by_top,
+            &tiles_by_left,
+            &tiles_by_top_left,
+        ) {
+            return Ok(TiledImg {
+                width: img_width,
+                tiles: ordered_tiles,
+            });
+        }
+        ordered_tiles.pop();
+        seen_tile_ids.remove(&tile.id);
+    }
+
+    Err("Unable to find a valid tile pattern".to_owned())
+}
+
+fn append_matching_tiles<'a>(
+    ordered_tiles: &mut Vec<&'a Tile>,
+    seen_tile_ids: &mut HashSet<u64>,
+    img_width: usize,
+    tiles_by_top: &HashMap<u16, Vec<&'a Tile>>,
+    tiles_by_left: &HashMap<u16, Vec<&'a Tile>>,
+    tiles_by_top_left: &HashMap<(u16, u16), Vec<&'a Tile>>,
+) -> bool {
+    // sanity check: method should not be called without an initial tile
+    if ordered_tiles.is_empty() {
+        return false;
+    }
+    if ordered_tiles.len() >= img_width * img_width {
+        return true;
+    }
+    if ordered_tiles.len().is_multiple_of(img_width) {
+        if let Some(tiles) =
+            tiles_by_top.get(&ordered_tiles[ordered_tiles.len() - img_width].border_bottom)
+        {
+            for tile in tiles {
+                if seen_tile_ids.contains(&tile.id) {
+                    continue;
+                }
+                ordered_tiles.push(tile);
+                seen_tile_ids.insert(tile.id);
+                if append_matching_tiles(
+                    ordered_tiles,
+                    seen_tile_ids,
+                    img_width,
+                    tiles_by_top,
+                    tiles_by_left,
+                    tiles_by_top_left,
+                ) {
+                    return true;
+                }
+                ordered_tiles.pop();
+                seen_tile_ids.remove(&tile.id);
+            }
+        }
+    } else if ordered_tiles.len() / img_width == 0 {
+        if let Some(tiles) = tiles_by_left.get(&ordered_tiles[ordered_tiles.len() - 1].border_right)
+        {
+            for tile in tiles {
+                if seen_tile_ids.contains(&tile.id) {
+                    continue;
+                }
+                ordered_tiles.push(tile);
+                seen_tile_ids.insert(tile.id);
+                if append_matching_tiles(
+                    ordered_tiles,
+                    seen_tile_ids,
+                    img_width,
+                    tiles_by_top,
+                    tiles_by_left,
+                    tiles_by_top_left,
+                ) {
+                    return true;
+                }
+                ordered_tiles.pop();
+                seen_tile_ids.remove(&tile.id);
+            }
+        }
+    } else if let Some(tiles) = tiles_by_top_left.get(&(
+        ordered_tiles[ordered_tiles.len() - img_width].border_bottom,
+        ordered_tiles[ordered_tiles.len() - 1].border_right,
+    )) {
+        for tile in tiles {
+            if seen_tile_ids.contains(&tile.id) {
+                continue;
+            }
+            ordered_tiles.push(tile);
+            seen_tile_ids.insert(tile.id);
+            if append_matching_tiles(
+                ordered_tiles,
+                seen_tile_ids,
+                img_width,
+                tiles_by_top,
+                tiles_by_left,
+                tiles_by_top_left,
+            ) {
+                return true;
+            }
+            ordered_tiles.pop();
+            seen_tile_ids.remove(&tile.id);
+        }
+    }
+    false
+}
+
+fn stupid_int_sqrt(value: usize) -> Option<usize> {
+    (1..(value / 2)).find(|root| root * root == value)
+}
+
+/// Parses the tiles, which are separated by blank lines.
+pub fn parse_tiles(input: &str) -> Result<Vec<Tile>, ParseError> {
+    input.split_terminator("\n\n").map(parse_tile).collect()
+}
+
+/// Parses a single tile: the `Tile <id>:` line and the pixel rows.
+pub fn parse_tile(input: &str) -> Result<Tile, ParseError> {
+    let (id_line, pixel_rows) = input.split_once('\n').unwrap_or((input, ""));
+    let id = id_line.trim_start_matches("Tile ").trim_end_matches(':');
+    let id = parse_int::<u64>(id)?;
+    let pixel: Grid<bool> = Grid::parse(pixel_rows)?;
+    if pixel.width() != TILE_SIZE || pixel.height() != TILE_SIZE {
+        return Err(ParseError::bad_layout(
+            input,
+            format!(
+                "expected tile to be {}x{} pixel but was {}x{}",
+                TILE_SIZE,
+                TILE_SIZE,
+                pixel.width(),
+                pixel.height()
+            ),
+        ));
+    }
+
+    let mut border_top = 0;
+    let mut border_left = 0;
+    let mut border_bottom = 0;
+    let mut border_right = 0;
+
+    for i in 0..TILE_SIZE {
+        border_top <<= 1;
+        border_left <<= 1;
+        border_bottom <<= 1;
+        border_right <<= 1;
+        if pixel[(i, 0)] {
+            border_top |= 1;
+        }
+        if pixel[(i, TILE_SIZE - 1)] {
+            border_bottom |= 1;
+        }
+        if pixel[(0, i)] {
+            border_left |= 1;
+        }
+        if pixel[(TILE_SIZE - 1, i)] {
+            border_right |= 1;
+        }
+    }
+    let img_data = Grid::from_fn(TILE_SIZE_IMG, TILE_SIZE_IMG, |x, y| pixel[(x + 1, y + 1)]);
+
+    Ok(Tile {
+        id,
+        border_top,
+        border_left,
+        border_bottom,
+        border_right,
+        img_data,
+    })
+}
+
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A camera tile. The borders are stored as bits, read from left to right or from top to bottom.
+/// The image data does not include the borders.
+pub struct Tile {
+    pub id: u64,
+    pub border_top: u16,
+    pub border_left: u16,
+    pub border_bottom: u16,
+    pub border_right: u16,
+    pub img_data: Grid<bool>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::ErrorKind;
+
+    const EXAMPLE_INPUT: &str = r"Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...
+
+";
+
+    #[test]
+    fn rotate_tile_counterclock_rotates_correctly() {
+        // given
+        let mut img_data = Grid::new(TILE_SIZE_IMG, TILE_SIZE_IMG, false);
+        img_data[(0, 0)] = true;
+        img_data[(1, 1)] = true;
+        let tile = Tile {
+            id: 42,
+            border_top: 0b0001,
+            border_left: 0b0011,
+            border_bottom: 0b0111,
+            border_right: 0b1111,
+            img_data,
+        };
+
+        let mut expected_img_data = Grid::new(TILE_SIZE_IMG, TILE_SIZE_IMG, false);
+        expected_img_data[(0, TILE_SIZE_IMG - 1)] = true;
+        expected_img_data[(1, TILE_SIZE_IMG - 2)] = true;
+
+        // when
+        let rotated = rotate_tile_counterclock(&tile);
+
+        assert_eq!(
+            rotated,
+            Tile {
+                id: 42,
+                border_top: 0b0000001111,
+                border_left: 0b1000000000,
+                border_bottom: 0b0000000011,
+                border_right: 0b1110000000,
+                img_data: expected_img_data,
+            }
+        )
+    }
+
+    #[test]
+    fn flip_tile_hor_flips_correctly() {
+        // given
+        let mut img_data = Grid::new(TILE_SIZE_IMG, TILE_SIZE_IMG, false);
+        img_data[(0, 0)] = true;
+        img_data[(1, 1)] = true;
+        let tile = Tile {
+            id: 42,
+            border_top: 0b0001,
+            border_left: 0b0011,
+            border_bottom: 0b0111,
+            border_right: 0b1111,
+            img_data,
+        };
+
+        let mut expected_img_data = Grid::new(TILE_SIZE_IMG, TILE_SIZE_IMG, false);
+        expected_img_data[(TILE_SIZE_IMG - 1, 0)] = true;
+        expected_img_data[(TILE_SIZE_IMG - 2, 1)] = true;
+
+        // when
+        let flipped = flip_tile_hor(&tile);
+
+        // then
+        assert_eq!(
+            flipped,
+            Tile {
+                id: 42,
+                border_top: 0b1000000000,
+                border_left: 0b1111,
+                border_bottom: 0b1110000000,
+                border_right: 0b0011,
+                img_data: expected_img_data,
+            }
+        )
+    }
+
+    #[test]
+    fn solve_tile_puzzle_works_for_example() {
+        // given
+        let tiles = parse_tiles(EXAMPLE_INPUT).expect("expected example input to parse");
+        let tile_variants = tiles_rot_flipped(tiles);
+
+        // when
+        let result = solve_tile_puzzle(&tile_variants).expect("expected puzzle to be solved");
+
+        // then
+        assert_eq!(result.width, 3);
+        assert_eq!(result.tiles.len(), 9);
+        assert_eq!(prod_corner_ids(&result), 20899048083289);
+    }
+
+    #[test]
+    fn count_water_roughness_works_for_example() {
+        // given
+        let tiles = parse_tiles(EXAMPLE_INPUT).expect("expected example input to parse");
+        let tile_variants = tiles_rot_flipped(tiles);
+        let tiled_img = solve_tile_puzzle(&tile_variants).expect("expected puzzle to be solved");
+        let img = img_from_tiled_img(&tiled_img);
+
+        // when
+        println!("{}", img);
+        let roughness = count_water_roughness(&img);
+
+        // then
+        assert_eq!(roughness, 273);
+    }
+
+    #[test]
+    fn parse_tile_fails_for_tile_of_wrong_size() {
+        // given
+        let input = "Tile 42:\n#.#\n.#.\n#.#";
+
+        // when
+        let result = parse_tile(input);
+
+        // then
+        assert!(matches!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::BadLayout { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_tile_fails_for_invalid_id() {
+        // given
+        let input = "Tile x42:\n#.#\n.#.\n#.#";
+
+        // when
+        let result = parse_tile(input);
+
+        // then
+        assert_eq!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::UnexpectedToken {
+                found: "x42".to_owned(),
+                expected: "a number".to_owned()
+            })
+        );
+    }
+}
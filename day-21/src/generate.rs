@@ -0,0 +1,116 @@
+use crate::Day21;
+use common::generate::{Generated, Generator, Rng};
+use std::collections::BTreeSet;
+
+const ALLERGENS: [&str; 8] = [
+    "dairy", "eggs", "fish", "nuts", "peanuts", "sesame", "soy", "wheat",
+];
+
+/// `size` is the number of foods (at least 2). There is one allergen for every four foods, up to
+/// eight. Each allergen has two foods that only share its ingredient, so the ingredient of each
+/// allergen is known right away. The other foods list some of the allergens of their dangerous
+/// ingredients.
+impl Generator for Day21 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.max(2);
+        let allergens = &ALLERGENS[..(size / 4).clamp(1, ALLERGENS.len())];
+        let mut names: BTreeSet<String> = BTreeSet::new();
+        let mut new_name = |rng: &mut Rng| loop {
+            let name: String = (0..rng.between(3, 8))
+                .map(|_| (b'a' + rng.below(26) as u8) as char)
+                .collect();
+            if names.insert(name.clone()) {
+                break name;
+            }
+        };
+        let dangerous: Vec<String> = allergens.iter().map(|_| new_name(rng)).collect();
+        let safe: Vec<String> = (0..(size + 4)).map(|_| new_name(rng)).collect();
+
+        // (ingredients, allergens) of each food
+        let mut foods: Vec<(Vec<&str>, Vec<&str>)> = Vec::with_capacity(size);
+        for (allergen, ingredient) in allergens.iter().zip(&dangerous) {
+            let mut anchor_safe: Vec<&str> = safe.iter().map(|s| s as &str).collect();
+            rng.shuffle(&mut anchor_safe);
+            for half in anchor_safe.chunks(2 + rng.index(3)).take(2) {
+                let mut ingredients = half.to_vec();
+                ingredients.push(ingredient);
+                foods.push((ingredients, vec![*allergen]));
+            }
+        }
+        while foods.len() < size {
+            let mut ingredients: Vec<&str> = Vec::new();
+            let mut listed: Vec<&str> = Vec::new();
+            for (allergen, ingredient) in allergens.iter().zip(&dangerous) {
+                if rng.chance(0.4) {
+                    ingredients.push(ingredient);
+                    if rng.chance(0.6) {
+                        listed.push(allergen);
+                    }
+                }
+            }
+            if listed.is_empty() {
+                continue;
+            }
+            for _ in 0..rng.between(1, 6) {
+                let ingredient: &str = rng.choose(&safe).as_str();
+                if !ingredients.contains(&ingredient) {
+                    ingredients.push(ingredient);
+                }
+            }
+            foods.push((ingredients, listed));
+        }
+        rng.shuffle(&mut foods);
+
+        let mut input = String::new();
+        let mut safe_occurrences = 0;
+        for (mut ingredients, listed) in foods {
+            rng.shuffle(&mut ingredients);
+            safe_occurrences += ingredients
+                .iter()
+                .filter(|i| !dangerous.iter().any(|d| d == *i))
+                .count();
+            input.push_str(&format!(
+                "{} (contains {})\n",
+                ingredients.join(" "),
+                listed.join(", ")
+            ));
+        }
+
+        Generated {
+            input,
+            part1: Some(safe_occurrences.to_string()),
+            // the allergens are sorted, so the dangerous ingredients are as well
+            part2: Some(dangerous.join(",")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day21::generate(&mut Rng::new(seed), 40);
+
+            // when
+            let mismatches = check_planted::<Day21>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day21>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,183 @@
+//! Day 21: Allergen Assessment
+
+use common::{end_of, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+
+mod generate;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = Vec<Food<'a>>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Vec<Food<'_>>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(foods: &Vec<Food>) -> Result<usize, String> {
+        let safe_ingredients = find_safe_ingredients(foods);
+        Ok(count_ingredient_occurences(&safe_ingredients, foods))
+    }
+
+    // the canonical dangerous ingredient list
+    fn part2(foods: &Vec<Food>) -> Result<String, String> {
+        let ordered_dangerous_ingredients = get_ordered_dangerous_ingredients(foods)?;
+        let canonical_dangerous_ingredients_list: Vec<&str> = ordered_dangerous_ingredients
+            .iter()
+            .map(|(ing, _)| *ing)
+            .collect();
+        Ok(canonical_dangerous_ingredients_list.join(","))
+    }
+}
+
+/// Counts how often the ingredients appear in all foods.
+pub fn count_ingredient_occurences(ingredients: &HashSet<&str>, foods: &[Food]) -> usize {
+    foods
+        .iter()
+        .flat_map(|food| &food.ingredients)
+        .filter(|ing| ingredients.contains(*ing))
+        .count()
+}
+
+/// Finds the ingredients that can not contain any of the allergens.
+pub fn find_safe_ingredients<'a>(foods: &[Food<'a>]) -> HashSet<&'a str> {
+    let may_contain = create_may_contain(foods);
+
+    let mut ingredients: HashSet<&str> = foods
+        .iter()
+        .flat_map(|food| &food.ingredients)
+        .copied()
+        .collect();
+
+    for allergen_ingr in may_contain.values() {
+        ingredients.retain(|ing| !allergen_ingr.contains(ing));
+    }
+
+    ingredients
+}
+
+/// Finds the ingredient for each allergen. Returns (ingredient, allergen) pairs, sorted by
+/// allergen.
+pub fn get_ordered_dangerous_ingredients<'a>(
+    foods: &[Food<'a>],
+) -> Result<Vec<(&'a str, &'a str)>, String> {
+    let mut may_contain = create_may_contain(foods);
+    let mut contains: HashMap<&'a str, &'a str> = HashMap::with_capacity(may_contain.len());
+
+    let mut found = true;
+    while found {
+        found = false;
+        let mut found_ingredient: Option<&str> = None;
+        for (allergen, ingredients) in may_contain.iter() {
+            if ingredients.len() == 1 {
+                let ingredient = ingredients.iter().next().unwrap();
+                contains.insert(ingredient, allergen);
+                found = true;
+                found_ingredient = Some(ingredient);
+            }
+        }
+        if let Some(ingredient) = found_ingredient {
+            for ings in may_contain.values_mut() {
+                ings.remove(ingredient);
+            }
+        }
+    }
+
+    if contains.len() != may_contain.len() {
+        return Err("Unable to find an ingredient for all allergens".to_owned());
+    }
+
+    let mut result: Vec<(&str, &str)> = contains
+        .iter()
+        .map(|(ingr, allerg)| (*ingr, *allerg))
+        .collect();
+    result.sort_unstable_by_key(|(_, allergen)| *allergen);
+    Ok(result)
+}
+
+fn create_may_contain<'a>(foods: &[Food<'a>]) -> HashMap<&'a str, HashSet<&'a str>> {
+    let mut may_contain: HashMap<&str, HashSet<&str>> = HashMap::with_capacity(foods.len() * 2);
+    for food in foods {
+        for allergen in &food.allergens {
+            may_contain
+                .entry(allergen)
+                .and_modify(|ingr| *ingr = ingr.intersection(&food.ingredients).copied().collect())
+                .or_insert_with(|| food.ingredients.clone());
+        }
+    }
+    may_contain
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+/// The ingredients of a food and some of the allergens it contains
+pub struct Food<'a> {
+    pub ingredients: HashSet<&'a str>,
+    pub allergens: HashSet<&'a str>,
+}
+
+/// Parses the foods, one like `mxmxvkd kfcds (contains dairy, fish)` per line.
+pub fn parse_input(input: &str) -> Result<Vec<Food<'_>>, ParseError> {
+    input.split_terminator('\n').map(parse_food).collect()
+}
+
+fn parse_food(line: &str) -> Result<Food<'_>, ParseError> {
+    let (ingredients, allergens) = line
+        .split_once(" (contains ")
+        .ok_or_else(|| ParseError::missing_field(end_of(line), "allergens"))?;
+    let ingredients: HashSet<&str> = ingredients.split_whitespace().collect();
+    let allergens: HashSet<&str> = allergens.trim_end_matches(')').split(", ").collect();
+
+    Ok(Food {
+        ingredients,
+        allergens,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn find_safe_ingredients_works_for_example() {
+        // given
+        let input = r"mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+        let foods = parse_input(input).expect("Expected example input to parse");
+
+        // when
+        let result = find_safe_ingredients(&foods);
+        let safe_ingredient_occurences = count_ingredient_occurences(&result, &foods);
+
+        // then
+        assert_eq!(result.len(), 4);
+        assert!(result.contains("kfcds"));
+        assert!(result.contains("nhms"));
+        assert!(result.contains("sbzzf"));
+        assert!(result.contains("trh"));
+
+        assert_eq!(safe_ingredient_occurences, 5);
+    }
+
+    #[test]
+    fn get_ordered_dangerous_ingredients_works_for_example() {
+        // given
+        let input = r"mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+        let foods = parse_input(input).expect("Expected example input to parse");
+
+        // when
+        let result = get_ordered_dangerous_ingredients(&foods).expect("Expected success");
+
+        // then
+        assert_eq!(
+            result,
+            &[("mxmxvkd", "dairy"), ("sqjhc", "fish"), ("fvjkl", "soy")]
+        );
+    }
+}
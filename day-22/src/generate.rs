@@ -0,0 +1,92 @@
+use crate::Day22;
+use common::generate::{Generated, Generator, Rng};
+use std::collections::VecDeque;
+
+// games of combat that take longer than this are assumed to never end
+const MAX_ROUNDS: usize = 100_000;
+
+/// `size` is the number of cards (2 to 50, like the real puzzle), dealt evenly to both players. Part 1 is planted
+/// by playing combat on the generated decks, decks that make the game go on forever are dealt
+/// again. Part 2 is not planted.
+impl Generator for Day22 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        // recursive combat gets very slow with more cards
+        let size = size.clamp(2, 50) / 2 * 2;
+        loop {
+            let mut cards: Vec<u64> = (1..=size as u64).collect();
+            rng.shuffle(&mut cards);
+            let (deck1, deck2) = cards.split_at(size / 2);
+            if let Some(score) = play_combat(deck1, deck2) {
+                break Generated {
+                    input: format!("Player 1:\n{}\nPlayer 2:\n{}", list(deck1), list(deck2)),
+                    part1: Some(score.to_string()),
+                    part2: None,
+                };
+            }
+        }
+    }
+}
+
+// the decks start with their top card
+fn play_combat(deck1: &[u64], deck2: &[u64]) -> Option<u64> {
+    let mut deck1: VecDeque<u64> = deck1.iter().copied().collect();
+    let mut deck2: VecDeque<u64> = deck2.iter().copied().collect();
+    for _ in 0..MAX_ROUNDS {
+        match (deck1.pop_front(), deck2.pop_front()) {
+            (Some(a), Some(b)) if a > b => deck1.extend([a, b]),
+            (Some(a), Some(b)) => deck2.extend([b, a]),
+            (Some(a), None) => {
+                deck1.push_front(a);
+                return Some(score(&deck1));
+            }
+            (None, Some(b)) => {
+                deck2.push_front(b);
+                return Some(score(&deck2));
+            }
+            (None, None) => return None,
+        }
+    }
+    None
+}
+
+fn score(deck: &VecDeque<u64>) -> u64 {
+    deck.iter()
+        .rev()
+        .zip(1..)
+        .map(|(card, factor)| card * factor)
+        .sum()
+}
+
+fn list(deck: &[u64]) -> String {
+    deck.iter().map(|card| format!("{}\n", card)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day22::generate(&mut Rng::new(seed), 20);
+
+            // when
+            let mismatches = check_planted::<Day22>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day22>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
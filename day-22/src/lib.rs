@@ -0,0 +1,254 @@
+//! Day 22: Crab Combat
+
+use common::{end_of, parse_int, ParseError, Solution};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+mod generate;
+mod trace;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    // both decks, the top card is at the back
+    type Input<'a> = (VecDeque<u64>, VecDeque<u64>);
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((deck1, deck2): &Self::Input<'_>) -> Result<u64, String> {
+        find_winner_score(deck1.clone(), deck2.clone())
+    }
+
+    fn part2((deck1, deck2): &Self::Input<'_>) -> Result<u64, String> {
+        let mut solved = HashMap::with_capacity(1000);
+        let rc_deck = recursive_combat(deck1.clone(), deck2.clone(), &mut solved).1;
+        score_deck(&rc_deck)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// The winner of a game
+pub enum Player {
+    You,
+    Crab,
+}
+
+/// Plays a game of recursive combat. Returns the winner and their deck. `solved` remembers the
+/// winners of sub games.
+pub fn recursive_combat(
+    deck1: VecDeque<u64>,
+    deck2: VecDeque<u64>,
+    solved: &mut HashMap<(VecDeque<u64>, VecDeque<u64>), Player>,
+) -> (Player, VecDeque<u64>) {
+    recursive_combat_with(deck1, deck2, solved, &mut |_, _| ())
+}
+
+/// Like `recursive_combat`, calls `on_round` with both decks after each round of this game (but
+/// not of the sub games).
+pub fn recursive_combat_with(
+    mut deck1: VecDeque<u64>,
+    mut deck2: VecDeque<u64>,
+    solved: &mut HashMap<(VecDeque<u64>, VecDeque<u64>), Player>,
+    on_round: &mut dyn FnMut(&VecDeque<u64>, &VecDeque<u64>),
+) -> (Player, VecDeque<u64>) {
+    let mut seen: HashSet<(VecDeque<u64>, VecDeque<u64>)> = HashSet::with_capacity(200);
+    // ok, so how can I look up a tuple in a hashset without moving the values to the tuple?
+    while !seen.contains(&(deck1.clone(), deck2.clone())) {
+        if deck1.is_empty() {
+            return (Player::Crab, deck2);
+        } else if deck2.is_empty() {
+            return (Player::You, deck1);
+        }
+        seen.insert((deck1.clone(), deck2.clone()));
+        let card1 = deck1.pop_back().unwrap();
+        let card2 = deck2.pop_back().unwrap();
+        if (card1 as usize) > deck1.len() || (card2 as usize) > deck2.len() {
+            if card1 > card2 {
+                deck1.push_front(card1);
+                deck1.push_front(card2);
+            } else {
+                deck2.push_front(card2);
+                deck2.push_front(card1);
+            }
+        } else {
+            let rec_deck1: VecDeque<u64> = deck1
+                .range((deck1.len() - (card1 as usize))..)
+                .copied()
+                .collect();
+            let rec_deck2: VecDeque<u64> = deck2
+                .range((deck2.len() - (card2 as usize))..)
+                .copied()
+                .collect();
+            // ok, so how can I look up a tuple in a hashset without moving the values to the tuple?
+            let winner = solved
+                .get(&(rec_deck1.clone(), rec_deck2.clone()))
+                .copied()
+                .unwrap_or_else(|| {
+                    let (recursive_winner, _) =
+                        recursive_combat(rec_deck1.clone(), rec_deck2.clone(), solved);
+                    solved.insert((rec_deck1.clone(), rec_deck2.clone()), recursive_winner);
+                    recursive_winner
+                });
+            match winner {
+                Player::You => {
+                    deck1.push_front(card1);
+                    deck1.push_front(card2);
+                }
+                Player::Crab => {
+                    deck2.push_front(card2);
+                    deck2.push_front(card1);
+                }
+            }
+        };
+        on_round(&deck1, &deck2);
+    }
+    (Player::You, deck1)
+}
+
+/// Plays a game of combat and returns the score of the winner. Unlike recursive combat, there is no
+/// rule against repeated rounds, so a game that comes back to the same decks is an error.
+pub fn find_winner_score(deck1: VecDeque<u64>, deck2: VecDeque<u64>) -> Result<u64, String> {
+    find_winner_score_with(deck1, deck2, |_, _| ())
+}
+
+/// Like `find_winner_score`, calls `on_round` with both decks after each round.
+pub fn find_winner_score_with(
+    mut deck1: VecDeque<u64>,
+    mut deck2: VecDeque<u64>,
+    mut on_round: impl FnMut(&VecDeque<u64>, &VecDeque<u64>),
+) -> Result<u64, String> {
+    let mut seen: HashSet<(VecDeque<u64>, VecDeque<u64>)> = HashSet::with_capacity(200);
+    while !deck1.is_empty() && !deck2.is_empty() {
+        if !seen.insert((deck1.clone(), deck2.clone())) {
+            return Err("The game never ends, the decks repeat".to_owned());
+        }
+        let card1 = deck1.pop_back().unwrap();
+        let card2 = deck2.pop_back().unwrap();
+        if card1 > card2 {
+            deck1.push_front(card1);
+            deck1.push_front(card2);
+        } else {
+            deck2.push_front(card2);
+            deck2.push_front(card1);
+        }
+        on_round(&deck1, &deck2);
+    }
+
+    let winner = if deck1.len() < deck2.len() {
+        deck2
+    } else {
+        deck1
+    };
+
+    score_deck(&winner)
+}
+
+/// The score of a deck, the top card is at the back. An error if it does not fit into a u64.
+pub fn score_deck(deck: &VecDeque<u64>) -> Result<u64, String> {
+    deck.iter()
+        .zip(1..)
+        .try_fold(0u64, |score, (card, pos)| {
+            card.checked_mul(pos)?.checked_add(score)
+        })
+        .ok_or_else(|| "Overflow computing the score".to_owned())
+}
+
+/// Parses the decks of both players, the top card is at the back.
+pub fn parse_input(input: &str) -> Result<(VecDeque<u64>, VecDeque<u64>), ParseError> {
+    let mut split = input.split_terminator("\n\n");
+    let deck1 = parse_deck(
+        split
+            .next()
+            .ok_or_else(|| ParseError::missing_field(input, "deck of player 1"))?
+            .trim_start_matches("Player 1:\n"),
+    )?;
+    let deck2 = parse_deck(
+        split
+            .next()
+            .ok_or_else(|| ParseError::missing_field(end_of(input), "deck of player 2"))?
+            .trim_start_matches("Player 2:\n"),
+    )?;
+
+    Ok((deck1, deck2))
+}
+
+fn parse_deck(input: &str) -> Result<VecDeque<u64>, ParseError> {
+    input.split_terminator('\n').rev().map(parse_int).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn find_winner_score_works_for_example() {
+        // given
+        let (deck1, deck2) = parse_input(
+            r"Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10",
+        )
+        .expect("Expected example input to parse");
+
+        // when
+        let score = find_winner_score(deck1, deck2);
+
+        // then
+        assert_eq!(score, Ok(306));
+    }
+
+    #[test]
+    fn find_winner_score_fails_for_endless_game() {
+        // given
+        let (deck1, deck2) = parse_input("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14\n")
+            .expect("Expected valid input");
+
+        // when
+        let score = find_winner_score(deck1, deck2);
+
+        // then
+        assert!(score.is_err());
+    }
+
+    #[test]
+    fn recursive_combat_works_for_example() {
+        // given
+        let (deck1, deck2) = parse_input(
+            r"Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10",
+        )
+        .expect("Expected example input to parse");
+
+        // when
+        let mut solved = HashMap::with_capacity(1000);
+        let winner = recursive_combat(deck1, deck2, &mut solved);
+
+        // then
+        let winning_deck = winner.1;
+        assert_eq!(score_deck(&winning_deck), Ok(291));
+    }
+}
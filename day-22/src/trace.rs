@@ -0,0 +1,97 @@
+use crate::{find_winner_score_with, recursive_combat_with, Day22};
+use common::trace::{Frame, Picture, Trace};
+use std::collections::{HashMap, VecDeque};
+
+/// One frame per round with the decks of both players, like in the puzzle description. For
+/// recursive combat, only the rounds of the outermost game are recorded.
+impl Trace for Day22 {
+    fn trace(
+        (deck1, deck2): &(VecDeque<u64>, VecDeque<u64>),
+        part: usize,
+    ) -> Result<Vec<Frame>, String> {
+        let mut frames = vec![Frame::new("start", decks(deck1, deck2))];
+        let mut on_round = |deck1: &VecDeque<u64>, deck2: &VecDeque<u64>| {
+            frames.push(Frame::new(
+                format!("round {}", frames.len()),
+                decks(deck1, deck2),
+            ));
+        };
+        if part == 1 {
+            find_winner_score_with(deck1.clone(), deck2.clone(), on_round)?;
+        } else {
+            recursive_combat_with(
+                deck1.clone(),
+                deck2.clone(),
+                &mut HashMap::new(),
+                &mut on_round,
+            );
+        }
+        Ok(frames)
+    }
+}
+
+fn decks(deck1: &VecDeque<u64>, deck2: &VecDeque<u64>) -> Picture {
+    // the top card is at the back
+    let cards = |deck: &VecDeque<u64>| {
+        deck.iter()
+            .rev()
+            .map(|card| card.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    Picture::Text(format!(
+        "Player 1's deck: {}\nPlayer 2's deck: {}\n",
+        cards(deck1),
+        cards(deck2)
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    const EXAMPLE_INPUT: &str = "Player 1:\n9\n2\n6\n3\n1\n\nPlayer 2:\n5\n8\n4\n7\n10\n";
+
+    #[test]
+    fn trace_shows_decks_of_each_round() {
+        // given
+        let decks = parse_input(EXAMPLE_INPUT).expect("Expected valid example input");
+
+        // when
+        let frames = Day22::trace(&decks, 1).expect("Expected a trace");
+
+        // then
+        assert_eq!(frames.len(), 30);
+        assert_eq!(
+            frames[1].picture,
+            Picture::Text(
+                "Player 1's deck: 2, 6, 3, 1, 9, 5\nPlayer 2's deck: 8, 4, 7, 10\n".to_owned()
+            )
+        );
+        assert_eq!(
+            frames[29].picture,
+            Picture::Text(
+                "Player 1's deck: \nPlayer 2's deck: 3, 2, 10, 6, 8, 5, 9, 4, 7, 1\n".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn trace_of_recursive_combat_ends_with_winner() {
+        // given
+        let decks = parse_input(EXAMPLE_INPUT).expect("Expected valid example input");
+
+        // when
+        let frames = Day22::trace(&decks, 2).expect("Expected a trace");
+
+        // then
+        assert_eq!(frames.len(), 18);
+        assert_eq!(
+            frames[17].picture,
+            Picture::Text(
+                "Player 1's deck: \nPlayer 2's deck: 7, 5, 6, 2, 4, 1, 10, 8, 9, 3\n".to_owned()
+            )
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::Day23;
+use common::generate::{Generated, Generator, Rng};
+use common::Params;
+
+/// `size` is ignored, there are always the cups 1 to 9 in a random order. Part 1 is planted by
+/// making the 100 moves on a plain list of cups, part 2 is not planted.
+impl Generator for Day23 {
+    fn generate(rng: &mut Rng, _size: usize) -> Generated {
+        let mut cups: Vec<u64> = (1..=9).collect();
+        rng.shuffle(&mut cups);
+        let input: String = cups.iter().map(|cup| cup.to_string()).collect();
+
+        // the current cup is always the first one in the list
+        let mut circle = cups;
+        for _ in 0..100 {
+            let current = circle[0];
+            let picked: Vec<u64> = circle.drain(1..4).collect();
+            let destination = (1..9)
+                .map(|i| (current + 9 - 1 - i) % 9 + 1)
+                .find(|label| !picked.contains(label))
+                .unwrap_or(current);
+            let position = circle
+                .iter()
+                .position(|cup| *cup == destination)
+                .unwrap_or(0);
+            circle.splice((position + 1)..(position + 1), picked);
+            circle.rotate_left(1);
+        }
+        let one = circle.iter().position(|cup| *cup == 1).unwrap_or(0);
+        circle.rotate_left(one);
+        let after_one: String = circle[1..].iter().map(|cup| cup.to_string()).collect();
+
+        Generated {
+            input: format!("{}\n", input),
+            part1: Some(after_one),
+            part2: None,
+        }
+    }
+
+    // ten million moves for each mutated input would take ages
+    fn fuzz_params() -> Params {
+        let mut params = Params::new();
+        params.insert("moves2", "1000");
+        params
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::Solution;
+
+    #[test]
+    fn generated_input_has_planted_answer_for_part1() {
+        for seed in 0..10 {
+            // given
+            let generated = Day23::generate(&mut Rng::new(seed), 9);
+            let game = Day23::parse(&generated.input).expect("Expected valid generated input");
+
+            // when
+            let answer = Day23::part1(&game);
+
+            // then
+            assert_eq!(answer.ok(), generated.part1);
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day23>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,228 @@
+//! Day 23: Crab Cups
+
+use common::{end_of, Params, ParseError, Solution};
+
+mod generate;
+
+pub struct Day23;
+
+/// The labels of the cups in clockwise order and how many moves to make for each part
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Game {
+    pub cups: Vec<u64>,
+    pub moves_part1: usize,
+    pub moves_part2: usize,
+}
+
+impl Solution for Day23 {
+    type Input<'a> = Game;
+    // the labels after cup 1, concatenated
+    type Answer1 = String;
+    type Answer2 = u64;
+
+    /// `start` replaces the cup labels from the input, `moves1` and `moves2` are the moves to
+    /// make for part 1 (default: 100) and part 2 (default: 10 000 000)
+    const PARAMS: &'static [&'static str] = &["start", "moves1", "moves2"];
+
+    fn parse(input: &str) -> Result<Game, ParseError> {
+        Self::parse_with_params(input, &Params::new())
+    }
+
+    fn parse_with_params(input: &str, params: &Params) -> Result<Game, ParseError> {
+        Ok(Game {
+            cups: parse_cups(params.get_str("start").unwrap_or(input))?,
+            moves_part1: params.get_int("moves1", 100)?,
+            moves_part2: params.get_int("moves2", 10_000_000)?,
+        })
+    }
+
+    fn part1(game: &Game) -> Result<String, String> {
+        let cups_after_moves = result_puzzle_1(&do_n_steps(game.moves_part1, &game.cups)?)?;
+        Ok(cups_after_moves
+            .iter()
+            .map(|label| label.to_string())
+            .collect())
+    }
+
+    fn part2(game: &Game) -> Result<u64, String> {
+        // There are probably better ways. But I will try the brute force way first.
+        let million_cups: Vec<u64> = game.cups.iter().copied().chain(10..=1_000_000).collect();
+        let cups_after_moves = do_n_steps(game.moves_part2, &million_cups)?;
+        result_puzzle_2(&cups_after_moves)
+    }
+}
+
+/// Parses the labels of the cups, e.g. "389125467". Each of the labels 1 to 9 must appear
+/// exactly once, finding a cup by its label relies on that.
+fn parse_cups(input: &str) -> Result<Vec<u64>, ParseError> {
+    let input = input.trim();
+    let mut cups: Vec<u64> = Vec::with_capacity(9);
+    for (i, c) in input.char_indices() {
+        let label = &input[i..i + c.len_utf8()];
+        match c.to_digit(10) {
+            Some(0) | None => {
+                return Err(ParseError::unexpected_token(
+                    label,
+                    "a cup label from 1 to 9",
+                ))
+            }
+            Some(digit) if cups.contains(&(digit as u64)) => {
+                return Err(ParseError::bad_layout(label, "the cup appears twice"))
+            }
+            Some(digit) => cups.push(digit as u64),
+        }
+    }
+    if cups.len() != 9 {
+        return Err(ParseError::missing_field(
+            end_of(input),
+            "the cups labeled 1 to 9",
+        ));
+    }
+    Ok(cups)
+}
+
+/// Makes `n` moves. Returns the cups as a linked list: for each cup, the index of the next cup
+/// and its label.
+pub fn do_n_steps(n: usize, cups: &[u64]) -> Result<Vec<(usize, u64)>, String> {
+    let mut links: Vec<(usize, u64)> = cups
+        .iter()
+        .enumerate()
+        .map(|(i, label)| ((i + 1) % cups.len(), *label))
+        .collect();
+    let mut current = 0;
+    for _ in 0..n {
+        current = do_step(&mut links as &mut [(usize, u64)], current)?;
+    }
+
+    Ok(links)
+}
+
+/// The labels of the cups after cup 1, clockwise
+pub fn result_puzzle_1(links: &[(usize, u64)]) -> Result<Vec<u64>, String> {
+    let mut index = links
+        .iter()
+        .find(|(_, label)| *label == 1)
+        .ok_or_else(|| "Unable to find cup with label 1".to_owned())?
+        .0;
+
+    let mut result: Vec<u64> = Vec::with_capacity(links.len() - 1);
+    while links[index].1 != 1 {
+        result.push(links[index].1);
+        index = links[index].0;
+    }
+    Ok(result)
+}
+
+/// The product of the labels of the two cups after cup 1
+pub fn result_puzzle_2(links: &[(usize, u64)]) -> Result<u64, String> {
+    let index1 = links
+        .iter()
+        .find(|(_, label)| *label == 1)
+        .ok_or_else(|| "Unable to find cup with label 1".to_owned())?
+        .0;
+    let (index2, label1) = links[index1];
+    let label2 = links[index2].1;
+    Ok(label1 * label2)
+}
+
+fn do_step(links: &mut [(usize, u64)], current: usize) -> Result<usize, String> {
+    let first_removed_index = links[current].0;
+    let first_removed: (usize, u64) = links[first_removed_index];
+    let second_removed: (usize, u64) = links[first_removed.0];
+    let third_removed: (usize, u64) = links[second_removed.0];
+    let removed_labels = [first_removed.1, second_removed.1, third_removed.1];
+    links[current].0 = third_removed.0;
+
+    let destination_index = find_destination_index(links, current, &removed_labels)?;
+    let prev_destination_successor = links[destination_index].0;
+    links[destination_index].0 = first_removed_index;
+    links[second_removed.0].0 = prev_destination_successor;
+
+    Ok(links[current].0)
+}
+
+fn find_destination_index(
+    links: &[(usize, u64)],
+    current: usize,
+    removed_labels: &[u64],
+) -> Result<usize, String> {
+    let mut destination_label = next_destination_label(links[current].1, links.len() as u64);
+    while removed_labels.contains(&destination_label) {
+        destination_label = next_destination_label(destination_label, links.len() as u64);
+    }
+    find_label_index(links, destination_label)
+        .ok_or_else(|| format!("Unable to find destination label {}", destination_label))
+}
+
+fn find_label_index(links: &[(usize, u64)], label: u64) -> Option<usize> {
+    if label < 10 {
+        links
+            .iter()
+            .enumerate()
+            .find(|(_, (_, l))| *l == label)
+            .map(|(i, _)| i)
+    } else {
+        Some(label as usize - 1)
+    }
+}
+
+fn next_destination_label(label: u64, max_label: u64) -> u64 {
+    if label <= 1 {
+        max_label
+    } else {
+        label - 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::ErrorKind;
+
+    #[test]
+    fn do_n_steps_works_for_example_10() {
+        // given
+        let cups = &[3, 8, 9, 1, 2, 5, 4, 6, 7];
+
+        // when
+        let result = do_n_steps(10, cups).and_then(|links| result_puzzle_1(&links));
+
+        // then
+        assert_eq!(result, Ok(vec![9, 2, 6, 5, 8, 3, 7, 4]))
+    }
+
+    #[test]
+    fn parse_with_params_reads_cups_and_moves() {
+        // given
+        let mut params = Params::new();
+        params.insert("moves1", "10");
+
+        // when
+        let result = Day23::parse_with_params("389125467\n", &params);
+
+        // then
+        assert_eq!(
+            result,
+            Ok(Game {
+                cups: vec![3, 8, 9, 1, 2, 5, 4, 6, 7],
+                moves_part1: 10,
+                moves_part2: 10_000_000,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_fails_for_repeated_cup() {
+        // given
+        let input = "389125463";
+
+        // when
+        let result = Day23::parse(input);
+
+        // then
+        assert!(matches!(
+            result.map_err(|e| e.kind().clone()),
+            Err(ErrorKind::BadLayout { .. })
+        ));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::Day24;
+use common::generate::{Generated, Generator, Rng};
+use std::collections::HashMap;
+
+// the steps with their axial coordinates, like in `tile_pos`
+const STEPS: [(&str, (i64, i64)); 6] = [
+    ("e", (1, 0)),
+    ("w", (-1, 0)),
+    ("ne", (1, -1)),
+    ("nw", (0, -1)),
+    ("se", (0, 1)),
+    ("sw", (-1, 1)),
+];
+
+/// `size` is the number of lines, i.e. the number of flips. The lines lead to about half as many
+/// different tiles, so some tiles are flipped more than once. Each line is a random detour
+/// followed by the way to its tile, shuffled. Part 1 is planted, part 2 is not.
+impl Generator for Day24 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let targets: Vec<(i64, i64)> = (0..(size / 2).max(1))
+            .map(|_| (rng.between(-8, 8), rng.between(-8, 8)))
+            .collect();
+        let mut flips: HashMap<(i64, i64), usize> = HashMap::new();
+        let mut input = String::new();
+        for _ in 0..size {
+            let target = *rng.choose(&targets);
+            *flips.entry(target).or_insert(0) += 1;
+
+            let mut steps: Vec<&str> = Vec::new();
+            let (mut x, mut y) = (0, 0);
+            for _ in 0..rng.index(10) {
+                let (name, (dx, dy)) = *rng.choose(&STEPS);
+                steps.push(name);
+                x += dx;
+                y += dy;
+            }
+            let horizontal = if target.0 > x { "e" } else { "w" };
+            let vertical = if target.1 > y { "se" } else { "nw" };
+            steps.extend((0..(target.0 - x).abs()).map(|_| horizontal));
+            steps.extend((0..(target.1 - y).abs()).map(|_| vertical));
+            rng.shuffle(&mut steps);
+
+            input.push_str(&steps.concat());
+            input.push('\n');
+        }
+        let black = flips.values().filter(|n| *n % 2 == 1).count();
+
+        Generated {
+            input,
+            part1: Some(black.to_string()),
+            part2: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..3 {
+            // given
+            let generated = Day24::generate(&mut Rng::new(seed), 40);
+
+            // when
+            let mismatches = check_planted::<Day24>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day24>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,273 @@
+//! Day 24: Lobby Layout
+
+use common::automaton::{Automaton, Hex, LifeLike};
+use common::{end_of, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+mod generate;
+mod trace;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input<'a> = Vec<Vec<Dir>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Vec<Dir>>, ParseError> {
+        parse_tiles(input)
+    }
+
+    fn part1(tile_directions: &Vec<Vec<Dir>>) -> Result<usize, String> {
+        let tile_positions = get_tile_positions(tile_directions);
+        Ok(get_black_tiles(&tile_positions).len())
+    }
+
+    fn part2(tile_directions: &Vec<Vec<Dir>>) -> Result<usize, String> {
+        let tile_positions = get_tile_positions(tile_directions);
+        let black_tiles = get_black_tiles(&tile_positions);
+        Ok(run_100_days(black_tiles).len())
+    }
+}
+
+/// Flips the tiles for 100 days. Returns the positions of the black tiles.
+pub fn run_100_days(black_tiles: HashSet<(i64, i64)>) -> HashSet<(i64, i64)> {
+    let mut lobby = lobby(black_tiles);
+    lobby.run(100);
+    lobby.cells().map(|(pos, _)| pos).collect()
+}
+
+/// The lobby floor as a cellular automaton: a black tile (`true`) with zero or more than two
+/// black neighbours is flipped to white, a white tile with exactly two black neighbours is
+/// flipped to black.
+pub fn lobby(black_tiles: HashSet<(i64, i64)>) -> Automaton<Hex, bool, LifeLike> {
+    Automaton::new(
+        Hex,
+        LifeLike::new(&[2], &[1, 2]),
+        false,
+        black_tiles.into_iter().map(|pos| (pos, true)),
+    )
+}
+
+/// The tiles that were flipped an odd number of times
+pub fn get_black_tiles(tiles: &HashMap<(i64, i64), usize>) -> HashSet<(i64, i64)> {
+    tiles
+        .iter()
+        .filter(|(_, n)| **n % 2 == 1)
+        .map(|(pos, _)| *pos)
+        .collect()
+}
+
+/// Counts how often each tile is flipped.
+pub fn get_tile_positions(tile_dirs: &[Vec<Dir>]) -> HashMap<(i64, i64), usize> {
+    tile_dirs.iter().map(|dirs| tile_pos(dirs)).fold(
+        HashMap::with_capacity(tile_dirs.len()),
+        |mut m, dir| {
+            *m.entry(dir).or_insert(0) += 1;
+            m
+        },
+    )
+}
+
+/// Follows the directions from the reference tile and returns the axial coordinates of the
+/// tile.
+pub fn tile_pos(dirs: &[Dir]) -> (i64, i64) {
+    dirs.iter()
+        .map(|dir| match dir {
+            Dir::E => (1, 0),
+            Dir::W => (-1, 0),
+            Dir::NE => (1, -1),
+            Dir::NW => (0, -1),
+            Dir::SE => (0, 1),
+            Dir::SW => (-1, 1),
+        })
+        .fold((0, 0), |(px, py), (dx, dy)| (dx + px, dy + py))
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+/// A step to one of the six neighbours of a hexagonal tile
+pub enum Dir {
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE,
+}
+
+/// Writes the direction as in the puzzle input, e.g. `ne`
+impl fmt::Display for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let dir = match self {
+            Dir::E => "e",
+            Dir::SE => "se",
+            Dir::SW => "sw",
+            Dir::W => "w",
+            Dir::NW => "nw",
+            Dir::NE => "ne",
+        };
+        write!(f, "{}", dir)
+    }
+}
+
+/// Parses the directions to the tiles to flip, one tile like `esenee` per line.
+pub fn parse_tiles(input: &str) -> Result<Vec<Vec<Dir>>, ParseError> {
+    input
+        .split_terminator('\n')
+        .map(parse_tile_directions)
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+enum Prefix {
+    S,
+    N,
+}
+
+fn parse_tile_directions(line: &str) -> Result<Vec<Dir>, ParseError> {
+    let mut directions = Vec::with_capacity(line.len());
+    let mut prev = None;
+    for (i, c) in line.char_indices() {
+        let token = &line[i..i + c.len_utf8()];
+        match c {
+            's' => {
+                prev = {
+                    if prev.is_none() {
+                        Some(Prefix::S)
+                    } else {
+                        return Err(ParseError::unexpected_token(
+                            token,
+                            "'e' or 'w' after prefix",
+                        ));
+                    }
+                }
+            }
+            'n' => {
+                prev = {
+                    if prev.is_none() {
+                        Some(Prefix::N)
+                    } else {
+                        return Err(ParseError::unexpected_token(
+                            token,
+                            "'e' or 'w' after prefix",
+                        ));
+                    }
+                }
+            }
+            'e' => {
+                let dir = match prev {
+                    Some(Prefix::N) => Dir::NE,
+                    Some(Prefix::S) => Dir::SE,
+                    None => Dir::E,
+                };
+                directions.push(dir);
+                prev = None;
+            }
+            'w' => {
+                let dir = match prev {
+                    Some(Prefix::N) => Dir::NW,
+                    Some(Prefix::S) => Dir::SW,
+                    None => Dir::W,
+                };
+                directions.push(dir);
+                prev = None;
+            }
+            _ => return Err(ParseError::unexpected_token(token, "a direction")),
+        }
+    }
+    if prev.is_some() {
+        Err(ParseError::missing_field(
+            end_of(line),
+            "'e' or 'w' after prefix",
+        ))
+    } else {
+        Ok(directions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::generate::{Generator, Rng};
+
+    const INPUT: &str = r"sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew";
+
+    #[test]
+    fn get_tile_positions_works_for_example() {
+        // given
+        let tile_dirs = parse_tiles(INPUT).expect("Expected example input to parse");
+
+        // when
+        let tile_positions = get_tile_positions(&tile_dirs);
+        let black_tiles = get_black_tiles(&tile_positions);
+
+        // then
+        assert_eq!(black_tiles.len(), 10);
+    }
+
+    #[test]
+    fn tile_pos_works_for_examples() {
+        // given
+        let directions = parse_tile_directions("nwwswee").expect("expected example input to parse");
+
+        // when
+        let (px, py) = tile_pos(&directions);
+
+        // then
+        assert_eq!(px, 0);
+        assert_eq!(py, 0);
+    }
+
+    #[test]
+    fn run_100_days_works_for_example() {
+        // given
+        let tile_dirs = parse_tiles(INPUT).expect("Expected example input to parse");
+        let tile_positions = get_tile_positions(&tile_dirs);
+        let black_tiles = get_black_tiles(&tile_positions);
+
+        // when
+        let after_100 = run_100_days(black_tiles);
+
+        // then
+        assert_eq!(after_100.len(), 2208);
+    }
+
+    #[test]
+    fn parse_inverts_display_for_generated_tiles() {
+        for seed in 0..10 {
+            // given
+            let input = Day24::generate(&mut Rng::new(seed), 50).input;
+            let tiles = parse_tiles(&input).expect("Expected valid generated input");
+
+            // when
+            let written: String = tiles
+                .iter()
+                .map(|dirs| dirs.iter().map(|dir| dir.to_string()).collect::<String>() + "\n")
+                .collect();
+            let reparsed = parse_tiles(&written);
+
+            // then
+            assert_eq!(reparsed, Ok(tiles));
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{get_black_tiles, get_tile_positions, lobby, Day24, Dir};
+use common::automaton::{Automaton, Hex, LifeLike};
+use common::trace::{Frame, Picture, Trace};
+
+/// Part 1 has a frame for the floor after each flipped tile, part 2 one for each of the 100 days.
+/// Black tiles are `#`, white tiles `.`, every other row is shifted by half a tile.
+impl Trace for Day24 {
+    fn trace(tile_directions: &Vec<Vec<Dir>>, part: usize) -> Result<Vec<Frame>, String> {
+        if part == 1 {
+            return Ok((0..=tile_directions.len())
+                .map(|flipped| {
+                    let tile_positions = get_tile_positions(&tile_directions[..flipped]);
+                    let floor = lobby(get_black_tiles(&tile_positions));
+                    Frame::new(format!("{} tiles flipped", flipped), draw_floor(&floor))
+                })
+                .collect());
+        }
+
+        let tile_positions = get_tile_positions(tile_directions);
+        let mut floor = lobby(get_black_tiles(&tile_positions));
+        let mut frames: Vec<Frame> = Vec::with_capacity(101);
+        loop {
+            frames.push(Frame::new(
+                format!("day {}", floor.generation()),
+                draw_floor(&floor),
+            ));
+            if floor.generation() == 100 {
+                return Ok(frames);
+            }
+            floor.step();
+        }
+    }
+}
+
+// in axial coordinates, a tile is half a tile further east for each row to the south. Each tile
+// is two characters wide, so the column of a tile is 2 * x + y.
+fn draw_floor(floor: &Automaton<Hex, bool, LifeLike>) -> Picture {
+    let (min_column, max_column, min_y, max_y) = floor.cells().fold(
+        (i64::MAX, i64::MIN, i64::MAX, i64::MIN),
+        |(min_c, max_c, min_y, max_y), ((x, y), _)| {
+            let column = 2 * x + y;
+            (
+                min_c.min(column),
+                max_c.max(column),
+                min_y.min(y),
+                max_y.max(y),
+            )
+        },
+    );
+    Picture::Map(
+        (min_y..=max_y)
+            .map(|y| {
+                (min_column..=max_column)
+                    .map(|column| {
+                        if (column - y).rem_euclid(2) != 0 {
+                            ' '
+                        } else if *floor.get(((column - y) / 2, y)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+                    .trim_end()
+                    .to_owned()
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_tiles;
+
+    #[test]
+    fn trace_draws_flipped_tiles() {
+        // given
+        let tile_directions = parse_tiles("e\nse\nsw\nw\ne\n").expect("Expected valid input");
+
+        // when
+        let frames = Day24::trace(&tile_directions, 1).expect("Expected a trace");
+
+        // then
+        assert_eq!(frames.len(), 6);
+        assert_eq!(frames[0].picture, Picture::Map(vec![]));
+        assert_eq!(
+            frames[4].picture,
+            Picture::Map(vec!["# . #".to_owned(), " # #".to_owned()])
+        );
+        assert_eq!(
+            frames[5].picture,
+            Picture::Map(vec!["# .".to_owned(), " # #".to_owned()])
+        );
+    }
+
+    #[test]
+    fn trace_has_a_frame_for_each_day() {
+        // given
+        let tile_directions = parse_tiles("e\nse\nsw\n").expect("Expected valid input");
+
+        // when
+        let frames = Day24::trace(&tile_directions, 2).expect("Expected a trace");
+
+        // then
+        assert_eq!(frames.len(), 101);
+        assert_eq!(frames[100].label, "day 100");
+    }
+}
@@ -0,0 +1,68 @@
+use crate::Day25;
+use common::generate::{Generated, Generator, Rng};
+
+const MODULUS: u64 = 20201227;
+
+/// `size` is the largest loop size in thousands (at least 1). The generator picks the secret
+/// loop sizes of the card and the door and derives the public keys from them, so the encryption
+/// key is known.
+impl Generator for Day25 {
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        // larger loop sizes would repeat the public keys
+        let max_loop_size = (size.max(1) as u64 * 1000).min(MODULUS - 2);
+        let card_loop_size = rng.below(max_loop_size) + 1;
+        let door_loop_size = rng.below(max_loop_size) + 1;
+        let card_pubkey = pow_mod(7, card_loop_size);
+        let door_pubkey = pow_mod(7, door_loop_size);
+
+        Generated {
+            input: format!("{}\n{}\n", door_pubkey, card_pubkey),
+            part1: Some(pow_mod(door_pubkey, card_loop_size).to_string()),
+            part2: None,
+        }
+    }
+}
+
+// square and multiply
+fn pow_mod(base: u64, mut exponent: u64) -> u64 {
+    let mut result = 1;
+    let mut base = base % MODULUS;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % MODULUS;
+        }
+        base = base * base % MODULUS;
+        exponent >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::fuzz::fuzz_solve;
+    use common::generate::check_planted;
+
+    #[test]
+    fn generated_input_has_planted_answers() {
+        for seed in 0..10 {
+            // given
+            let generated = Day25::generate(&mut Rng::new(seed), 10);
+
+            // when
+            let mismatches = check_planted::<Day25>(&generated);
+
+            // then
+            assert_eq!(mismatches, Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_for_mutated_inputs() {
+        // when
+        let failures = fuzz_solve::<Day25>(0, 200);
+
+        // then
+        assert_eq!(failures, vec![]);
+    }
+}
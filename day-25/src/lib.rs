@@ -0,0 +1,100 @@
+//! Day 25: Combo Breaker
+
+use common::{end_of, parse_int, ParseError, Solution};
+use std::convert::Infallible;
+
+mod generate;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    // door public key and card public key
+    type Input<'a> = (u64, u64);
+    type Answer1 = u64;
+    type Answer2 = Infallible;
+
+    const HAS_PART2: bool = false;
+
+    fn parse(input: &str) -> Result<(u64, u64), ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((door_pubkey, card_pubkey): &(u64, u64)) -> Result<u64, String> {
+        find_encryption_key(*door_pubkey, *card_pubkey)
+            .ok_or_else(|| format!("{} is not a public key", door_pubkey))
+    }
+
+    fn part2(_: &(u64, u64)) -> Result<Infallible, String> {
+        Err("There is no second puzzle on day 25".to_owned())
+    }
+}
+
+/// Parses the public keys of the door and the card.
+pub fn parse_input(input: &str) -> Result<(u64, u64), ParseError> {
+    let mut iter = input.split_terminator('\n');
+    let door_pubkey = iter
+        .next()
+        .ok_or_else(|| ParseError::missing_field(input, "door public key"))
+        .and_then(parse_int::<u64>)?;
+    let card_pubkey = iter
+        .next()
+        .ok_or_else(|| ParseError::missing_field(end_of(input), "card public key"))
+        .and_then(parse_int::<u64>)?;
+    Ok((door_pubkey, card_pubkey))
+}
+
+const MODULUS: u64 = 20201227;
+
+/// Finds the loop size that transforms the subject number into `transformed`. `None` if no loop
+/// size does: the values repeat after fewer than `MODULUS` loops.
+pub fn find_loop_size(subject_number: u64, transformed: u64) -> Option<u64> {
+    let subject_number = subject_number % MODULUS;
+    let mut value = 1;
+    for i in 1..MODULUS {
+        value *= subject_number;
+        value %= MODULUS;
+        if value == transformed {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Transforms the subject number with the given loop size.
+pub fn transform(subject_number: u64, loop_size: u64) -> u64 {
+    let subject_number = subject_number % MODULUS;
+    let mut value = 1;
+    for _ in 0..loop_size {
+        value *= subject_number;
+        value %= MODULUS;
+    }
+    value
+}
+
+/// `None` if the door public key is not a power of 7
+pub fn find_encryption_key(door_pubkey: u64, card_pubkey: u64) -> Option<u64> {
+    let door_loop = find_loop_size(7, door_pubkey)?;
+    Some(transform(card_pubkey, door_loop))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn find_loop_size_works_for_examples() {
+        assert_eq!(find_loop_size(7, 5764801), Some(8));
+        assert_eq!(find_loop_size(7, 17807724), Some(11));
+    }
+
+    #[test]
+    fn find_encryption_key_works_for_example() {
+        assert_eq!(find_encryption_key(5764801, 17807724), Some(14897079));
+    }
+
+    #[test]
+    fn find_loop_size_fails_for_values_out_of_range() {
+        assert_eq!(find_loop_size(7, 0), None);
+        assert_eq!(find_loop_size(7, MODULUS), None);
+    }
+}